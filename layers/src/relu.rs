@@ -17,10 +17,8 @@ impl ReLU {
         [(); D0 * (D1 * D2)]:,
         MetalGpu: Relu<f32>,
     {
-        // Apply ReLU using the tensor's built-in method
-        let result = input.clone().relu();
-        
-        // Update the input with the result
-        *input = result;
+        // Apply ReLU in place. On Metal this swaps in a fresh buffer rather
+        // than writing through one that clones of `input` may share.
+        input.relu_();
     }
-}
\ No newline at end of file
+}
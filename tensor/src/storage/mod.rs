@@ -1,6 +1,7 @@
 #[cfg(target_os = "macos")]
 pub mod metal_gpu;
 pub mod naive_cpu;
+pub mod parallel_cpu;
 
 pub trait HasStorage<T: Copy + Default, const N: usize> {
     type Storage;
@@ -0,0 +1,145 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Multi-threaded CPU backend.
+///
+/// Storage is the same as [`NaiveCpu`](crate::storage::naive_cpu::NaiveCpu)'s
+/// and every op computes the same values; the work is split across scoped
+/// threads. The degree of parallelism is process-wide, see
+/// [`ParallelCpu::set_num_threads`], and can be overridden for a single
+/// calling thread with [`ParallelCpu::with_num_threads`].
+///
+/// Matmul, convolution, elementwise and broadcast ops have parallel kernels;
+/// the rest (reductions, scans, softmax, sort, linalg, ...) run the NaiveCpu
+/// kernel on the calling thread.
+pub struct ParallelCpu;
+
+/// `0` means "use `std::thread::available_parallelism`".
+static NUM_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Override set by [`ParallelCpu::with_num_threads`]; `0` means none.
+    static SCOPED_NUM_THREADS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Restores the previous scoped override, also when the closure panics.
+struct ScopeGuard(usize);
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPED_NUM_THREADS.with(|c| c.set(self.0));
+    }
+}
+
+impl ParallelCpu {
+    /// Sets the number of threads ops may use; `0` restores the default of
+    /// one thread per available core.
+    pub fn set_num_threads(n: usize) {
+        NUM_THREADS.store(n, Ordering::Relaxed);
+    }
+
+    /// Runs `f` with ops issued from the calling thread using `n` threads,
+    /// leaving the process-wide setting and other threads untouched.
+    pub fn with_num_threads<R>(n: usize, f: impl FnOnce() -> R) -> R {
+        let _guard = ScopeGuard(SCOPED_NUM_THREADS.with(|c| c.replace(n)));
+        f()
+    }
+
+    /// Number of threads ops issued from the calling thread may use.
+    pub fn num_threads() -> usize {
+        let scoped = SCOPED_NUM_THREADS.with(Cell::get);
+        if scoped != 0 {
+            return scoped;
+        }
+        match NUM_THREADS.load(Ordering::Relaxed) {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        }
+    }
+}
+
+/// Splits `out` into contiguous runs of whole `unit`-sized items and calls
+/// `f(first_item, run)` for each run on its own scoped thread.
+///
+/// At most [`ParallelCpu::num_threads`] runs are made, each with at least
+/// `min_items` items, so small outputs stay on the calling thread.
+pub(crate) fn for_each_chunk<T, F>(out: &mut [T], unit: usize, min_items: usize, f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut [T]) + Sync,
+{
+    let items = out.len() / unit.max(1);
+    let threads = ParallelCpu::num_threads().min(items / min_items.max(1)).max(1);
+    if threads == 1 {
+        f(0, out);
+        return;
+    }
+    let per_thread = items.div_ceil(threads);
+    std::thread::scope(|s| {
+        for (t, run) in out.chunks_mut(per_thread * unit).enumerate() {
+            let f = &f;
+            s.spawn(move || f(t * per_thread, run));
+        }
+    });
+}
+
+/// Views ParallelCpu storage as NaiveCpu storage (both are `[T; N]`), so ops
+/// without a parallel kernel can run the NaiveCpu one.
+#[inline]
+pub(crate) fn naive<T: Copy + Default, const N: usize>(
+    s: &<ParallelCpu as HasStorage<T, N>>::Storage,
+) -> &<NaiveCpu as HasStorage<T, N>>::Storage {
+    s
+}
+
+/// Mutable counterpart of [`naive`].
+#[inline]
+pub(crate) fn naive_mut<T: Copy + Default, const N: usize>(
+    s: &mut <ParallelCpu as HasStorage<T, N>>::Storage,
+) -> &mut <NaiveCpu as HasStorage<T, N>>::Storage {
+    s
+}
+
+impl<T: Copy + Default, const N: usize> HasStorage<T, N> for ParallelCpu {
+    type Storage = [T; N];
+
+    #[inline]
+    fn storage_from_array(src: [T; N]) -> Self::Storage {
+        src
+    }
+
+    #[inline]
+    fn storage_uninit() -> Self::Storage {
+        [T::default(); N]
+    }
+
+    #[inline]
+    fn storage_zeroes() -> Self::Storage {
+        [T::default(); N]
+    }
+
+    #[inline]
+    fn storage_ones() -> Self::Storage
+    where
+        T: num_traits::One,
+    {
+        [T::one(); N]
+    }
+
+    #[inline]
+    fn storage_full(val: T) -> Self::Storage {
+        [val; N]
+    }
+
+    #[inline]
+    fn as_slice(storage: &Self::Storage) -> &[T] {
+        storage
+    }
+
+    #[inline]
+    fn as_mut_slice(storage: &mut Self::Storage) -> &mut [T] {
+        storage
+    }
+}
@@ -129,6 +129,7 @@ macro_rules! impl_tensor_rank {
     (@prod  $only:ident)                   => { $only };
 }
 
+impl_tensor_rank!(Tensor1, [N]);
 impl_tensor_rank!(Tensor2, [R, C]);
 impl_tensor_rank!(Tensor3, [D0, D1, D2]);
 impl_tensor_rank!(Tensor4, [D0, D1, D3, D4]);
@@ -179,6 +180,7 @@ macro_rules! impl_tensor_display {
     };
 }
 
+impl_tensor_display!(Tensor1, [N]);
 impl_tensor_display!(Tensor2, [R, C]);
 impl_tensor_display!(Tensor3, [D0, D1, D2]);
 impl_tensor_display!(Tensor4, [D0, D1, D3, D4]);
@@ -0,0 +1,90 @@
+//! Batch-by-batch matrix multiplication, where both operands carry the same
+//! leading batch dimensions: `[BATCH, R, C] * [BATCH, C, K]` and
+//! `[B0, B1, R, C] * [B0, B1, C, K]`.
+//!
+//! Unlike [`broadcast_matmul`](crate::tensor_ops::broadcast_matmul), the
+//! right operand is not shared, so batch `i` of the output is `a[i] * b[i]`.
+//! Backend implementers should implement [`BatchedMatMul3`] and
+//! [`BatchedMatMul4`].
+
+pub mod naive_cpu;
+pub mod parallel_cpu;
+
+use crate::storage::HasStorage;
+use crate::tensor::{Tensor3, Tensor4};
+use std::ops::{Add, Mul};
+
+pub trait BatchedMatMul3<T: Copy + Default>: Sized {
+    fn batched_matmul3<const BATCH: usize, const R: usize, const C: usize, const K: usize>(
+        a: &<Self as HasStorage<T, { BATCH * (R * C) }>>::Storage,
+        b: &<Self as HasStorage<T, { BATCH * (C * K) }>>::Storage,
+        out: &mut <Self as HasStorage<T, { BATCH * (R * K) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { BATCH * (R * C) }>
+            + HasStorage<T, { BATCH * (C * K) }>
+            + HasStorage<T, { BATCH * (R * K) }>;
+}
+
+pub trait BatchedMatMul4<T: Copy + Default>: Sized {
+    fn batched_matmul4<
+        const B0: usize,
+        const B1: usize,
+        const R: usize,
+        const C: usize,
+        const K: usize,
+    >(
+        a: &<Self as HasStorage<T, { B0 * (B1 * (R * C)) }>>::Storage,
+        b: &<Self as HasStorage<T, { B0 * (B1 * (C * K)) }>>::Storage,
+        out: &mut <Self as HasStorage<T, { B0 * (B1 * (R * K)) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { B0 * (B1 * (R * C)) }>
+            + HasStorage<T, { B0 * (B1 * (C * K)) }>
+            + HasStorage<T, { B0 * (B1 * (R * K)) }>;
+}
+
+impl_binop! {
+    impl<[T, const BATCH: usize, const R: usize, const C: usize, const K: usize, B]>
+    Mul::mul(Tensor3<T, BATCH, R, C, B>, Tensor3<T, BATCH, C, K, B>) -> Tensor3<T, BATCH, R, K, B>
+    where {
+        T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+        B: HasStorage<T, { BATCH * (R * C) }>
+            + HasStorage<T, { BATCH * (C * K) }>
+            + HasStorage<T, { BATCH * (R * K) }>
+            + BatchedMatMul3<T>,
+    }
+    |a, b| {
+        let mut out: <B as HasStorage<T, { BATCH * (R * K) }>>::Storage =
+            <B as HasStorage<T, { BATCH * (R * K) }>>::storage_uninit();
+
+        B::batched_matmul3::<BATCH, R, C, K>(&a.storage, &b.storage, &mut out);
+
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const B0: usize, const B1: usize, const R: usize, const C: usize, const K: usize, B]>
+    Mul::mul(Tensor4<T, B0, B1, R, C, B>, Tensor4<T, B0, B1, C, K, B>)
+        -> Tensor4<T, B0, B1, R, K, B>
+    where {
+        T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+        B: HasStorage<T, { B0 * (B1 * (R * C)) }>
+            + HasStorage<T, { B0 * (B1 * (C * K)) }>
+            + HasStorage<T, { B0 * (B1 * (R * K)) }>
+            + BatchedMatMul4<T>,
+    }
+    |a, b| {
+        let mut out: <B as HasStorage<T, { B0 * (B1 * (R * K)) }>>::Storage =
+            <B as HasStorage<T, { B0 * (B1 * (R * K)) }>>::storage_uninit();
+
+        B::batched_matmul4::<B0, B1, R, C, K>(&a.storage, &b.storage, &mut out);
+
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::tensor_ops::batched_matmul::{BatchedMatMul3, BatchedMatMul4};
+use crate::tensor_ops::matmul::kernel::matmul_into;
+use core::ops::{Add, Mul};
+
+/// `out[i] = a[i] * b[i]` for each of the `batch` back-to-back `m x k` and
+/// `k x n` matrices.
+pub(crate) fn batched_matmul_into<T>(
+    a: &[T],
+    b: &[T],
+    out: &mut [T],
+    batch: usize,
+    m: usize,
+    k: usize,
+    n: usize,
+) where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + 'static,
+{
+    for i in 0..batch {
+        matmul_into(
+            &a[i * m * k..(i + 1) * m * k],
+            &b[i * k * n..(i + 1) * k * n],
+            &mut out[i * m * n..(i + 1) * m * n],
+            m,
+            k,
+            n,
+        );
+    }
+}
+
+impl<T> BatchedMatMul3<T> for NaiveCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + 'static,
+{
+    fn batched_matmul3<const BATCH: usize, const R: usize, const C: usize, const K: usize>(
+        a: &<Self as HasStorage<T, { BATCH * (R * C) }>>::Storage,
+        b: &<Self as HasStorage<T, { BATCH * (C * K) }>>::Storage,
+        out: &mut <Self as HasStorage<T, { BATCH * (R * K) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { BATCH * (R * C) }>
+            + HasStorage<T, { BATCH * (C * K) }>
+            + HasStorage<T, { BATCH * (R * K) }>,
+    {
+        let a = <Self as HasStorage<T, { BATCH * (R * C) }>>::as_slice(a);
+        let b = <Self as HasStorage<T, { BATCH * (C * K) }>>::as_slice(b);
+        let o = <Self as HasStorage<T, { BATCH * (R * K) }>>::as_mut_slice(out);
+        batched_matmul_into(a, b, o, BATCH, R, C, K);
+    }
+}
+
+impl<T> BatchedMatMul4<T> for NaiveCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + 'static,
+{
+    fn batched_matmul4<
+        const B0: usize,
+        const B1: usize,
+        const R: usize,
+        const C: usize,
+        const K: usize,
+    >(
+        a: &<Self as HasStorage<T, { B0 * (B1 * (R * C)) }>>::Storage,
+        b: &<Self as HasStorage<T, { B0 * (B1 * (C * K)) }>>::Storage,
+        out: &mut <Self as HasStorage<T, { B0 * (B1 * (R * K)) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { B0 * (B1 * (R * C)) }>
+            + HasStorage<T, { B0 * (B1 * (C * K)) }>
+            + HasStorage<T, { B0 * (B1 * (R * K)) }>,
+    {
+        let a = <Self as HasStorage<T, { B0 * (B1 * (R * C)) }>>::as_slice(a);
+        let b = <Self as HasStorage<T, { B0 * (B1 * (C * K)) }>>::as_slice(b);
+        let o = <Self as HasStorage<T, { B0 * (B1 * (R * K)) }>>::as_mut_slice(out);
+        // The two batch dimensions flatten into one.
+        batched_matmul_into(a, b, o, B0 * B1, R, C, K);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::tensor::{Tensor2, Tensor3, Tensor4};
+
+    #[test]
+    fn test_batched_matmul3_matches_per_batch_matmul() {
+        let a: Vec<f32> = (0..3 * 2 * 4).map(|i| (i % 7) as f32 - 3.0).collect();
+        let b: Vec<f32> = (0..3 * 4 * 5).map(|i| (i % 5) as f32 * 0.5).collect();
+        let got = Tensor3::<f32, 3, 2, 4, NaiveCpu>::new_from_slice(&a)
+            * Tensor3::<f32, 3, 4, 5, NaiveCpu>::new_from_slice(&b);
+
+        for i in 0..3 {
+            let want = Tensor2::<f32, 2, 4, NaiveCpu>::new_from_slice(&a[i * 8..(i + 1) * 8])
+                * Tensor2::<f32, 4, 5, NaiveCpu>::new_from_slice(&b[i * 20..(i + 1) * 20]);
+            assert_eq!(&got.as_slice()[i * 10..(i + 1) * 10], want.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_batched_matmul4() {
+        // Batch (i, j) multiplies [[1, 0], [0, 1]] * (i + j) by a 2x1 column of ones.
+        let mut a = [0.0f64; 2 * 3 * 4];
+        for (batch, m) in a.chunks_exact_mut(4).enumerate() {
+            let s = (batch / 3 + batch % 3) as f64;
+            m.copy_from_slice(&[s, 0.0, 0.0, s]);
+        }
+        let a = Tensor4::<f64, 2, 3, 2, 2, NaiveCpu>::new(a);
+        let b = Tensor4::<f64, 2, 3, 2, 1, NaiveCpu>::ones();
+        let out = a * b;
+        let want = [0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0];
+        assert_eq!(out.as_slice(), &want);
+    }
+}
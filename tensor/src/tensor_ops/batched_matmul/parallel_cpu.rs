@@ -0,0 +1,100 @@
+use crate::storage::HasStorage;
+use crate::storage::parallel_cpu::{ParallelCpu, for_each_chunk};
+use crate::tensor_ops::batched_matmul::naive_cpu::batched_matmul_into;
+use crate::tensor_ops::batched_matmul::{BatchedMatMul3, BatchedMatMul4};
+use crate::tensor_ops::matmul::parallel_cpu::MIN_ROWS_PER_THREAD;
+use core::ops::{Add, Mul};
+
+/// [`batched_matmul_into`] with runs of whole batches on separate threads.
+fn par_batched_matmul_into<T>(
+    a: &[T],
+    b: &[T],
+    out: &mut [T],
+    batch: usize,
+    m: usize,
+    k: usize,
+    n: usize,
+) where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync + 'static,
+{
+    let min_batches = MIN_ROWS_PER_THREAD.div_ceil(m.max(1));
+    for_each_chunk(&mut out[..batch * m * n], m * n, min_batches, |first, run| {
+        let count = run.len() / (m * n).max(1);
+        batched_matmul_into(
+            &a[first * m * k..(first + count) * m * k],
+            &b[first * k * n..(first + count) * k * n],
+            run,
+            count,
+            m,
+            k,
+            n,
+        );
+    });
+}
+
+impl<T> BatchedMatMul3<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync + 'static,
+{
+    fn batched_matmul3<const BATCH: usize, const R: usize, const C: usize, const K: usize>(
+        a: &<Self as HasStorage<T, { BATCH * (R * C) }>>::Storage,
+        b: &<Self as HasStorage<T, { BATCH * (C * K) }>>::Storage,
+        out: &mut <Self as HasStorage<T, { BATCH * (R * K) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { BATCH * (R * C) }>
+            + HasStorage<T, { BATCH * (C * K) }>
+            + HasStorage<T, { BATCH * (R * K) }>,
+    {
+        let a = <Self as HasStorage<T, { BATCH * (R * C) }>>::as_slice(a);
+        let b = <Self as HasStorage<T, { BATCH * (C * K) }>>::as_slice(b);
+        let o = <Self as HasStorage<T, { BATCH * (R * K) }>>::as_mut_slice(out);
+        par_batched_matmul_into(a, b, o, BATCH, R, C, K);
+    }
+}
+
+impl<T> BatchedMatMul4<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync + 'static,
+{
+    fn batched_matmul4<
+        const B0: usize,
+        const B1: usize,
+        const R: usize,
+        const C: usize,
+        const K: usize,
+    >(
+        a: &<Self as HasStorage<T, { B0 * (B1 * (R * C)) }>>::Storage,
+        b: &<Self as HasStorage<T, { B0 * (B1 * (C * K)) }>>::Storage,
+        out: &mut <Self as HasStorage<T, { B0 * (B1 * (R * K)) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { B0 * (B1 * (R * C)) }>
+            + HasStorage<T, { B0 * (B1 * (C * K)) }>
+            + HasStorage<T, { B0 * (B1 * (R * K)) }>,
+    {
+        let a = <Self as HasStorage<T, { B0 * (B1 * (R * C)) }>>::as_slice(a);
+        let b = <Self as HasStorage<T, { B0 * (B1 * (C * K)) }>>::as_slice(b);
+        let o = <Self as HasStorage<T, { B0 * (B1 * (R * K)) }>>::as_mut_slice(out);
+        par_batched_matmul_into(a, b, o, B0 * B1, R, C, K);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::storage::parallel_cpu::ParallelCpu;
+    use crate::tensor::Tensor3;
+
+    #[test]
+    fn test_matches_naive_cpu() {
+        ParallelCpu::with_num_threads(3, || {
+            let a: Vec<f32> = (0..7 * 4 * 6).map(|i| (i % 17) as f32 * 0.25 - 2.0).collect();
+            let b: Vec<f32> = (0..7 * 6 * 3).map(|i| (i % 5) as f32 - 1.5).collect();
+
+            let want = Tensor3::<f32, 7, 4, 6, NaiveCpu>::new_from_slice(&a)
+                * Tensor3::<f32, 7, 6, 3, NaiveCpu>::new_from_slice(&b);
+            let got = Tensor3::<f32, 7, 4, 6, ParallelCpu>::new_from_slice(&a)
+                * Tensor3::<f32, 7, 6, 3, ParallelCpu>::new_from_slice(&b);
+            assert_eq!(got.as_slice(), want.as_slice());
+        });
+    }
+}
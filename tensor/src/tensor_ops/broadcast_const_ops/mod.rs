@@ -1,302 +1,319 @@
+//! Numpy-style broadcasting arithmetic between tensors of any rank.
+//!
+//! Shapes are right-aligned and every pair of dimensions must either match or
+//! contain a `1`, which is expanded to the other size. Compatibility is checked
+//! at compile time through [`broadcast_dim`]; mismatched shapes fail to build.
+//!
+//! The named [`BroadcastAdd`], [`BroadcastSub`], [`BroadcastMul`] and
+//! [`BroadcastDiv`] methods broadcast between every pair of ranks, including
+//! equal ranks. The `+`, `-` and `/` operators broadcast only between tensors
+//! of different rank; between equal ranks they are the same-shape operators of
+//! [`elemwise`](crate::tensor_ops::elemwise), which return `Self`. `*` also
+//! broadcasts for `Tensor4 * Tensor3`, the only rank pair where it is not a
+//! (batched) matrix product; elsewhere use [`BroadcastMul`].
+//!
+//! `+=` and `-=` accept a lower-rank right operand that broadcasts to the left
+//! operand's shape, such as a `[C, 1, 1]` bias on an `[N, C, H, W]` tensor. A
+//! right operand that would grow the left one fails to build.
+//!
+//! Backend implementers should implement [`BroadcastConstAdd`],
+//! [`BroadcastConstSub`], [`BroadcastConstMul`] and [`BroadcastConstDiv`] for
+//! their backend.
+
 pub mod naive_cpu;
+pub mod parallel_cpu;
 
 use crate::storage::HasStorage;
 use crate::tensor::{Tensor2, Tensor3, Tensor4};
-use core::ops::{Add, Div, Mul, Sub};
+use core::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
 
-pub trait BroadcastConstAdd<T: Copy + Default>: Sized {
-    fn add43<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * (D3 * D4) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D1 * (D3 * D4) }>;
+/// Size of one output dimension when broadcasting dimensions `a` and `b`.
+///
+/// Panics (and therefore fails compilation when used in a type) if the two
+/// sizes differ and neither is `1`.
+pub const fn broadcast_dim(a: usize, b: usize) -> usize {
+    if a == b || b == 1 {
+        a
+    } else if a == 1 {
+        b
+    } else {
+        panic!("incompatible dimensions for broadcasting")
+    }
+}
 
-    fn add42<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D3 * D4 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
+/// Panics (failing the build where evaluated in a `const` block) unless
+/// broadcasting leaves a left-operand dimension `a` unchanged as `out`, as
+/// compound assignment requires.
+const fn assert_keeps_dim(a: usize, out: usize) {
+    if a != out {
+        panic!("right operand does not broadcast to the left operand's shape");
+    }
+}
+
+/// Backend trait for broadcasting addition.
+///
+/// `a_shape` and `b_shape` are the logical shapes of `a` and `b`; they may have
+/// different ranks and are right-aligned against each other.
+pub trait BroadcastConstAdd<T: Copy + Default>: Sized {
+    fn broadcast_add<const NA: usize, const NB: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D3 * D4 }>;
+        T: Add<Output = T>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>;
 
-    fn add32<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * D2 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
+    /// In-place variant: `a = a + b`, where `b` broadcasts to `a_shape`.
+    fn broadcast_add_assign<const NA: usize, const NB: usize>(
+        a: &mut <Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>;
+        T: Add<Output = T>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB>;
 }
 
+/// Backend trait for broadcasting subtraction.
 pub trait BroadcastConstSub<T: Copy + Default>: Sized {
-    fn sub43<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * (D3 * D4) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
+    fn broadcast_sub<const NA: usize, const NB: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D1 * (D3 * D4) }>;
+        T: Sub<Output = T>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>;
 
-    fn sub42<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D3 * D4 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
+    /// In-place variant: `a = a - b`, where `b` broadcasts to `a_shape`.
+    fn broadcast_sub_assign<const NA: usize, const NB: usize>(
+        a: &mut <Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D3 * D4 }>;
-
-    fn sub32<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * D2 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>;
+        T: Sub<Output = T>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB>;
 }
 
+/// Backend trait for broadcasting multiplication.
 pub trait BroadcastConstMul<T: Copy + Default>: Sized {
-    fn mul43<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * (D3 * D4) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D1 * (D3 * D4) }>;
-
-    fn mul42<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D3 * D4 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D3 * D4 }>;
-
-    fn mul32<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * D2 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
+    fn broadcast_mul<const NA: usize, const NB: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>;
+        T: Mul<Output = T>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>;
 }
 
+/// Backend trait for broadcasting division.
 pub trait BroadcastConstDiv<T: Copy + Default>: Sized {
-    fn div43<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * (D3 * D4) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D1 * (D3 * D4) }>;
-
-    fn div42<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D3 * D4 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D3 * D4 }>;
-
-    fn div32<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * D2 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
+    fn broadcast_div<const NA: usize, const NB: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>;
+        T: Div<Output = T>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>;
 }
 
-impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
-    Add<Tensor3<T, D1, D3, D4, B>> for Tensor4<T, D0, D1, D3, D4, B>
-where
-    T: Copy + Default + Add<Output = T>,
-    B: BroadcastConstAdd<T>
-        + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>
-        + HasStorage<T, { D1 * (D3 * D4) }>,
-{
-    type Output = Tensor4<T, D0, D1, D3, D4, B>;
-    #[inline]
-    fn add(self, rhs: Tensor3<T, D1, D3, D4, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
-        B::add43::<D0, D1, D3, D4>(&self.storage, &rhs.storage, &mut out);
-        Tensor4 { storage: out, _p: core::marker::PhantomData }
-    }
+/// Broadcasting addition, available between every pair of tensor ranks.
+pub trait BroadcastAdd<Rhs> {
+    type Output;
+    fn broadcast_add(self, rhs: Rhs) -> Self::Output;
 }
 
-impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
-    Sub<Tensor3<T, D1, D3, D4, B>> for Tensor4<T, D0, D1, D3, D4, B>
-where
-    T: Copy + Default + Sub<Output = T>,
-    B: BroadcastConstSub<T>
-        + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>
-        + HasStorage<T, { D1 * (D3 * D4) }>,
-{
-    type Output = Tensor4<T, D0, D1, D3, D4, B>;
-    #[inline]
-    fn sub(self, rhs: Tensor3<T, D1, D3, D4, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
-        B::sub43::<D0, D1, D3, D4>(&self.storage, &rhs.storage, &mut out);
-        Tensor4 { storage: out, _p: core::marker::PhantomData }
-    }
+/// Broadcasting subtraction, available between every pair of tensor ranks.
+pub trait BroadcastSub<Rhs> {
+    type Output;
+    fn broadcast_sub(self, rhs: Rhs) -> Self::Output;
 }
 
-impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
-    Mul<Tensor3<T, D1, D3, D4, B>> for Tensor4<T, D0, D1, D3, D4, B>
-where
-    T: Copy + Default + Mul<Output = T>,
-    B: BroadcastConstMul<T>
-        + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>
-        + HasStorage<T, { D1 * (D3 * D4) }>,
-{
-    type Output = Tensor4<T, D0, D1, D3, D4, B>;
-    #[inline]
-    fn mul(self, rhs: Tensor3<T, D1, D3, D4, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
-        B::mul43::<D0, D1, D3, D4>(&self.storage, &rhs.storage, &mut out);
-        Tensor4 { storage: out, _p: core::marker::PhantomData }
-    }
+/// Broadcasting multiplication, available between every pair of tensor ranks.
+pub trait BroadcastMul<Rhs> {
+    type Output;
+    fn broadcast_mul(self, rhs: Rhs) -> Self::Output;
 }
 
-impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
-    Div<Tensor3<T, D1, D3, D4, B>> for Tensor4<T, D0, D1, D3, D4, B>
-where
-    T: Copy + Default + Div<Output = T>,
-    B: BroadcastConstDiv<T>
-        + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>
-        + HasStorage<T, { D1 * (D3 * D4) }>,
-{
-    type Output = Tensor4<T, D0, D1, D3, D4, B>;
-    #[inline]
-    fn div(self, rhs: Tensor3<T, D1, D3, D4, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
-        B::div43::<D0, D1, D3, D4>(&self.storage, &rhs.storage, &mut out);
-        Tensor4 { storage: out, _p: core::marker::PhantomData }
-    }
+/// Broadcasting division, available between every pair of tensor ranks.
+pub trait BroadcastDiv<Rhs> {
+    type Output;
+    fn broadcast_div(self, rhs: Rhs) -> Self::Output;
 }
 
-impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
-    Add<Tensor2<T, D3, D4, B>> for Tensor4<T, D0, D1, D3, D4, B>
-where
-    T: Copy + Default + Add<Output = T>,
-    B: BroadcastConstAdd<T>
-        + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>
-        + HasStorage<T, { D3 * D4 }>,
-{
-    type Output = Tensor4<T, D0, D1, D3, D4, B>;
-    #[inline]
-    fn add(self, rhs: Tensor2<T, D3, D4, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
-        B::add42::<D0, D1, D3, D4>(&self.storage, &rhs.storage, &mut out);
-        Tensor4 { storage: out, _p: core::marker::PhantomData }
-    }
-}
+macro_rules! impl_broadcast {
+    // Implements the `Broadcast*` traits for one rank pair, plus the listed
+    // `core::ops` operators for owned and borrowed operands.
+    ($lhs:ident $ad:tt, $rhs:ident $bd:tt => $out:ident $od:tt; ops: [$($op:ident),*]) => {
+        impl_broadcast!(@impl BroadcastAdd, broadcast_add, BroadcastConstAdd, broadcast_add, Add, $lhs $ad, $rhs $bd => $out $od);
+        impl_broadcast!(@impl BroadcastSub, broadcast_sub, BroadcastConstSub, broadcast_sub, Sub, $lhs $ad, $rhs $bd => $out $od);
+        impl_broadcast!(@impl BroadcastMul, broadcast_mul, BroadcastConstMul, broadcast_mul, Mul, $lhs $ad, $rhs $bd => $out $od);
+        impl_broadcast!(@impl BroadcastDiv, broadcast_div, BroadcastConstDiv, broadcast_div, Div, $lhs $ad, $rhs $bd => $out $od);
+        $(impl_broadcast!(@op $op, $lhs $ad, $rhs $bd => $out $od);)*
+    };
 
-impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
-    Sub<Tensor2<T, D3, D4, B>> for Tensor4<T, D0, D1, D3, D4, B>
-where
-    T: Copy + Default + Sub<Output = T>,
-    B: BroadcastConstSub<T>
-        + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>
-        + HasStorage<T, { D3 * D4 }>,
-{
-    type Output = Tensor4<T, D0, D1, D3, D4, B>;
-    #[inline]
-    fn sub(self, rhs: Tensor2<T, D3, D4, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
-        B::sub42::<D0, D1, D3, D4>(&self.storage, &rhs.storage, &mut out);
-        Tensor4 { storage: out, _p: core::marker::PhantomData }
-    }
-}
+    (@op Add, $($rest:tt)*) => {
+        impl_broadcast!(@binop Add, add, BroadcastConstAdd, broadcast_add, $($rest)*);
+    };
+    (@op Sub, $($rest:tt)*) => {
+        impl_broadcast!(@binop Sub, sub, BroadcastConstSub, broadcast_sub, $($rest)*);
+    };
+    (@op Mul, $($rest:tt)*) => {
+        impl_broadcast!(@binop Mul, mul, BroadcastConstMul, broadcast_mul, $($rest)*);
+    };
+    (@op Div, $($rest:tt)*) => {
+        impl_broadcast!(@binop Div, div, BroadcastConstDiv, broadcast_div, $($rest)*);
+    };
+    (@op AddAssign, $($rest:tt)*) => {
+        impl_broadcast!(@assign AddAssign, add_assign, BroadcastConstAdd, broadcast_add_assign, Add, $($rest)*);
+    };
+    (@op SubAssign, $($rest:tt)*) => {
+        impl_broadcast!(@assign SubAssign, sub_assign, BroadcastConstSub, broadcast_sub_assign, Sub, $($rest)*);
+    };
 
-impl<'a, T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
-    Mul<&'a Tensor2<T, D3, D4, B>> for Tensor4<T, D0, D1, D3, D4, B>
-where
-    T: Copy + Default + Mul<Output = T>,
-    B: BroadcastConstMul<T>
-        + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>
-        + HasStorage<T, { D3 * D4 }>,
-{
-    type Output = Tensor4<T, D0, D1, D3, D4, B>;
-    #[inline]
-    fn mul(self, rhs: &Tensor2<T, D3, D4, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
-        B::mul42::<D0, D1, D3, D4>(&self.storage, &rhs.storage, &mut out);
-        Tensor4 { storage: out, _p: core::marker::PhantomData }
-    }
-}
+    // Compound assignment, for right operands that broadcast to the left
+    // operand's shape.
+    (@assign $tr:ident, $method:ident, $kernel_tr:ident, $kernel:ident, $bound:ident,
+        $lhs:ident [$($a:ident),+], $rhs:ident [$($b:ident),+] => $out:ident [$($o:expr),+]) => {
+        impl_assign_op! {
+            impl<[T, $(const $a: usize,)+ $(const $b: usize,)+ B]>
+            $tr::$method($lhs<T, $($a,)+ B>, $rhs<T, $($b,)+ B>)
+            where {
+                T: Copy + Default + $bound<Output = T>,
+                B: $kernel_tr<T>
+                    + HasStorage<T, { impl_broadcast!(@prod $($a),+) }>
+                    + HasStorage<T, { impl_broadcast!(@prod $($b),+) }>,
+            }
+            |lhs, rhs| {
+                $(const { assert_keeps_dim($a, $o) };)+
+                B::$kernel::<
+                    { impl_broadcast!(@prod $($a),+) },
+                    { impl_broadcast!(@prod $($b),+) },
+                >(&mut lhs.storage, &[$($a),+], &rhs.storage, &[$($b),+]);
+            }
+        }
+    };
 
-impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
-    Div<Tensor2<T, D3, D4, B>> for Tensor4<T, D0, D1, D3, D4, B>
-where
-    T: Copy + Default + Div<Output = T>,
-    B: BroadcastConstDiv<T>
-        + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>
-        + HasStorage<T, { D3 * D4 }>,
-{
-    type Output = Tensor4<T, D0, D1, D3, D4, B>;
-    #[inline]
-    fn div(self, rhs: Tensor2<T, D3, D4, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
-        B::div42::<D0, D1, D3, D4>(&self.storage, &rhs.storage, &mut out);
-        Tensor4 { storage: out, _p: core::marker::PhantomData }
-    }
-}
+    // `core::ops` operator over owned and borrowed operands.
+    (@binop $tr:ident, $method:ident, $kernel_tr:ident, $kernel:ident,
+        $lhs:ident [$($a:ident),+], $rhs:ident [$($b:ident),+] => $out:ident [$($o:expr),+]) => {
+        impl_binop! {
+            impl<[T, $(const $a: usize,)+ $(const $b: usize,)+ B]>
+            $tr::$method($lhs<T, $($a,)+ B>, $rhs<T, $($b,)+ B>) -> $out<T, $({ $o },)+ B>
+            where {
+                T: Copy + Default + $tr<Output = T>,
+                B: $kernel_tr<T>
+                    + HasStorage<T, { impl_broadcast!(@prod $($a),+) }>
+                    + HasStorage<T, { impl_broadcast!(@prod $($b),+) }>
+                    + HasStorage<T, { impl_broadcast!(@prod $($o),+) }>,
+            }
+            |lhs, rhs| {
+                let mut out =
+                    <B as HasStorage<T, { impl_broadcast!(@prod $($o),+) }>>::storage_uninit();
+                B::$kernel::<
+                    { impl_broadcast!(@prod $($a),+) },
+                    { impl_broadcast!(@prod $($b),+) },
+                    { impl_broadcast!(@prod $($o),+) },
+                >(&lhs.storage, &[$($a),+], &rhs.storage, &[$($b),+], &mut out);
+                $out {
+                    storage: out,
+                    _p: core::marker::PhantomData,
+                }
+            }
+        }
+    };
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, B>
-    Add<Tensor2<T, D1, D2, B>> for Tensor3<T, D0, D1, D2, B>
-where
-    T: Copy + Default + Add<Output = T>,
-    B: BroadcastConstAdd<T>
-        + HasStorage<T, { D0 * (D1 * D2) }>
-        + HasStorage<T, { D1 * D2 }>,
-{
-    type Output = Tensor3<T, D0, D1, D2, B>;
-    #[inline]
-    fn add(self, rhs: Tensor2<T, D1, D2, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
-        B::add32::<D0, D1, D2>(&self.storage, &rhs.storage, &mut out);
-        Tensor3 { storage: out, _p: core::marker::PhantomData }
-    }
-}
+    // Named `Broadcast*` trait method.
+    (@impl $tr:ident, $method:ident, $kernel_tr:ident, $kernel:ident, $bound:ident,
+        $lhs:ident [$($a:ident),+], $rhs:ident [$($b:ident),+] => $out:ident [$($o:expr),+]) => {
+        impl<T, $(const $a: usize,)+ $(const $b: usize,)+ B> $tr<$rhs<T, $($b,)+ B>>
+            for $lhs<T, $($a,)+ B>
+        where
+            T: Copy + Default + $bound<Output = T>,
+            B: $kernel_tr<T>
+                + HasStorage<T, { impl_broadcast!(@prod $($a),+) }>
+                + HasStorage<T, { impl_broadcast!(@prod $($b),+) }>
+                + HasStorage<T, { impl_broadcast!(@prod $($o),+) }>,
+        {
+            type Output = $out<T, $({ $o },)+ B>;
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, B>
-    Sub<Tensor2<T, D1, D2, B>> for Tensor3<T, D0, D1, D2, B>
-where
-    T: Copy + Default + Sub<Output = T>,
-    B: BroadcastConstSub<T>
-        + HasStorage<T, { D0 * (D1 * D2) }>
-        + HasStorage<T, { D1 * D2 }>,
-{
-    type Output = Tensor3<T, D0, D1, D2, B>;
-    #[inline]
-    fn sub(self, rhs: Tensor2<T, D1, D2, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
-        B::sub32::<D0, D1, D2>(&self.storage, &rhs.storage, &mut out);
-        Tensor3 { storage: out, _p: core::marker::PhantomData }
-    }
-}
+            #[inline]
+            fn $method(self, rhs: $rhs<T, $($b,)+ B>) -> Self::Output {
+                let mut out =
+                    <B as HasStorage<T, { impl_broadcast!(@prod $($o),+) }>>::storage_uninit();
+                B::$kernel::<
+                    { impl_broadcast!(@prod $($a),+) },
+                    { impl_broadcast!(@prod $($b),+) },
+                    { impl_broadcast!(@prod $($o),+) },
+                >(&self.storage, &[$($a),+], &rhs.storage, &[$($b),+], &mut out);
+                $out {
+                    storage: out,
+                    _p: core::marker::PhantomData,
+                }
+            }
+        }
+    };
 
-impl<'a, T, const D0: usize, const D1: usize, const D2: usize, B>
-    Mul<&'a Tensor2<T, D1, D2, B>> for Tensor3<T, D0, D1, D2, B>
-where
-    T: Copy + Default + Mul<Output = T>,
-    B: BroadcastConstMul<T>
-        + HasStorage<T, { D0 * (D1 * D2) }>
-        + HasStorage<T, { D1 * D2 }>,
-{
-    type Output = Tensor3<T, D0, D1, D2, B>;
-    #[inline]
-    fn mul(self, rhs: &Tensor2<T, D1, D2, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
-        B::mul32::<D0, D1, D2>(&self.storage, &rhs.storage, &mut out);
-        Tensor3 { storage: out, _p: core::marker::PhantomData }
-    }
+    (@prod $first:expr $(,$rest:expr)+) => { $first * impl_broadcast!(@prod $($rest),+) };
+    (@prod $only:expr) => { $only };
 }
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, B>
-    Div<Tensor2<T, D1, D2, B>> for Tensor3<T, D0, D1, D2, B>
-where
-    T: Copy + Default + Div<Output = T>,
-    B: BroadcastConstDiv<T>
-        + HasStorage<T, { D0 * (D1 * D2) }>
-        + HasStorage<T, { D1 * D2 }>,
-{
-    type Output = Tensor3<T, D0, D1, D2, B>;
-    #[inline]
-    fn div(self, rhs: Tensor2<T, D1, D2, B>) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
-        B::div32::<D0, D1, D2>(&self.storage, &rhs.storage, &mut out);
-        Tensor3 { storage: out, _p: core::marker::PhantomData }
-    }
-}
+impl_broadcast!(
+    Tensor2 [A0, A1], Tensor2 [B0, B1]
+        => Tensor2 [broadcast_dim(A0, B0), broadcast_dim(A1, B1)];
+    ops: []
+);
+impl_broadcast!(
+    Tensor3 [A0, A1, A2], Tensor3 [B0, B1, B2]
+        => Tensor3 [broadcast_dim(A0, B0), broadcast_dim(A1, B1), broadcast_dim(A2, B2)];
+    ops: []
+);
+impl_broadcast!(
+    Tensor4 [A0, A1, A2, A3], Tensor4 [B0, B1, B2, B3]
+        => Tensor4 [
+            broadcast_dim(A0, B0),
+            broadcast_dim(A1, B1),
+            broadcast_dim(A2, B2),
+            broadcast_dim(A3, B3)
+        ];
+    ops: []
+);
 
+impl_broadcast!(
+    Tensor3 [A0, A1, A2], Tensor2 [B0, B1]
+        => Tensor3 [A0, broadcast_dim(A1, B0), broadcast_dim(A2, B1)];
+    ops: [Add, Sub, Div, AddAssign, SubAssign]
+);
+impl_broadcast!(
+    Tensor2 [A0, A1], Tensor3 [B0, B1, B2]
+        => Tensor3 [B0, broadcast_dim(A0, B1), broadcast_dim(A1, B2)];
+    ops: [Add, Sub, Div]
+);
+impl_broadcast!(
+    Tensor4 [A0, A1, A2, A3], Tensor2 [B0, B1]
+        => Tensor4 [A0, A1, broadcast_dim(A2, B0), broadcast_dim(A3, B1)];
+    ops: [Add, Sub, Div, AddAssign, SubAssign]
+);
+impl_broadcast!(
+    Tensor2 [A0, A1], Tensor4 [B0, B1, B2, B3]
+        => Tensor4 [B0, B1, broadcast_dim(A0, B2), broadcast_dim(A1, B3)];
+    ops: [Add, Sub, Div]
+);
+impl_broadcast!(
+    Tensor4 [A0, A1, A2, A3], Tensor3 [B0, B1, B2]
+        => Tensor4 [A0, broadcast_dim(A1, B0), broadcast_dim(A2, B1), broadcast_dim(A3, B2)];
+    ops: [Add, Sub, Mul, Div, AddAssign, SubAssign]
+);
+impl_broadcast!(
+    Tensor3 [A0, A1, A2], Tensor4 [B0, B1, B2, B3]
+        => Tensor4 [B0, broadcast_dim(A0, B1), broadcast_dim(A1, B2), broadcast_dim(A2, B3)];
+    ops: [Add, Sub, Div]
+);
@@ -3,248 +3,274 @@ use crate::storage::HasStorage;
 use crate::tensor_ops::broadcast_const_ops::{
     BroadcastConstAdd, BroadcastConstDiv, BroadcastConstMul, BroadcastConstSub,
 };
+use core::ops::{Add, Div, Mul, Sub};
 
-impl<T> BroadcastConstAdd<T> for NaiveCpu
-where
-    T: Copy + Default + core::ops::Add<Output = T>,
-{
-    fn add43<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * (D3 * D4) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D1 * (D3 * D4) }>,
-    {
-        let a = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D1 * (D3 * D4) }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            let base = i0 * (D1 * (D3 * D4));
-            for i in 0..(D1 * (D3 * D4)) {
-                o[base + i] = a[base + i] + b[i];
-            }
+/// Left-pad `shape` with ones up to rank 4.
+pub(crate) fn pad4(shape: &[usize]) -> [usize; 4] {
+    let mut padded = [1usize; 4];
+    padded[4 - shape.len()..].copy_from_slice(shape);
+    padded
+}
+
+/// Row-major strides for `shape`, with stride 0 on size-1 (broadcast) axes.
+pub(crate) fn broadcast_strides(shape: [usize; 4]) -> [usize; 4] {
+    let mut strides = [0usize; 4];
+    let mut acc = 1;
+    for d in (0..4).rev() {
+        strides[d] = if shape[d] == 1 { 0 } else { acc };
+        acc *= shape[d];
+    }
+    strides
+}
+
+/// Apply `f` elementwise over the broadcast of `a` and `b` into `out`.
+fn broadcast_zip<T: Copy>(
+    a: &[T],
+    a_shape: &[usize],
+    b: &[T],
+    b_shape: &[usize],
+    out: &mut [T],
+    f: impl Fn(T, T) -> T,
+) {
+    if a_shape == b_shape {
+        for (o, (&x, &y)) in out.iter_mut().zip(a.iter().zip(b)) {
+            *o = f(x, y);
         }
+        return;
     }
 
-    fn add42<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D3 * D4 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D3 * D4 }>,
-    {
-        let a = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D3 * D4 }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i1 in 0..D1 {
-                let base = (i0 * D1 + i1) * (D3 * D4);
-                for i in 0..(D3 * D4) {
-                    o[base + i] = a[base + i] + b[i];
+    let sa = pad4(a_shape);
+    let sb = pad4(b_shape);
+    let so: [usize; 4] = core::array::from_fn(|d| sa[d].max(sb[d]));
+    let ta = broadcast_strides(sa);
+    let tb = broadcast_strides(sb);
+
+    let mut o = 0;
+    for i0 in 0..so[0] {
+        for i1 in 0..so[1] {
+            for i2 in 0..so[2] {
+                for i3 in 0..so[3] {
+                    let ia = i0 * ta[0] + i1 * ta[1] + i2 * ta[2] + i3 * ta[3];
+                    let ib = i0 * tb[0] + i1 * tb[1] + i2 * tb[2] + i3 * tb[3];
+                    out[o] = f(a[ia], b[ib]);
+                    o += 1;
                 }
             }
         }
     }
+}
 
-    fn add32<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * D2 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>,
-    {
-        let a = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D1 * D2 }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            let base = i0 * (D1 * D2);
-            for i in 0..(D1 * D2) {
-                o[base + i] = a[base + i] + b[i];
-            }
+/// Apply `f` to `a` and the broadcast of `b` against `a`'s shape, writing the
+/// result back into `a`.
+fn broadcast_zip_assign<T: Copy>(
+    a: &mut [T],
+    a_shape: &[usize],
+    b: &[T],
+    b_shape: &[usize],
+    f: impl Fn(T, T) -> T,
+) {
+    if a_shape == b_shape {
+        for (x, &y) in a.iter_mut().zip(b) {
+            *x = f(*x, y);
         }
+        return;
     }
-}
 
-impl<T> BroadcastConstSub<T> for NaiveCpu
-where
-    T: Copy + Default + core::ops::Sub<Output = T>,
-{
-    fn sub43<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * (D3 * D4) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D1 * (D3 * D4) }>,
-    {
-        let a = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D1 * (D3 * D4) }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            let base = i0 * (D1 * (D3 * D4));
-            for i in 0..(D1 * (D3 * D4)) {
-                o[base + i] = a[base + i] - b[i];
+    let sa = pad4(a_shape);
+    let tb = broadcast_strides(pad4(b_shape));
+
+    let mut o = 0;
+    for i0 in 0..sa[0] {
+        for i1 in 0..sa[1] {
+            for i2 in 0..sa[2] {
+                for i3 in 0..sa[3] {
+                    let ib = i0 * tb[0] + i1 * tb[1] + i2 * tb[2] + i3 * tb[3];
+                    a[o] = f(a[o], b[ib]);
+                    o += 1;
+                }
             }
         }
     }
+}
 
-    fn sub42<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D3 * D4 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
+impl<T> BroadcastConstAdd<T> for NaiveCpu
+where
+    T: Copy + Default + Add<Output = T>,
+{
+    fn broadcast_add<const NA: usize, const NB: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D3 * D4 }>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>,
     {
-        let a = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D3 * D4 }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i1 in 0..D1 {
-                let base = (i0 * D1 + i1) * (D3 * D4);
-                for i in 0..(D3 * D4) {
-                    o[base + i] = a[base + i] - b[i];
-                }
-            }
-        }
+        let a = <Self as HasStorage<T, NA>>::as_slice(a);
+        let b = <Self as HasStorage<T, NB>>::as_slice(b);
+        let o = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+        broadcast_zip(a, a_shape, b, b_shape, o, |x, y| x + y);
     }
 
-    fn sub32<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * D2 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
+    fn broadcast_add_assign<const NA: usize, const NB: usize>(
+        a: &mut <Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB>,
     {
-        let a = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D1 * D2 }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            let base = i0 * (D1 * D2);
-            for i in 0..(D1 * D2) {
-                o[base + i] = a[base + i] - b[i];
-            }
-        }
+        let a = <Self as HasStorage<T, NA>>::as_mut_slice(a);
+        let b = <Self as HasStorage<T, NB>>::as_slice(b);
+        broadcast_zip_assign(a, a_shape, b, b_shape, |x, y| x + y);
     }
 }
 
-impl<T> BroadcastConstMul<T> for NaiveCpu
+impl<T> BroadcastConstSub<T> for NaiveCpu
 where
-    T: Copy + Default + core::ops::Mul<Output = T>,
+    T: Copy + Default + Sub<Output = T>,
 {
-    fn mul43<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * (D3 * D4) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
+    fn broadcast_sub<const NA: usize, const NB: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D1 * (D3 * D4) }>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>,
     {
-        let a = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D1 * (D3 * D4) }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            let base = i0 * (D1 * (D3 * D4));
-            for i in 0..(D1 * (D3 * D4)) {
-                o[base + i] = a[base + i] * b[i];
-            }
-        }
+        let a = <Self as HasStorage<T, NA>>::as_slice(a);
+        let b = <Self as HasStorage<T, NB>>::as_slice(b);
+        let o = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+        broadcast_zip(a, a_shape, b, b_shape, o, |x, y| x - y);
     }
 
-    fn mul42<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D3 * D4 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
+    fn broadcast_sub_assign<const NA: usize, const NB: usize>(
+        a: &mut <Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D3 * D4 }>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB>,
     {
-        let a = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D3 * D4 }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i1 in 0..D1 {
-                let base = (i0 * D1 + i1) * (D3 * D4);
-                for i in 0..(D3 * D4) {
-                    o[base + i] = a[base + i] * b[i];
-                }
-            }
-        }
+        let a = <Self as HasStorage<T, NA>>::as_mut_slice(a);
+        let b = <Self as HasStorage<T, NB>>::as_slice(b);
+        broadcast_zip_assign(a, a_shape, b, b_shape, |x, y| x - y);
     }
+}
 
-    fn mul32<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * D2 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
+impl<T> BroadcastConstMul<T> for NaiveCpu
+where
+    T: Copy + Default + Mul<Output = T>,
+{
+    fn broadcast_mul<const NA: usize, const NB: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>,
     {
-        let a = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D1 * D2 }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            let base = i0 * (D1 * D2);
-            for i in 0..(D1 * D2) {
-                o[base + i] = a[base + i] * b[i];
-            }
-        }
+        let a = <Self as HasStorage<T, NA>>::as_slice(a);
+        let b = <Self as HasStorage<T, NB>>::as_slice(b);
+        let o = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+        broadcast_zip(a, a_shape, b, b_shape, o, |x, y| x * y);
     }
 }
 
 impl<T> BroadcastConstDiv<T> for NaiveCpu
 where
-    T: Copy + Default + core::ops::Div<Output = T>,
+    T: Copy + Default + Div<Output = T>,
 {
-    fn div43<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * (D3 * D4) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
+    fn broadcast_div<const NA: usize, const NB: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        a_shape: &[usize],
+        b: &<Self as HasStorage<T, NB>>::Storage,
+        b_shape: &[usize],
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D1 * (D3 * D4) }>,
+        Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>,
     {
-        let a = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D1 * (D3 * D4) }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            let base = i0 * (D1 * (D3 * D4));
-            for i in 0..(D1 * (D3 * D4)) {
-                o[base + i] = a[base + i] / b[i];
-            }
-        }
+        let a = <Self as HasStorage<T, NA>>::as_slice(a);
+        let b = <Self as HasStorage<T, NB>>::as_slice(b);
+        let o = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+        broadcast_zip(a, a_shape, b, b_shape, o, |x, y| x / y);
     }
+}
 
-    fn div42<const D0: usize, const D1: usize, const D3: usize, const D4: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-        b: &<Self as HasStorage<T, { D3 * D4 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * (D3 * D4)) }> + HasStorage<T, { D3 * D4 }>,
-    {
-        let a = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D3 * D4 }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i1 in 0..D1 {
-                let base = (i0 * D1 + i1) * (D3 * D4);
-                for i in 0..(D3 * D4) {
-                    o[base + i] = a[base + i] / b[i];
-                }
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Tensor2, Tensor3, Tensor4};
+    use crate::tensor_ops::broadcast_const_ops::{BroadcastAdd, BroadcastMul, BroadcastSub};
+
+    #[test]
+    fn test_channel_bias_4d() {
+        let x = Tensor4::<i32, 2, 2, 1, 2, NaiveCpu>::new([1, 2, 3, 4, 5, 6, 7, 8]);
+        let bias = Tensor3::<i32, 2, 1, 1, NaiveCpu>::new([10, 20]);
+        let y = x + bias;
+        assert_eq!(y.as_slice(), &[11, 12, 23, 24, 15, 16, 27, 28]);
+
+        let scale = Tensor3::<i32, 2, 1, 1, NaiveCpu>::new([2, 3]);
+        assert_eq!((x * scale).as_slice(), &[2, 4, 9, 12, 10, 12, 21, 24]);
     }
 
-    fn div32<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        b: &<Self as HasStorage<T, { D1 * D2 }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>,
-    {
-        let a = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { D1 * D2 }>>::as_slice(b);
-        let o = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            let base = i0 * (D1 * D2);
-            for i in 0..(D1 * D2) {
-                o[base + i] = a[base + i] / b[i];
-            }
-        }
+    #[test]
+    fn test_broadcast_compound_assign() {
+        let mut x = Tensor4::<i32, 1, 2, 2, 2, NaiveCpu>::new([1, 2, 3, 4, 5, 6, 7, 8]);
+        x += Tensor3::<i32, 2, 1, 1, NaiveCpu>::new([10, 20]);
+        assert_eq!(x.as_slice(), &[11, 12, 13, 14, 25, 26, 27, 28]);
+        x -= &Tensor2::<i32, 1, 2, NaiveCpu>::new([1, 2]);
+        assert_eq!(x.as_slice(), &[10, 10, 12, 12, 24, 24, 26, 26]);
+
+        let mut t = Tensor3::<i32, 2, 1, 3, NaiveCpu>::new([1, 2, 3, 4, 5, 6]);
+        t -= Tensor2::<i32, 1, 3, NaiveCpu>::new([1, 2, 3]);
+        assert_eq!(t.as_slice(), &[0, 0, 0, 3, 3, 3]);
     }
-}
 
+    #[test]
+    fn test_row_and_column_vectors_2d() {
+        let m = Tensor2::<i32, 2, 3, NaiveCpu>::new([1, 2, 3, 4, 5, 6]);
+        let row = Tensor2::<i32, 1, 3, NaiveCpu>::new([10, 20, 30]);
+        assert_eq!(m.broadcast_add(row).as_slice(), &[11, 22, 33, 14, 25, 36]);
+
+        let col = Tensor2::<i32, 2, 1, NaiveCpu>::new([1, 2]);
+        assert_eq!(m.broadcast_sub(col).as_slice(), &[0, 1, 2, 2, 3, 4]);
+
+        // Both operands expand: [2, 1] * [1, 3] -> [2, 3].
+        let outer = col.broadcast_mul(row);
+        assert_eq!(outer.as_slice(), &[10, 20, 30, 20, 40, 60]);
+
+        // Same-shape operators keep their shape.
+        let sum: Tensor2<i32, 2, 3, NaiveCpu> = m + m;
+        assert_eq!(sum.as_slice(), &[2, 4, 6, 8, 10, 12]);
+        assert_eq!((m / m).as_slice(), &[1; 6]);
+    }
+
+    #[test]
+    fn test_mixed_rank_both_orders() {
+        let t = Tensor3::<f32, 2, 1, 2, NaiveCpu>::new([1.0, 2.0, 3.0, 4.0]);
+        let m = Tensor2::<f32, 2, 2, NaiveCpu>::new([2.0, 2.0, 4.0, 4.0]);
+
+        let q = t / m;
+        assert_eq!(q.as_slice(), &[0.5, 1.0, 0.25, 0.5, 1.5, 2.0, 0.75, 1.0]);
+
+        let d = m - t;
+        assert_eq!(d.as_slice(), &[1.0, 0.0, 3.0, 2.0, -1.0, -2.0, 1.0, 0.0]);
+    }
+
+    // NaiveCpu tensors are `Copy`, so clippy considers the borrows needless;
+    // they are what is under test here.
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_borrowed_operands() {
+        let t = Tensor3::<i32, 2, 1, 2, NaiveCpu>::new([1, 2, 3, 4]);
+        let m = Tensor2::<i32, 2, 2, NaiveCpu>::new([10, 20, 30, 40]);
+
+        let s = &t + &m;
+        assert_eq!(s.as_slice(), &[11, 22, 31, 42, 13, 24, 33, 44]);
+        assert_eq!((&m - t).as_slice(), &[9, 18, 29, 38, 7, 16, 27, 36]);
+        assert_eq!((m + &s).as_slice(), &[21, 42, 61, 82, 23, 44, 63, 84]);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::storage::HasStorage;
+use crate::storage::parallel_cpu::{ParallelCpu, for_each_chunk};
+use crate::tensor_ops::broadcast_const_ops::naive_cpu::{broadcast_strides, pad4};
+use crate::tensor_ops::broadcast_const_ops::{
+    BroadcastConstAdd, BroadcastConstDiv, BroadcastConstMul, BroadcastConstSub,
+};
+use core::ops::{Add, Div, Mul, Sub};
+
+/// Fewest output elements worth giving a thread of their own.
+const MIN_ELEMS_PER_THREAD: usize = 1 << 14;
+
+/// Apply `f` elementwise over the broadcast of `a` and `b` into `out`, in runs
+/// of whole output rows across threads.
+fn broadcast_zip<T, F>(
+    a: &[T],
+    a_shape: &[usize],
+    b: &[T],
+    b_shape: &[usize],
+    out: &mut [T],
+    f: F,
+) where
+    T: Copy + Send + Sync,
+    F: Fn(T, T) -> T + Sync,
+{
+    if a_shape == b_shape {
+        for_each_chunk(out, 1, MIN_ELEMS_PER_THREAD, |start, chunk| {
+            for (i, o) in chunk.iter_mut().enumerate() {
+                *o = f(a[start + i], b[start + i]);
+            }
+        });
+        return;
+    }
+
+    let sa = pad4(a_shape);
+    let sb = pad4(b_shape);
+    let so: [usize; 4] = core::array::from_fn(|d| sa[d].max(sb[d]));
+    let ta = broadcast_strides(sa);
+    let tb = broadcast_strides(sb);
+
+    let row = so[3];
+    for_each_chunk(out, row, MIN_ELEMS_PER_THREAD.div_ceil(row), |first, run| {
+        for (r, dst) in run.chunks_mut(row).enumerate() {
+            let line = first + r;
+            let (i2, rest) = (line % so[2], line / so[2]);
+            let (i1, i0) = (rest % so[1], rest / so[1]);
+            let ia = i0 * ta[0] + i1 * ta[1] + i2 * ta[2];
+            let ib = i0 * tb[0] + i1 * tb[1] + i2 * tb[2];
+            for (i3, o) in dst.iter_mut().enumerate() {
+                *o = f(a[ia + i3 * ta[3]], b[ib + i3 * tb[3]]);
+            }
+        }
+    });
+}
+
+/// In-place counterpart of [`broadcast_zip`]: `a = f(a, b)`, with `b`
+/// broadcast to `a`'s shape.
+fn broadcast_zip_assign<T, F>(a: &mut [T], a_shape: &[usize], b: &[T], b_shape: &[usize], f: F)
+where
+    T: Copy + Send + Sync,
+    F: Fn(T, T) -> T + Sync,
+{
+    if a_shape == b_shape {
+        for_each_chunk(a, 1, MIN_ELEMS_PER_THREAD, |start, chunk| {
+            for (i, x) in chunk.iter_mut().enumerate() {
+                *x = f(*x, b[start + i]);
+            }
+        });
+        return;
+    }
+
+    let sa = pad4(a_shape);
+    let tb = broadcast_strides(pad4(b_shape));
+
+    let row = sa[3];
+    for_each_chunk(a, row, MIN_ELEMS_PER_THREAD.div_ceil(row), |first, run| {
+        for (r, dst) in run.chunks_mut(row).enumerate() {
+            let line = first + r;
+            let (i2, rest) = (line % sa[2], line / sa[2]);
+            let (i1, i0) = (rest % sa[1], rest / sa[1]);
+            let ib = i0 * tb[0] + i1 * tb[1] + i2 * tb[2];
+            for (i3, x) in dst.iter_mut().enumerate() {
+                *x = f(*x, b[ib + i3 * tb[3]]);
+            }
+        }
+    });
+}
+
+macro_rules! impl_parallel_broadcast {
+    ($tr:ident, $bound:ident, $op:ident $(, $assign:ident)?, |$x:ident, $y:ident| $body:expr) => {
+        impl<T> $tr<T> for ParallelCpu
+        where
+            T: Copy + Default + $bound<Output = T> + Send + Sync,
+        {
+            fn $op<const NA: usize, const NB: usize, const NO: usize>(
+                a: &<Self as HasStorage<T, NA>>::Storage,
+                a_shape: &[usize],
+                b: &<Self as HasStorage<T, NB>>::Storage,
+                b_shape: &[usize],
+                out: &mut <Self as HasStorage<T, NO>>::Storage,
+            ) where
+                Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>,
+            {
+                let a = <Self as HasStorage<T, NA>>::as_slice(a);
+                let b = <Self as HasStorage<T, NB>>::as_slice(b);
+                let o = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+                broadcast_zip(a, a_shape, b, b_shape, o, |$x, $y| $body);
+            }
+
+            $(
+                fn $assign<const NA: usize, const NB: usize>(
+                    a: &mut <Self as HasStorage<T, NA>>::Storage,
+                    a_shape: &[usize],
+                    b: &<Self as HasStorage<T, NB>>::Storage,
+                    b_shape: &[usize],
+                ) where
+                    Self: HasStorage<T, NA> + HasStorage<T, NB>,
+                {
+                    let a = <Self as HasStorage<T, NA>>::as_mut_slice(a);
+                    let b = <Self as HasStorage<T, NB>>::as_slice(b);
+                    broadcast_zip_assign(a, a_shape, b, b_shape, |$x, $y| $body);
+                }
+            )?
+        }
+    };
+}
+
+impl_parallel_broadcast!(
+    BroadcastConstAdd, Add, broadcast_add, broadcast_add_assign, |x, y| x + y
+);
+impl_parallel_broadcast!(
+    BroadcastConstSub, Sub, broadcast_sub, broadcast_sub_assign, |x, y| x - y
+);
+impl_parallel_broadcast!(BroadcastConstMul, Mul, broadcast_mul, |x, y| x * y);
+impl_parallel_broadcast!(BroadcastConstDiv, Div, broadcast_div, |x, y| x / y);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::tensor::{Tensor2, Tensor3, Tensor4};
+    use crate::tensor_ops::broadcast_const_ops::{BroadcastAdd, BroadcastMul};
+
+    #[test]
+    fn test_broadcast_matches_naive_cpu() {
+        let x: [f32; 4 * 8 * 16] = core::array::from_fn(|i| (i % 13) as f32 - 6.0);
+        let r: [f32; 16] = core::array::from_fn(|i| (i % 5) as f32 + 1.0);
+        let c: [f32; 4] = core::array::from_fn(|i| i as f32 + 0.5);
+
+        let px = Tensor3::<f32, 4, 8, 16, ParallelCpu>::new(x);
+        let pr = Tensor2::<f32, 1, 16, ParallelCpu>::new(r);
+        let pc = Tensor4::<f32, 4, 1, 1, 1, ParallelCpu>::new(c);
+        let nx = Tensor3::<f32, 4, 8, 16, NaiveCpu>::new(x);
+        let nr = Tensor2::<f32, 1, 16, NaiveCpu>::new(r);
+        let nc = Tensor4::<f32, 4, 1, 1, 1, NaiveCpu>::new(c);
+
+        assert_eq!((px / pr).as_slice(), (nx / nr).as_slice());
+        assert_eq!((pr - px).as_slice(), (nr - nx).as_slice());
+        assert_eq!(px.broadcast_mul(pc).as_slice(), nx.broadcast_mul(nc).as_slice());
+        assert_eq!(px.broadcast_add(px).as_slice(), nx.broadcast_add(nx).as_slice());
+
+        let (mut pa, mut na) = (px, nx);
+        pa -= pr;
+        na -= nr;
+        assert_eq!(pa.as_slice(), na.as_slice());
+    }
+
+    #[test]
+    fn test_rows_split_across_threads() {
+        ParallelCpu::with_num_threads(4, || {
+            let (rows, cols) = (8, MIN_ELEMS_PER_THREAD / 2);
+            let a: Vec<f32> = (0..rows * cols).map(|i| (i % 13) as f32).collect();
+            let b: Vec<f32> = (0..cols).map(|i| (i % 5) as f32).collect();
+            let mut out = vec![0.0; rows * cols];
+            broadcast_zip(&a, &[rows, cols], &b, &[1, cols], &mut out, |x, y| x - y);
+            for (i, &o) in out.iter().enumerate() {
+                assert_eq!(o, a[i] - b[i % cols]);
+            }
+
+            let mut acc = a.clone();
+            broadcast_zip_assign(&mut acc, &[rows, cols], &b, &[1, cols], |x, y| x - y);
+            assert_eq!(acc, out);
+        });
+    }
+}
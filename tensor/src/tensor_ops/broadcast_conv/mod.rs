@@ -1,7 +1,9 @@
 pub mod naive_cpu;
+pub mod parallel_cpu;
 
 use crate::storage::HasStorage;
 use crate::tensor::{Tensor2, Tensor3, Tensor4};
+use crate::tensor_ops::conv::Padding;
 use std::ops::{Add, Mul};
 
 pub trait BroadcastConv3<T: Copy + Default>: Sized {
@@ -13,16 +15,18 @@ pub trait BroadcastConv3<T: Copy + Default>: Sized {
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         input: &<Self as HasStorage<T, { BATCH * (H * W) }>>::Storage,
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
-        output: &mut <Self as HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>>::Storage,
+        output: &mut <Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::Storage,
     ) where
         Self: HasStorage<T, { BATCH * (H * W) }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>;
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>;
 
     fn conv3_backward<
         const BATCH: usize,
@@ -30,16 +34,38 @@ pub trait BroadcastConv3<T: Copy + Default>: Sized {
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
-        grad_output: &<Self as HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::Storage,
         grad_input: &mut <Self as HasStorage<T, { BATCH * (H * W) }>>::Storage,
     ) where
         Self: HasStorage<T, { BATCH * (H * W) }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>;
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>;
+
+    /// Gradient of [`Self::conv3`] with respect to its kernel, summed over the batch.
+    fn conv3_backward_kernel<
+        const BATCH: usize,
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { BATCH * (H * W) }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::Storage,
+        grad_kernel: &mut <Self as HasStorage<T, { KH * KW }>>::Storage,
+    ) where
+        Self: HasStorage<T, { BATCH * (H * W) }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>;
 }
 
 pub trait BroadcastConv4<T: Copy + Default>: Sized {
@@ -52,16 +78,18 @@ pub trait BroadcastConv4<T: Copy + Default>: Sized {
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         input: &<Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::Storage,
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
-        output: &mut <Self as HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>>::Storage,
+        output: &mut <Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::Storage,
     ) where
         Self: HasStorage<T, { B0 * (B1 * (H * W)) }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>;
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>;
 
     fn conv4_backward<
         const B0: usize,
@@ -70,16 +98,39 @@ pub trait BroadcastConv4<T: Copy + Default>: Sized {
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
-        grad_output: &<Self as HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::Storage,
         grad_input: &mut <Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::Storage,
     ) where
         Self: HasStorage<T, { B0 * (B1 * (H * W)) }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>;
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>;
+
+    /// Gradient of [`Self::conv4`] with respect to its kernel, summed over the batch.
+    fn conv4_backward_kernel<
+        const B0: usize,
+        const B1: usize,
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::Storage,
+        grad_kernel: &mut <Self as HasStorage<T, { KH * KW }>>::Storage,
+    ) where
+        Self: HasStorage<T, { B0 * (B1 * (H * W)) }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>;
 }
 
 impl<T, const BATCH: usize, const H: usize, const W: usize, B> Tensor3<T, BATCH, H, W, B>
@@ -89,18 +140,19 @@ where
 {
     /// For a 3D tensor of shape [BATCH × H × W], convolve each H×W slice
     /// against the 2D kernel.  Returns a 3D tensor of shape
-    /// [BATCH × (H-KH+1) × (W-KW+1)].
+    /// [BATCH × (H-KH+1) × (W-KW+1)]. Dilated kernels go through
+    /// [`Self::convolve_padded`].
     pub fn convolve<const KH: usize, const KW: usize, const STRIDE: usize, const PAD: usize>(
         &self,
         kernel: &Tensor2<T, KH, KW, B>,
-    ) -> Tensor3<T, BATCH, { (H + 2 * PAD - KH) / STRIDE + 1 }, { (W + 2 * PAD - KW) / STRIDE + 1 }, B>
+    ) -> Tensor3<T, BATCH, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) }, { Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }, B>
     where
         B: BroadcastConv3<T>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>,
+            + HasStorage<T, { BATCH * (Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1)) }>,
     {
-        let mut out = <B as HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>>::storage_uninit();
-        B::conv3::<BATCH, H, W, KH, KW, STRIDE, PAD>(&self.storage, &kernel.storage, &mut out);
+        let mut out = <B as HasStorage<T, { BATCH * (Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1)) }>>::storage_uninit();
+        B::conv3::<BATCH, H, W, KH, KW, STRIDE, STRIDE, 1, { Padding::symmetric(PAD) }>(&self.storage, &kernel.storage, &mut out);
         Tensor3 {
             storage: out,
             _p: core::marker::PhantomData,
@@ -108,16 +160,94 @@ where
     }
 
     pub fn conv_backward<const KH: usize, const KW: usize, const STRIDE: usize, const PAD: usize>(
-        grad_output: &Tensor3<T, BATCH, { (H + 2 * PAD - KH) / STRIDE + 1 }, { (W + 2 * PAD - KW) / STRIDE + 1 }, B>,
+        grad_output: &Tensor3<T, BATCH, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) }, { Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }, B>,
+        kernel: &Tensor2<T, KH, KW, B>,
+    ) -> Self
+    where
+        B: BroadcastConv3<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { BATCH * (Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1)) }>,
+    {
+        let mut grad_input = <B as HasStorage<T, { BATCH * (H * W) }>>::storage_uninit();
+        B::conv3_backward::<BATCH, H, W, KH, KW, STRIDE, STRIDE, 1, { Padding::symmetric(PAD) }>(
+            &kernel.storage,
+            &grad_output.storage,
+            &mut grad_input,
+        );
+        Tensor3 {
+            storage: grad_input,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::convolve`] with respect to its kernel, where `self`
+    /// is the forward input. Summed over the batch.
+    pub fn conv_backward_kernel<const KH: usize, const KW: usize, const STRIDE: usize, const PAD: usize>(
+        &self,
+        grad_output: &Tensor3<T, BATCH, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) }, { Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }, B>,
+    ) -> Tensor2<T, KH, KW, B>
+    where
+        B: BroadcastConv3<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { BATCH * (Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1)) }>,
+    {
+        let mut grad_kernel = <B as HasStorage<T, { KH * KW }>>::storage_uninit();
+        B::conv3_backward_kernel::<BATCH, H, W, KH, KW, STRIDE, STRIDE, 1, { Padding::symmetric(PAD) }>(
+            &self.storage,
+            &grad_output.storage,
+            &mut grad_kernel,
+        );
+        Tensor2 {
+            storage: grad_kernel,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// [`Self::convolve`] with strides `SH` and `SW` along the rows and
+    /// columns and the zero padding on each side given by `PADDING`.
+    pub fn convolve_padded<
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        &self,
+        kernel: &Tensor2<T, KH, KW, B>,
+    ) -> Tensor3<T, BATCH, { PADDING.out_h(H, KH, SH, DILATION) }, { PADDING.out_w(W, KW, SW, DILATION) }, B>
+    where
+        B: BroadcastConv3<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>,
+    {
+        let mut out = <B as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::storage_uninit();
+        B::conv3::<BATCH, H, W, KH, KW, SH, SW, DILATION, PADDING>(&self.storage, &kernel.storage, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::convolve_padded`] with respect to its input.
+    pub fn conv_backward_padded<
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        grad_output: &Tensor3<T, BATCH, { PADDING.out_h(H, KH, SH, DILATION) }, { PADDING.out_w(W, KW, SW, DILATION) }, B>,
         kernel: &Tensor2<T, KH, KW, B>,
     ) -> Self
     where
         B: BroadcastConv3<T>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>,
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>,
     {
         let mut grad_input = <B as HasStorage<T, { BATCH * (H * W) }>>::storage_uninit();
-        B::conv3_backward::<BATCH, H, W, KH, KW, STRIDE, PAD>(
+        B::conv3_backward::<BATCH, H, W, KH, KW, SH, SW, DILATION, PADDING>(
             &kernel.storage,
             &grad_output.storage,
             &mut grad_input,
@@ -127,6 +257,36 @@ where
             _p: core::marker::PhantomData,
         }
     }
+
+    /// Gradient of [`Self::convolve_padded`] with respect to its kernel, where
+    /// `self` is the forward input. Summed over the batch.
+    pub fn conv_backward_kernel_padded<
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        &self,
+        grad_output: &Tensor3<T, BATCH, { PADDING.out_h(H, KH, SH, DILATION) }, { PADDING.out_w(W, KW, SW, DILATION) }, B>,
+    ) -> Tensor2<T, KH, KW, B>
+    where
+        B: BroadcastConv3<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>,
+    {
+        let mut grad_kernel = <B as HasStorage<T, { KH * KW }>>::storage_uninit();
+        B::conv3_backward_kernel::<BATCH, H, W, KH, KW, SH, SW, DILATION, PADDING>(
+            &self.storage,
+            &grad_output.storage,
+            &mut grad_kernel,
+        );
+        Tensor2 {
+            storage: grad_kernel,
+            _p: core::marker::PhantomData,
+        }
+    }
 }
 
 impl<T, const B0: usize, const B1: usize, const H: usize, const W: usize, B>
@@ -137,18 +297,19 @@ where
 {
     /// For a 4D tensor of shape [B0 × B1 × H × W], convolve each H×W slice
     /// (for every pair (i0,i1)) with the same KH×KW kernel.  The result is
-    /// a new 4D tensor [B0 × B1 × (H-KH+1) × (W-KW+1)].
+    /// a new 4D tensor [B0 × B1 × (H-KH+1) × (W-KW+1)]. Dilated kernels go
+    /// through [`Self::convolve_padded`].
     pub fn convolve<const KH: usize, const KW: usize, const STRIDE: usize, const PAD: usize>(
         &self,
         kernel: &Tensor2<T, KH, KW, B>,
-    ) -> Tensor4<T, B0, B1, { (H + 2 * PAD - KH) / STRIDE + 1 }, { (W + 2 * PAD - KW) / STRIDE + 1 }, B>
+    ) -> Tensor4<T, B0, B1, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) }, { Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }, B>
     where
         B: BroadcastConv4<T>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>,
+            + HasStorage<T, { B0 * (B1 * (Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1))) }>,
     {
-        let mut out = <B as HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>>::storage_uninit();
-        B::conv4::<B0, B1, H, W, KH, KW, STRIDE, PAD>(&self.storage, &kernel.storage, &mut out);
+        let mut out = <B as HasStorage<T, { B0 * (B1 * (Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1))) }>>::storage_uninit();
+        B::conv4::<B0, B1, H, W, KH, KW, STRIDE, STRIDE, 1, { Padding::symmetric(PAD) }>(&self.storage, &kernel.storage, &mut out);
         Tensor4 {
             storage: out,
             _p: core::marker::PhantomData,
@@ -156,16 +317,16 @@ where
     }
 
     pub fn conv_backward<const KH: usize, const KW: usize, const STRIDE: usize, const PAD: usize>(
-        grad_output: &Tensor4<T, B0, B1, { (H + 2 * PAD - KH) / STRIDE + 1 }, { (W + 2 * PAD - KW) / STRIDE + 1 }, B>,
+        grad_output: &Tensor4<T, B0, B1, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) }, { Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }, B>,
         kernel: &Tensor2<T, KH, KW, B>,
     ) -> Self
     where
         B: BroadcastConv4<T>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>,
+            + HasStorage<T, { B0 * (B1 * (Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1))) }>,
     {
         let mut grad_input = <B as HasStorage<T, { B0 * (B1 * (H * W)) }>>::storage_uninit();
-        B::conv4_backward::<B0, B1, H, W, KH, KW, STRIDE, PAD>(
+        B::conv4_backward::<B0, B1, H, W, KH, KW, STRIDE, STRIDE, 1, { Padding::symmetric(PAD) }>(
             &kernel.storage,
             &grad_output.storage,
             &mut grad_input,
@@ -175,4 +336,113 @@ where
             _p: core::marker::PhantomData,
         }
     }
+
+    /// Gradient of [`Self::convolve`] with respect to its kernel, where `self`
+    /// is the forward input. Summed over both batch dimensions.
+    pub fn conv_backward_kernel<const KH: usize, const KW: usize, const STRIDE: usize, const PAD: usize>(
+        &self,
+        grad_output: &Tensor4<T, B0, B1, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) }, { Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }, B>,
+    ) -> Tensor2<T, KH, KW, B>
+    where
+        B: BroadcastConv4<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { B0 * (B1 * (Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1))) }>,
+    {
+        let mut grad_kernel = <B as HasStorage<T, { KH * KW }>>::storage_uninit();
+        B::conv4_backward_kernel::<B0, B1, H, W, KH, KW, STRIDE, STRIDE, 1, { Padding::symmetric(PAD) }>(
+            &self.storage,
+            &grad_output.storage,
+            &mut grad_kernel,
+        );
+        Tensor2 {
+            storage: grad_kernel,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// [`Self::convolve`] with strides `SH` and `SW` along the rows and
+    /// columns and the zero padding on each side given by `PADDING`.
+    pub fn convolve_padded<
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        &self,
+        kernel: &Tensor2<T, KH, KW, B>,
+    ) -> Tensor4<T, B0, B1, { PADDING.out_h(H, KH, SH, DILATION) }, { PADDING.out_w(W, KW, SW, DILATION) }, B>
+    where
+        B: BroadcastConv4<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>,
+    {
+        let mut out = <B as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::storage_uninit();
+        B::conv4::<B0, B1, H, W, KH, KW, SH, SW, DILATION, PADDING>(&self.storage, &kernel.storage, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::convolve_padded`] with respect to its input.
+    pub fn conv_backward_padded<
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        grad_output: &Tensor4<T, B0, B1, { PADDING.out_h(H, KH, SH, DILATION) }, { PADDING.out_w(W, KW, SW, DILATION) }, B>,
+        kernel: &Tensor2<T, KH, KW, B>,
+    ) -> Self
+    where
+        B: BroadcastConv4<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>,
+    {
+        let mut grad_input = <B as HasStorage<T, { B0 * (B1 * (H * W)) }>>::storage_uninit();
+        B::conv4_backward::<B0, B1, H, W, KH, KW, SH, SW, DILATION, PADDING>(
+            &kernel.storage,
+            &grad_output.storage,
+            &mut grad_input,
+        );
+        Tensor4 {
+            storage: grad_input,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::convolve_padded`] with respect to its kernel, where
+    /// `self` is the forward input. Summed over both
+    /// batch dimensions.
+    pub fn conv_backward_kernel_padded<
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        &self,
+        grad_output: &Tensor4<T, B0, B1, { PADDING.out_h(H, KH, SH, DILATION) }, { PADDING.out_w(W, KW, SW, DILATION) }, B>,
+    ) -> Tensor2<T, KH, KW, B>
+    where
+        B: BroadcastConv4<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>,
+    {
+        let mut grad_kernel = <B as HasStorage<T, { KH * KW }>>::storage_uninit();
+        B::conv4_backward_kernel::<B0, B1, H, W, KH, KW, SH, SW, DILATION, PADDING>(
+            &self.storage,
+            &grad_output.storage,
+            &mut grad_kernel,
+        );
+        Tensor2 {
+            storage: grad_kernel,
+            _p: core::marker::PhantomData,
+        }
+    }
 }
@@ -1,6 +1,10 @@
 use crate::storage::HasStorage;
 use crate::storage::naive_cpu::NaiveCpu;
 use crate::tensor_ops::broadcast_conv::{BroadcastConv3, BroadcastConv4};
+use crate::tensor_ops::conv::Padding;
+use crate::tensor_ops::conv::kernel::{
+    Conv2Geom, conv2_backward_rows, conv2_kernel_grad_rows, conv2_rows,
+};
 use std::ops::{Add, Mul};
 
 impl<T> BroadcastConv3<T> for NaiveCpu
@@ -13,51 +17,27 @@ where
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         input: &<Self as HasStorage<T, { BATCH * (H * W) }>>::Storage,
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
-        output: &mut <Self as HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>>::Storage,
+        output: &mut <Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::Storage,
     ) where
         Self: HasStorage<T, { BATCH * (H * W) }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>,
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>,
     {
-        // Convert storages into flat slices:
         let inp = <Self as HasStorage<T, { BATCH * (H * W) }>>::as_slice(input);
         let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
-        let out = <Self as HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>>::as_mut_slice(output);
-
-        let out_h = (H + 2 * PAD - KH) / STRIDE + 1;
-        let out_w = (W + 2 * PAD - KW) / STRIDE + 1;
-
-        // For each batch, run a 2D conv on that H×W slice.
-        for batch in 0..BATCH {
-            let base_in = batch * (H * W);
-            let base_out = batch * (out_h * out_w);
-
-            for i in 0..out_h {
-                for j in 0..out_w {
-                    let mut acc = T::default();
-
-                    // sum over the KH×KW window
-                    for ki in 0..KH {
-                        for kj in 0..KW {
-                            let hi = i * STRIDE + ki;
-                            let wj = j * STRIDE + kj;
-                            if hi >= PAD && hi < H + PAD && wj >= PAD && wj < W + PAD {
-                                let idx_in = base_in + (hi - PAD) * W + (wj - PAD);
-                                let idx_ker = ki * KW + kj;
-                                acc = acc + inp[idx_in] * ker[idx_ker];
-                            }
-                        }
-                    }
-
-                    let idx_out = base_out + i * out_w + j;
-                    out[idx_out] = acc;
-                }
-            }
+        let out = <Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::as_mut_slice(output);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        let out_len = g.out_h() * g.out_w();
+        for (image, out) in inp.chunks(H * W).zip(out.chunks_mut(out_len)) {
+            conv2_rows(&g, image, ker, 0, out);
         }
     }
 
@@ -67,47 +47,55 @@ where
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
-        grad_output: &<Self as HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::Storage,
         grad_input: &mut <Self as HasStorage<T, { BATCH * (H * W) }>>::Storage,
     ) where
         Self: HasStorage<T, { BATCH * (H * W) }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>,
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>,
     {
         let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
-        let grad_out = <Self as HasStorage<T, { BATCH * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1)) }>>::as_slice(grad_output);
+        let grad_out = <Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::as_slice(grad_output);
         let grad_in = <Self as HasStorage<T, { BATCH * (H * W) }>>::as_mut_slice(grad_input);
 
-        for v in grad_in.iter_mut() {
-            *v = T::default();
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        let out_len = g.out_h() * g.out_w();
+        for (grad_out, grad_in) in grad_out.chunks(out_len).zip(grad_in.chunks_mut(H * W)) {
+            conv2_backward_rows(&g, ker, grad_out, 0, grad_in);
         }
+    }
 
-        let out_h = (H + 2 * PAD - KH) / STRIDE + 1;
-        let out_w = (W + 2 * PAD - KW) / STRIDE + 1;
-
-        for batch in 0..BATCH {
-            let base_in = batch * (H * W);
-            let base_out = batch * (out_h * out_w);
-            for i in 0..out_h {
-                for j in 0..out_w {
-                    let go = grad_out[base_out + i * out_w + j];
-                    for ki in 0..KH {
-                        for kj in 0..KW {
-                            let hi = i * STRIDE + ki;
-                            let wj = j * STRIDE + kj;
-                            if hi >= PAD && hi < H + PAD && wj >= PAD && wj < W + PAD {
-                                grad_in[base_in + (hi - PAD) * W + (wj - PAD)] =
-                                    grad_in[base_in + (hi - PAD) * W + (wj - PAD)] + ker[ki * KW + kj] * go;
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    fn conv3_backward_kernel<
+        const BATCH: usize,
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { BATCH * (H * W) }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::Storage,
+        grad_kernel: &mut <Self as HasStorage<T, { KH * KW }>>::Storage,
+    ) where
+        Self: HasStorage<T, { BATCH * (H * W) }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>,
+    {
+        let inp = <Self as HasStorage<T, { BATCH * (H * W) }>>::as_slice(input);
+        let grad_out = <Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::as_slice(grad_output);
+        let grad_ker = <Self as HasStorage<T, { KH * KW }>>::as_mut_slice(grad_kernel);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        conv2_kernel_grad_rows(&g, inp, grad_out, BATCH, 0, grad_ker);
     }
 }
 
@@ -122,54 +110,28 @@ where
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         input: &<Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::Storage,
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
-        output: &mut <Self as HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>>::Storage,
+        output: &mut <Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::Storage,
     ) where
         Self: HasStorage<T, { B0 * (B1 * (H * W)) }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>,
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>,
     {
         let inp = <Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::as_slice(input);
         let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
         let out =
-            <Self as HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>>::as_mut_slice(output);
-
-        let out_h = (H + 2 * PAD - KH) / STRIDE + 1;
-        let out_w = (W + 2 * PAD - KW) / STRIDE + 1;
-
-        // Loop over both batch dims:
-        for i0 in 0..B0 {
-            for i1 in 0..B1 {
-                // Compute the slice base for input and output:
-                let base_in = ((i0 * B1 + i1) * (H * W)) as usize;
-                let base_out = ((i0 * B1 + i1) * (out_h * out_w)) as usize;
-
-                for i in 0..out_h {
-                    for j in 0..out_w {
-                        let mut acc = T::default();
-
-                        // convolve over the KH×KW window within this (H×W) slice
-                        for ki in 0..KH {
-                            for kj in 0..KW {
-                                let hi = i * STRIDE + ki;
-                                let wj = j * STRIDE + kj;
-                                if hi >= PAD && hi < H + PAD && wj >= PAD && wj < W + PAD {
-                                    let idx_in = base_in + (hi - PAD) * W + (wj - PAD);
-                                    let idx_ker = ki * KW + kj;
-                                    acc = acc + inp[idx_in] * ker[idx_ker];
-                                }
-                            }
-                        }
-
-                        let idx_out = base_out + i * out_w + j;
-                        out[idx_out] = acc;
-                    }
-                }
-            }
+            <Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::as_mut_slice(output);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        let out_len = g.out_h() * g.out_w();
+        for (image, out) in inp.chunks(H * W).zip(out.chunks_mut(out_len)) {
+            conv2_rows(&g, image, ker, 0, out);
         }
     }
 
@@ -180,48 +142,109 @@ where
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
-        grad_output: &<Self as HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::Storage,
         grad_input: &mut <Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::Storage,
     ) where
         Self: HasStorage<T, { B0 * (B1 * (H * W)) }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>,
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>,
     {
         let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
-        let grad_out = <Self as HasStorage<T, { B0 * (B1 * (((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1))) }>>::as_slice(grad_output);
+        let grad_out = <Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::as_slice(grad_output);
         let grad_in = <Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::as_mut_slice(grad_input);
 
-        for v in grad_in.iter_mut() {
-            *v = T::default();
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        let out_len = g.out_h() * g.out_w();
+        for (grad_out, grad_in) in grad_out.chunks(out_len).zip(grad_in.chunks_mut(H * W)) {
+            conv2_backward_rows(&g, ker, grad_out, 0, grad_in);
         }
+    }
+
+    fn conv4_backward_kernel<
+        const B0: usize,
+        const B1: usize,
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::Storage,
+        grad_kernel: &mut <Self as HasStorage<T, { KH * KW }>>::Storage,
+    ) where
+        Self: HasStorage<T, { B0 * (B1 * (H * W)) }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>,
+    {
+        let inp = <Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::as_slice(input);
+        let grad_out = <Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::as_slice(grad_output);
+        let grad_ker = <Self as HasStorage<T, { KH * KW }>>::as_mut_slice(grad_kernel);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        conv2_kernel_grad_rows(&g, inp, grad_out, B0 * B1, 0, grad_ker);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::tensor::{Tensor2, Tensor3, Tensor4};
+    use crate::tensor_ops::conv::Padding;
+
+    #[test]
+    fn test_kernel_gradient_sums_over_batch() {
+        let x: [i32; 3 * 4 * 4] = core::array::from_fn(|i| (i * 5 % 9) as i32 - 4);
+        let g: [i32; 3 * 2 * 2] = core::array::from_fn(|i| (i * 7 % 5) as i32 - 2);
+        let dk = Tensor3::<i32, 3, 4, 4, NaiveCpu>::new(x).conv_backward_kernel::<2, 2, 2, 0>(
+            &Tensor3::<i32, 3, 2, 2, NaiveCpu>::new(g),
+        );
+
+        let mut want = [0; 4];
+        for b in 0..3 {
+            let image = Tensor2::<i32, 4, 4, NaiveCpu>::new_from_slice(&x[b * 16..(b + 1) * 16]);
+            let grad = Tensor2::<i32, 2, 2, NaiveCpu>::new_from_slice(&g[b * 4..(b + 1) * 4]);
+            let part = image.conv2_backward_kernel::<2, 2, 2, 0>(&grad);
+            want.iter_mut().zip(part.as_slice()).for_each(|(w, p)| *w += p);
+        }
+        assert_eq!(dk.as_slice(), &want);
+
+        let dk4 = Tensor4::<i32, 3, 1, 4, 4, NaiveCpu>::new(x)
+            .conv_backward_kernel::<2, 2, 2, 0>(&Tensor4::<i32, 3, 1, 2, 2, NaiveCpu>::new(g));
+        assert_eq!(dk4.as_slice(), &want);
+    }
 
-        let out_h = (H + 2 * PAD - KH) / STRIDE + 1;
-        let out_w = (W + 2 * PAD - KW) / STRIDE + 1;
-
-        for i0 in 0..B0 {
-            for i1 in 0..B1 {
-                let base_in = (i0 * B1 + i1) * (H * W);
-                let base_out = (i0 * B1 + i1) * (out_h * out_w);
-                for i in 0..out_h {
-                    for j in 0..out_w {
-                        let go = grad_out[base_out + i * out_w + j];
-                        for ki in 0..KH {
-                            for kj in 0..KW {
-                                let hi = i * STRIDE + ki;
-                                let wj = j * STRIDE + kj;
-                                if hi >= PAD && hi < H + PAD && wj >= PAD && wj < W + PAD {
-                                    grad_in[base_in + (hi - PAD) * W + (wj - PAD)] =
-                                        grad_in[base_in + (hi - PAD) * W + (wj - PAD)] + ker[ki * KW + kj] * go;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    #[test]
+    fn test_padded_convolves_each_image() {
+        const PAD: Padding = Padding::Explicit { top: 0, bottom: 1, left: 2, right: 0 };
+        let x: [i32; 2 * 4 * 5] = core::array::from_fn(|i| (i * 5 % 9) as i32 - 4);
+        let k = Tensor2::<i32, 2, 3, NaiveCpu>::new([1, -2, 0, 3, 1, -1]);
+        let g: [i32; 2 * 2 * 5] = core::array::from_fn(|i| (i * 7 % 5) as i32 - 2);
+
+        let y = Tensor3::<i32, 2, 4, 5, NaiveCpu>::new(x).convolve_padded::<2, 3, 2, 1, 1, PAD>(&k);
+        let dx = Tensor4::<i32, 1, 2, 4, 5, NaiveCpu>::conv_backward_padded::<2, 3, 2, 1, 1, PAD>(
+            &Tensor4::<i32, 1, 2, 2, 5, NaiveCpu>::new(g),
+            &k,
+        );
+        for b in 0..2 {
+            let image = Tensor2::<i32, 4, 5, NaiveCpu>::new_from_slice(&x[b * 20..(b + 1) * 20]);
+            let want = image.convolve_padded::<2, 3, 2, 1, 1, PAD>(&k);
+            assert_eq!(&y.as_slice()[b * 10..(b + 1) * 10], want.as_slice());
+
+            let grad = Tensor2::<i32, 2, 5, NaiveCpu>::new_from_slice(&g[b * 10..(b + 1) * 10]);
+            let want = Tensor2::<i32, 4, 5, NaiveCpu>::conv2_backward_padded::<2, 3, 2, 1, 1, PAD>(
+                &grad, &k,
+            );
+            assert_eq!(&dx.as_slice()[b * 20..(b + 1) * 20], want.as_slice());
         }
     }
 }
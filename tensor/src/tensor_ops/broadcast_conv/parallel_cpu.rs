@@ -0,0 +1,212 @@
+use crate::storage::HasStorage;
+use crate::storage::parallel_cpu::{ParallelCpu, for_each_chunk};
+use crate::tensor_ops::broadcast_conv::{BroadcastConv3, BroadcastConv4};
+use crate::tensor_ops::conv::Padding;
+use crate::tensor_ops::conv::kernel::{
+    Conv2Geom, conv2_backward_rows, conv2_kernel_grad_rows, conv2_rows,
+};
+use std::ops::{Add, Mul};
+
+impl<T> BroadcastConv3<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    fn conv3<
+        const BATCH: usize,
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { BATCH * (H * W) }>>::Storage,
+        kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
+        output: &mut <Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { BATCH * (H * W) }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>,
+    {
+        let inp = <Self as HasStorage<T, { BATCH * (H * W) }>>::as_slice(input);
+        let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
+        let out = <Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::as_mut_slice(output);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        let out_len = g.out_h() * g.out_w();
+        for_each_chunk(out, out_len, 1, |first, images| {
+            let inp = &inp[first * H * W..];
+            for (image, out) in inp.chunks(H * W).zip(images.chunks_mut(out_len)) {
+                conv2_rows(&g, image, ker, 0, out);
+            }
+        });
+    }
+
+    fn conv3_backward<
+        const BATCH: usize,
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::Storage,
+        grad_input: &mut <Self as HasStorage<T, { BATCH * (H * W) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { BATCH * (H * W) }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>,
+    {
+        let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
+        let grad_out = <Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::as_slice(grad_output);
+        let grad_in = <Self as HasStorage<T, { BATCH * (H * W) }>>::as_mut_slice(grad_input);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        let out_len = g.out_h() * g.out_w();
+        for_each_chunk(grad_in, H * W, 1, |first, images| {
+            let grad_out = &grad_out[first * out_len..];
+            for (grad_out, grad_in) in grad_out.chunks(out_len).zip(images.chunks_mut(H * W)) {
+                conv2_backward_rows(&g, ker, grad_out, 0, grad_in);
+            }
+        });
+    }
+
+    fn conv3_backward_kernel<
+        const BATCH: usize,
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { BATCH * (H * W) }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::Storage,
+        grad_kernel: &mut <Self as HasStorage<T, { KH * KW }>>::Storage,
+    ) where
+        Self: HasStorage<T, { BATCH * (H * W) }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>,
+    {
+        let inp = <Self as HasStorage<T, { BATCH * (H * W) }>>::as_slice(input);
+        let grad_out = <Self as HasStorage<T, { BATCH * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION)) }>>::as_slice(grad_output);
+        let grad_ker = <Self as HasStorage<T, { KH * KW }>>::as_mut_slice(grad_kernel);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        for_each_chunk(grad_ker, KW, 1, |first, rows| {
+            conv2_kernel_grad_rows(&g, inp, grad_out, BATCH, first, rows);
+        });
+    }
+}
+
+impl<T> BroadcastConv4<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    fn conv4<
+        const B0: usize,
+        const B1: usize,
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::Storage,
+        kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
+        output: &mut <Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { B0 * (B1 * (H * W)) }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>,
+    {
+        let inp = <Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::as_slice(input);
+        let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
+        let out =
+            <Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::as_mut_slice(output);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        let out_len = g.out_h() * g.out_w();
+        for_each_chunk(out, out_len, 1, |first, images| {
+            let inp = &inp[first * H * W..];
+            for (image, out) in inp.chunks(H * W).zip(images.chunks_mut(out_len)) {
+                conv2_rows(&g, image, ker, 0, out);
+            }
+        });
+    }
+
+    fn conv4_backward<
+        const B0: usize,
+        const B1: usize,
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::Storage,
+        grad_input: &mut <Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { B0 * (B1 * (H * W)) }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>,
+    {
+        let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
+        let grad_out = <Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::as_slice(grad_output);
+        let grad_in = <Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::as_mut_slice(grad_input);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        let out_len = g.out_h() * g.out_w();
+        for_each_chunk(grad_in, H * W, 1, |first, images| {
+            let grad_out = &grad_out[first * out_len..];
+            for (grad_out, grad_in) in grad_out.chunks(out_len).zip(images.chunks_mut(H * W)) {
+                conv2_backward_rows(&g, ker, grad_out, 0, grad_in);
+            }
+        });
+    }
+
+    fn conv4_backward_kernel<
+        const B0: usize,
+        const B1: usize,
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::Storage,
+        grad_kernel: &mut <Self as HasStorage<T, { KH * KW }>>::Storage,
+    ) where
+        Self: HasStorage<T, { B0 * (B1 * (H * W)) }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>,
+    {
+        let inp = <Self as HasStorage<T, { B0 * (B1 * (H * W)) }>>::as_slice(input);
+        let grad_out = <Self as HasStorage<T, { B0 * (B1 * (PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION))) }>>::as_slice(grad_output);
+        let grad_ker = <Self as HasStorage<T, { KH * KW }>>::as_mut_slice(grad_kernel);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        for_each_chunk(grad_ker, KW, 1, |first, rows| {
+            conv2_kernel_grad_rows(&g, inp, grad_out, B0 * B1, first, rows);
+        });
+    }
+}
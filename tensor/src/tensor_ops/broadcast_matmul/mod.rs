@@ -1,4 +1,5 @@
 pub mod naive_cpu;
+pub mod parallel_cpu;
 #[cfg(target_os = "macos")]
 pub mod metal_gpu;
 
@@ -28,23 +29,21 @@ pub trait BroadcastMatMul4<T: Copy + Default>: Sized {
             + HasStorage<T, { B0 * (B1 * (R * K)) }>;
 }
 
-impl<T, const BATCH: usize, const R: usize, const C: usize, const K: usize, B>
-    Mul<Tensor2<T, C, K, B>> for Tensor3<T, BATCH, R, C, B>
-where
-    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
-    B: HasStorage<T, { BATCH * (R * C) }>
-        + HasStorage<T, { C * K }>
-        + HasStorage<T, { BATCH * (R * K) }>
-        + BroadcastMatMul3<T>,
-{
-    type Output = Tensor3<T, BATCH, R, K, B>;
-
-    #[inline]
-    fn mul(self, rhs: Tensor2<T, C, K, B>) -> Self::Output {
+impl_binop! {
+    impl<[T, const BATCH: usize, const R: usize, const C: usize, const K: usize, B]>
+    Mul::mul(Tensor3<T, BATCH, R, C, B>, Tensor2<T, C, K, B>) -> Tensor3<T, BATCH, R, K, B>
+    where {
+        T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+        B: HasStorage<T, { BATCH * (R * C) }>
+            + HasStorage<T, { C * K }>
+            + HasStorage<T, { BATCH * (R * K) }>
+            + BroadcastMatMul3<T>,
+    }
+    |a, b| {
         let mut out: <B as HasStorage<T, { BATCH * (R * K) }>>::Storage =
             <B as HasStorage<T, { BATCH * (R * K) }>>::storage_uninit();
 
-        B::matmul3::<BATCH, R, C, K>(&self.storage, &rhs.storage, &mut out);
+        B::matmul3::<BATCH, R, C, K>(&a.storage, &b.storage, &mut out);
 
         Tensor3 {
             storage: out,
@@ -53,23 +52,21 @@ where
     }
 }
 
-impl<T, const B0: usize, const B1: usize, const R: usize, const C: usize, const K: usize, B>
-    Mul<Tensor2<T, C, K, B>> for Tensor4<T, B0, B1, R, C, B>
-where
-    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
-    B: HasStorage<T, { B0 * (B1 * (R * C)) }>
-        + HasStorage<T, { C * K }>
-        + HasStorage<T, { B0 * (B1 * (R * K)) }>
-        + BroadcastMatMul4<T>,
-{
-    type Output = Tensor4<T, B0, B1, R, K, B>;
-
-    #[inline]
-    fn mul(self, rhs: Tensor2<T, C, K, B>) -> Self::Output {
+impl_binop! {
+    impl<[T, const B0: usize, const B1: usize, const R: usize, const C: usize, const K: usize, B]>
+    Mul::mul(Tensor4<T, B0, B1, R, C, B>, Tensor2<T, C, K, B>) -> Tensor4<T, B0, B1, R, K, B>
+    where {
+        T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+        B: HasStorage<T, { B0 * (B1 * (R * C)) }>
+            + HasStorage<T, { C * K }>
+            + HasStorage<T, { B0 * (B1 * (R * K)) }>
+            + BroadcastMatMul4<T>,
+    }
+    |a, b| {
         let mut out: <B as HasStorage<T, { B0 * (B1 * (R * K)) }>>::Storage =
             <B as HasStorage<T, { B0 * (B1 * (R * K)) }>>::storage_uninit();
 
-        B::matmul4::<B0, B1, R, C, K>(&self.storage, &rhs.storage, &mut out);
+        B::matmul4::<B0, B1, R, C, K>(&a.storage, &b.storage, &mut out);
 
         Tensor4 {
             storage: out,
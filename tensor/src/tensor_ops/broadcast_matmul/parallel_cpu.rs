@@ -0,0 +1,45 @@
+use crate::storage::HasStorage;
+use crate::storage::parallel_cpu::ParallelCpu;
+use crate::tensor_ops::broadcast_matmul::{BroadcastMatMul3, BroadcastMatMul4};
+use crate::tensor_ops::matmul::parallel_cpu::par_matmul_into;
+use core::ops::{Add, Mul};
+
+impl<T> BroadcastMatMul3<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync + 'static,
+{
+    fn matmul3<const BATCH: usize, const R: usize, const C: usize, const K: usize>(
+        a: &<Self as HasStorage<T, { BATCH * (R * C) }>>::Storage,
+        b: &<Self as HasStorage<T, { C * K }>>::Storage,
+        out: &mut <Self as HasStorage<T, { BATCH * (R * K) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { BATCH * (R * C) }>
+            + HasStorage<T, { C * K }>
+            + HasStorage<T, { BATCH * (R * K) }>,
+    {
+        let a = <Self as HasStorage<T, { BATCH * (R * C) }>>::as_slice(a);
+        let b = <Self as HasStorage<T, { C * K }>>::as_slice(b);
+        let o = <Self as HasStorage<T, { BATCH * (R * K) }>>::as_mut_slice(out);
+        par_matmul_into(a, b, o, BATCH * R, C, K);
+    }
+}
+
+impl<T> BroadcastMatMul4<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync + 'static,
+{
+    fn matmul4<const B0: usize, const B1: usize, const R: usize, const C: usize, const K: usize>(
+        a: &<Self as HasStorage<T, { B0 * (B1 * (R * C)) }>>::Storage,
+        b: &<Self as HasStorage<T, { C * K }>>::Storage,
+        out: &mut <Self as HasStorage<T, { B0 * (B1 * (R * K)) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { B0 * (B1 * (R * C)) }>
+            + HasStorage<T, { C * K }>
+            + HasStorage<T, { B0 * (B1 * (R * K)) }>,
+    {
+        let a = <Self as HasStorage<T, { B0 * (B1 * (R * C)) }>>::as_slice(a);
+        let b = <Self as HasStorage<T, { C * K }>>::as_slice(b);
+        let o = <Self as HasStorage<T, { B0 * (B1 * (R * K)) }>>::as_mut_slice(out);
+        par_matmul_into(a, b, o, B0 * B1 * R, C, K);
+    }
+}
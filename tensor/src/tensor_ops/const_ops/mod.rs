@@ -1,13 +1,22 @@
 //! Element-wise tensor and scalar operations.
 //!
-//! Backend implementers should implement [`ConstAdd`], [`ConstMul`], and
-//! [`ConstDiv`] for their backend.
+//! The scalar may appear on either side of `+`, `-`, `*` and `/`; the
+//! scalar-on-the-left forms are provided for `f32` and `f64`.
+//!
+//! `maximum`, `minimum` and `clamp` propagate NaN as numpy does: a NaN element
+//! or bound gives NaN rather than the other operand.
+//!
+//! Backend implementers should implement [`ConstAdd`], [`ConstSub`],
+//! [`ConstMul`], [`ConstDiv`], [`ConstMax`] and [`ConstMin`] for their backend.
 
 pub mod naive_cpu;
+pub mod parallel_cpu;
 
 use crate::storage::HasStorage;
 use crate::tensor::{Tensor2, Tensor3, Tensor4};
-use core::ops::{Add, Div, Mul};
+use crate::tensor_ops::elemwise::{Maximum, Minimum};
+use core::cmp::PartialOrd;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
 /// Trait for backends that support element-wise scalar addition.
 pub trait ConstAdd<T: Copy + Default>: Sized {
@@ -18,6 +27,38 @@ pub trait ConstAdd<T: Copy + Default>: Sized {
     ) where
         T: Add<Output = T>,
         Self: HasStorage<T, N>;
+
+    /// In-place variant: `a = a + k`.
+    fn constadd_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        T: Add<Output = T>,
+        Self: HasStorage<T, N>;
+}
+
+/// Trait for backends that support element-wise scalar subtraction.
+pub trait ConstSub<T: Copy + Default>: Sized {
+    fn constsub<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        T: Sub<Output = T>,
+        Self: HasStorage<T, N>;
+
+    /// In-place variant: `a = a - k`.
+    fn constsub_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        T: Sub<Output = T>,
+        Self: HasStorage<T, N>;
+
+    /// Reversed operands: `out = k - a`.
+    fn rconstsub<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        T: Sub<Output = T>,
+        Self: HasStorage<T, N>;
 }
 
 /// Trait for backends that support element-wise scalar multiplication.
@@ -29,6 +70,12 @@ pub trait ConstMul<T: Copy + Default>: Sized {
     ) where
         T: Mul<Output = T>,
         Self: HasStorage<T, N>;
+
+    /// In-place variant: `a = a * k`.
+    fn constmul_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        T: Mul<Output = T>,
+        Self: HasStorage<T, N>;
 }
 
 /// Trait for backends that support element-wise scalar division.
@@ -40,19 +87,101 @@ pub trait ConstDiv<T: Copy + Default>: Sized {
     ) where
         T: Div<Output = T>,
         Self: HasStorage<T, N>;
+
+    /// In-place variant: `a = a / k`.
+    fn constdiv_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        T: Div<Output = T>,
+        Self: HasStorage<T, N>;
+
+    /// Reversed operands: `out = k / a`.
+    fn rconstdiv<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        T: Div<Output = T>,
+        Self: HasStorage<T, N>;
 }
 
-impl<T, const R: usize, const C: usize, B> Add<T> for Tensor2<T, R, C, B>
-where
-    T: Copy + Default + Add<Output = T>,
-    B: ConstAdd<T> + HasStorage<T, { R * C }>,
-{
-    type Output = Tensor2<T, R, C, B>;
+/// Trait for backends that support an element-wise maximum with a scalar.
+///
+/// NaN in either operand gives NaN.
+pub trait ConstMax<T: Copy + Default + PartialOrd>: Sized {
+    fn constmax<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+}
 
-    #[inline]
-    fn add(self, rhs: T) -> Self::Output {
+/// Trait for backends that support an element-wise minimum with a scalar.
+///
+/// NaN in either operand gives NaN.
+pub trait ConstMin<T: Copy + Default + PartialOrd>: Sized {
+    fn constmin<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+}
+
+/// Scalar-on-the-left operators, `k op tensor`, for a concrete scalar type.
+macro_rules! impl_scalar_lhs {
+    ($s:ty; $name:ident [$($d:ident),+], { $n:expr }) => {
+        impl_scalar_lhs!(@op $s, Add, add, ConstAdd, constadd; $name [$($d),+], { $n });
+        impl_scalar_lhs!(@op $s, Sub, sub, ConstSub, rconstsub; $name [$($d),+], { $n });
+        impl_scalar_lhs!(@op $s, Mul, mul, ConstMul, constmul; $name [$($d),+], { $n });
+        impl_scalar_lhs!(@op $s, Div, div, ConstDiv, rconstdiv; $name [$($d),+], { $n });
+    };
+
+    (@op $s:ty, $tr:ident, $method:ident, $kernel_tr:ident, $kernel:ident;
+        $name:ident [$($d:ident),+], { $n:expr }) => {
+        impl<$(const $d: usize,)+ B> $tr<$name<$s, $($d,)+ B>> for $s
+        where
+            B: $kernel_tr<$s> + HasStorage<$s, { $n }>,
+            [(); $n]:,
+        {
+            type Output = $name<$s, $($d,)+ B>;
+
+            #[inline]
+            fn $method(self, rhs: $name<$s, $($d,)+ B>) -> Self::Output {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<'a, $(const $d: usize,)+ B> $tr<&'a $name<$s, $($d,)+ B>> for $s
+        where
+            B: $kernel_tr<$s> + HasStorage<$s, { $n }>,
+            [(); $n]:,
+        {
+            type Output = $name<$s, $($d,)+ B>;
+
+            #[inline]
+            fn $method(self, rhs: &'a $name<$s, $($d,)+ B>) -> Self::Output {
+                let mut out = <B as HasStorage<$s, { $n }>>::storage_uninit();
+                B::$kernel::<{ $n }>(&rhs.storage, self, &mut out);
+                $name {
+                    storage: out,
+                    _p: core::marker::PhantomData,
+                }
+            }
+        }
+    };
+}
+
+impl_scalar_op! {
+    impl<[T, const R: usize, const C: usize, B]>
+    Add::add(Tensor2<T, R, C, B>, T) -> Tensor2<T, R, C, B>
+    where {
+        T: Copy + Default + Add<Output = T>,
+        B: ConstAdd<T> + HasStorage<T, { R * C }>,
+    }
+    |a, k| {
         let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
-        B::constadd::<{ R * C }>(&self.storage, rhs, &mut out);
+        B::constadd::<{ R * C }>(&a.storage, k, &mut out);
         Tensor2 {
             storage: out,
             _p: core::marker::PhantomData,
@@ -60,17 +189,16 @@ where
     }
 }
 
-impl<T, const R: usize, const C: usize, B> Mul<T> for Tensor2<T, R, C, B>
-where
-    T: Copy + Default + Mul<Output = T>,
-    B: ConstMul<T> + HasStorage<T, { R * C }>,
-{
-    type Output = Tensor2<T, R, C, B>;
-
-    #[inline]
-    fn mul(self, rhs: T) -> Self::Output {
+impl_scalar_op! {
+    impl<[T, const R: usize, const C: usize, B]>
+    Sub::sub(Tensor2<T, R, C, B>, T) -> Tensor2<T, R, C, B>
+    where {
+        T: Copy + Default + Sub<Output = T>,
+        B: ConstSub<T> + HasStorage<T, { R * C }>,
+    }
+    |a, k| {
         let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
-        B::constmul::<{ R * C }>(&self.storage, rhs, &mut out);
+        B::constsub::<{ R * C }>(&a.storage, k, &mut out);
         Tensor2 {
             storage: out,
             _p: core::marker::PhantomData,
@@ -78,17 +206,33 @@ where
     }
 }
 
-impl<T, const R: usize, const C: usize, B> Div<T> for Tensor2<T, R, C, B>
-where
-    T: Copy + Default + Div<Output = T>,
-    B: ConstDiv<T> + HasStorage<T, { R * C }>,
-{
-    type Output = Tensor2<T, R, C, B>;
+impl_scalar_op! {
+    impl<[T, const R: usize, const C: usize, B]>
+    Mul::mul(Tensor2<T, R, C, B>, T) -> Tensor2<T, R, C, B>
+    where {
+        T: Copy + Default + Mul<Output = T>,
+        B: ConstMul<T> + HasStorage<T, { R * C }>,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::constmul::<{ R * C }>(&a.storage, k, &mut out);
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
 
-    #[inline]
-    fn div(self, rhs: T) -> Self::Output {
+impl_scalar_op! {
+    impl<[T, const R: usize, const C: usize, B]>
+    Div::div(Tensor2<T, R, C, B>, T) -> Tensor2<T, R, C, B>
+    where {
+        T: Copy + Default + Div<Output = T>,
+        B: ConstDiv<T> + HasStorage<T, { R * C }>,
+    }
+    |a, k| {
         let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
-        B::constdiv::<{ R * C }>(&self.storage, rhs, &mut out);
+        B::constdiv::<{ R * C }>(&a.storage, k, &mut out);
         Tensor2 {
             storage: out,
             _p: core::marker::PhantomData,
@@ -96,96 +240,417 @@ where
     }
 }
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, B> Add<T> for Tensor3<T, D0, D1, D2, B>
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    Add::add(Tensor3<T, D0, D1, D2, B>, T) -> Tensor3<T, D0, D1, D2, B>
+    where {
+        T: Copy + Default + Add<Output = T>,
+        B: ConstAdd<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::constadd::<{ D0 * (D1 * D2) }>(&a.storage, k, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    Sub::sub(Tensor3<T, D0, D1, D2, B>, T) -> Tensor3<T, D0, D1, D2, B>
+    where {
+        T: Copy + Default + Sub<Output = T>,
+        B: ConstSub<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::constsub::<{ D0 * (D1 * D2) }>(&a.storage, k, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    Mul::mul(Tensor3<T, D0, D1, D2, B>, T) -> Tensor3<T, D0, D1, D2, B>
+    where {
+        T: Copy + Default + Mul<Output = T>,
+        B: ConstMul<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::constmul::<{ D0 * (D1 * D2) }>(&a.storage, k, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    Div::div(Tensor3<T, D0, D1, D2, B>, T) -> Tensor3<T, D0, D1, D2, B>
+    where {
+        T: Copy + Default + Div<Output = T>,
+        B: ConstDiv<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::constdiv::<{ D0 * (D1 * D2) }>(&a.storage, k, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Add::add(Tensor4<T, D0, D1, D3, D4, B>, T) -> Tensor4<T, D0, D1, D3, D4, B>
+    where {
+        T: Copy + Default + Add<Output = T>,
+        B: ConstAdd<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::constadd::<{ D0 * (D1 * (D3 * D4)) }>(&a.storage, k, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Sub::sub(Tensor4<T, D0, D1, D3, D4, B>, T) -> Tensor4<T, D0, D1, D3, D4, B>
+    where {
+        T: Copy + Default + Sub<Output = T>,
+        B: ConstSub<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::constsub::<{ D0 * (D1 * (D3 * D4)) }>(&a.storage, k, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Mul::mul(Tensor4<T, D0, D1, D3, D4, B>, T) -> Tensor4<T, D0, D1, D3, D4, B>
+    where {
+        T: Copy + Default + Mul<Output = T>,
+        B: ConstMul<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::constmul::<{ D0 * (D1 * (D3 * D4)) }>(&a.storage, k, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Div::div(Tensor4<T, D0, D1, D3, D4, B>, T) -> Tensor4<T, D0, D1, D3, D4, B>
+    where {
+        T: Copy + Default + Div<Output = T>,
+        B: ConstDiv<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::constdiv::<{ D0 * (D1 * (D3 * D4)) }>(&a.storage, k, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, const R: usize, const C: usize, B> AddAssign<T> for Tensor2<T, R, C, B>
+where
+    T: Copy + Default + Add<Output = T>,
+    B: ConstAdd<T> + HasStorage<T, { R * C }>,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: T) {
+        B::constadd_assign::<{ R * C }>(&mut self.storage, rhs);
+    }
+}
+
+impl<T, const R: usize, const C: usize, B> SubAssign<T> for Tensor2<T, R, C, B>
+where
+    T: Copy + Default + Sub<Output = T>,
+    B: ConstSub<T> + HasStorage<T, { R * C }>,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: T) {
+        B::constsub_assign::<{ R * C }>(&mut self.storage, rhs);
+    }
+}
+
+impl<T, const R: usize, const C: usize, B> MulAssign<T> for Tensor2<T, R, C, B>
+where
+    T: Copy + Default + Mul<Output = T>,
+    B: ConstMul<T> + HasStorage<T, { R * C }>,
+{
+    #[inline]
+    fn mul_assign(&mut self, rhs: T) {
+        B::constmul_assign::<{ R * C }>(&mut self.storage, rhs);
+    }
+}
+
+impl<T, const R: usize, const C: usize, B> DivAssign<T> for Tensor2<T, R, C, B>
+where
+    T: Copy + Default + Div<Output = T>,
+    B: ConstDiv<T> + HasStorage<T, { R * C }>,
+{
+    #[inline]
+    fn div_assign(&mut self, rhs: T) {
+        B::constdiv_assign::<{ R * C }>(&mut self.storage, rhs);
+    }
+}
+
+impl<T, const D0: usize, const D1: usize, const D2: usize, B> AddAssign<T>
+    for Tensor3<T, D0, D1, D2, B>
 where
     T: Copy + Default + Add<Output = T>,
     B: ConstAdd<T> + HasStorage<T, { D0 * (D1 * D2) }>,
     [(); D0 * (D1 * D2)]:,
 {
-    type Output = Tensor3<T, D0, D1, D2, B>;
+    #[inline]
+    fn add_assign(&mut self, rhs: T) {
+        B::constadd_assign::<{ D0 * (D1 * D2) }>(&mut self.storage, rhs);
+    }
+}
 
+impl<T, const D0: usize, const D1: usize, const D2: usize, B> SubAssign<T>
+    for Tensor3<T, D0, D1, D2, B>
+where
+    T: Copy + Default + Sub<Output = T>,
+    B: ConstSub<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+    [(); D0 * (D1 * D2)]:,
+{
     #[inline]
-    fn add(self, rhs: T) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
-        B::constadd::<{ D0 * (D1 * D2) }>(&self.storage, rhs, &mut out);
-        Self {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
+    fn sub_assign(&mut self, rhs: T) {
+        B::constsub_assign::<{ D0 * (D1 * D2) }>(&mut self.storage, rhs);
     }
 }
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, B> Mul<T> for Tensor3<T, D0, D1, D2, B>
+impl<T, const D0: usize, const D1: usize, const D2: usize, B> MulAssign<T>
+    for Tensor3<T, D0, D1, D2, B>
 where
     T: Copy + Default + Mul<Output = T>,
     B: ConstMul<T> + HasStorage<T, { D0 * (D1 * D2) }>,
     [(); D0 * (D1 * D2)]:,
 {
-    type Output = Tensor3<T, D0, D1, D2, B>;
-
     #[inline]
-    fn mul(self, rhs: T) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
-        B::constmul::<{ D0 * (D1 * D2) }>(&self.storage, rhs, &mut out);
-        Self {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
+    fn mul_assign(&mut self, rhs: T) {
+        B::constmul_assign::<{ D0 * (D1 * D2) }>(&mut self.storage, rhs);
     }
 }
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, B> Div<T> for Tensor3<T, D0, D1, D2, B>
+impl<T, const D0: usize, const D1: usize, const D2: usize, B> DivAssign<T>
+    for Tensor3<T, D0, D1, D2, B>
 where
     T: Copy + Default + Div<Output = T>,
     B: ConstDiv<T> + HasStorage<T, { D0 * (D1 * D2) }>,
     [(); D0 * (D1 * D2)]:,
 {
-    type Output = Tensor3<T, D0, D1, D2, B>;
-
     #[inline]
-    fn div(self, rhs: T) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
-        B::constdiv::<{ D0 * (D1 * D2) }>(&self.storage, rhs, &mut out);
-        Self {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
+    fn div_assign(&mut self, rhs: T) {
+        B::constdiv_assign::<{ D0 * (D1 * D2) }>(&mut self.storage, rhs);
     }
 }
 
-impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B> Add<T>
+impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B> AddAssign<T>
     for Tensor4<T, D0, D1, D3, D4, B>
 where
     T: Copy + Default + Add<Output = T>,
     B: ConstAdd<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
     [(); D0 * (D1 * (D3 * D4))]:,
 {
-    type Output = Tensor4<T, D0, D1, D3, D4, B>;
+    #[inline]
+    fn add_assign(&mut self, rhs: T) {
+        B::constadd_assign::<{ D0 * (D1 * (D3 * D4)) }>(&mut self.storage, rhs);
+    }
+}
 
+impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B> SubAssign<T>
+    for Tensor4<T, D0, D1, D3, D4, B>
+where
+    T: Copy + Default + Sub<Output = T>,
+    B: ConstSub<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+    [(); D0 * (D1 * (D3 * D4))]:,
+{
     #[inline]
-    fn add(self, rhs: T) -> Self::Output {
-        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
-        B::constadd::<{ D0 * (D1 * (D3 * D4)) }>(&self.storage, rhs, &mut out);
-        Self {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
+    fn sub_assign(&mut self, rhs: T) {
+        B::constsub_assign::<{ D0 * (D1 * (D3 * D4)) }>(&mut self.storage, rhs);
     }
 }
 
-impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B> Mul<T>
+impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B> MulAssign<T>
     for Tensor4<T, D0, D1, D3, D4, B>
 where
     T: Copy + Default + Mul<Output = T>,
     B: ConstMul<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
     [(); D0 * (D1 * (D3 * D4))]:,
 {
-    type Output = Tensor4<T, D0, D1, D3, D4, B>;
+    #[inline]
+    fn mul_assign(&mut self, rhs: T) {
+        B::constmul_assign::<{ D0 * (D1 * (D3 * D4)) }>(&mut self.storage, rhs);
+    }
+}
 
+impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B> DivAssign<T>
+    for Tensor4<T, D0, D1, D3, D4, B>
+where
+    T: Copy + Default + Div<Output = T>,
+    B: ConstDiv<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+    [(); D0 * (D1 * (D3 * D4))]:,
+{
     #[inline]
-    fn mul(self, rhs: T) -> Self::Output {
+    fn div_assign(&mut self, rhs: T) {
+        B::constdiv_assign::<{ D0 * (D1 * (D3 * D4)) }>(&mut self.storage, rhs);
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const R: usize, const C: usize, B]>
+    Maximum::maximum(Tensor2<T, R, C, B>, T) -> Tensor2<T, R, C, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ConstMax<T> + HasStorage<T, { R * C }>,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::constmax::<{ R * C }>(&a.storage, k, &mut out);
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const R: usize, const C: usize, B]>
+    Minimum::minimum(Tensor2<T, R, C, B>, T) -> Tensor2<T, R, C, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ConstMin<T> + HasStorage<T, { R * C }>,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::constmin::<{ R * C }>(&a.storage, k, &mut out);
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    Maximum::maximum(Tensor3<T, D0, D1, D2, B>, T) -> Tensor3<T, D0, D1, D2, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ConstMax<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::constmax::<{ D0 * (D1 * D2) }>(&a.storage, k, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    Minimum::minimum(Tensor3<T, D0, D1, D2, B>, T) -> Tensor3<T, D0, D1, D2, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ConstMin<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::constmin::<{ D0 * (D1 * D2) }>(&a.storage, k, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Maximum::maximum(Tensor4<T, D0, D1, D3, D4, B>, T) -> Tensor4<T, D0, D1, D3, D4, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ConstMax<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, k| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::constmax::<{ D0 * (D1 * (D3 * D4)) }>(&a.storage, k, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_scalar_op! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Minimum::minimum(Tensor4<T, D0, D1, D3, D4, B>, T) -> Tensor4<T, D0, D1, D3, D4, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ConstMin<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, k| {
         let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
-        B::constmul::<{ D0 * (D1 * (D3 * D4)) }>(&self.storage, rhs, &mut out);
+        B::constmin::<{ D0 * (D1 * (D3 * D4)) }>(&a.storage, k, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, const R: usize, const C: usize, B> Tensor2<T, R, C, B>
+where
+    T: Copy + Default + PartialOrd,
+    B: ConstMax<T> + ConstMin<T> + HasStorage<T, { R * C }>,
+{
+    /// Limits every element to the closed interval `[min, max]`. NaN elements
+    /// stay NaN.
+    #[inline]
+    pub fn clamp(self, min: T, max: T) -> Self {
+        let mut lower = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::constmax::<{ R * C }>(&self.storage, min, &mut lower);
+        let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::constmin::<{ R * C }>(&lower, max, &mut out);
         Self {
             storage: out,
             _p: core::marker::PhantomData,
@@ -193,22 +658,52 @@ where
     }
 }
 
-impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B> Div<T>
-    for Tensor4<T, D0, D1, D3, D4, B>
+impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
 where
-    T: Copy + Default + Div<Output = T>,
-    B: ConstDiv<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
-    [(); D0 * (D1 * (D3 * D4))]:,
+    T: Copy + Default + PartialOrd,
+    B: ConstMax<T> + ConstMin<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+    [(); D0 * (D1 * D2)]:,
 {
-    type Output = Tensor4<T, D0, D1, D3, D4, B>;
+    /// Limits every element to the closed interval `[min, max]`. NaN elements
+    /// stay NaN.
+    #[inline]
+    pub fn clamp(self, min: T, max: T) -> Self {
+        let mut lower = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::constmax::<{ D0 * (D1 * D2) }>(&self.storage, min, &mut lower);
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::constmin::<{ D0 * (D1 * D2) }>(&lower, max, &mut out);
+        Self {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
 
+impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
+    Tensor4<T, D0, D1, D3, D4, B>
+where
+    T: Copy + Default + PartialOrd,
+    B: ConstMax<T> + ConstMin<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+    [(); D0 * (D1 * (D3 * D4))]:,
+{
+    /// Limits every element to the closed interval `[min, max]`. NaN elements
+    /// stay NaN.
     #[inline]
-    fn div(self, rhs: T) -> Self::Output {
+    pub fn clamp(self, min: T, max: T) -> Self {
+        let mut lower = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::constmax::<{ D0 * (D1 * (D3 * D4)) }>(&self.storage, min, &mut lower);
         let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
-        B::constdiv::<{ D0 * (D1 * (D3 * D4)) }>(&self.storage, rhs, &mut out);
+        B::constmin::<{ D0 * (D1 * (D3 * D4)) }>(&lower, max, &mut out);
         Self {
             storage: out,
             _p: core::marker::PhantomData,
         }
     }
 }
+
+impl_scalar_lhs!(f32; Tensor2 [R, C], { R * C });
+impl_scalar_lhs!(f32; Tensor3 [D0, D1, D2], { D0 * (D1 * D2) });
+impl_scalar_lhs!(f32; Tensor4 [D0, D1, D3, D4], { D0 * (D1 * (D3 * D4)) });
+impl_scalar_lhs!(f64; Tensor2 [R, C], { R * C });
+impl_scalar_lhs!(f64; Tensor3 [D0, D1, D2], { D0 * (D1 * D2) });
+impl_scalar_lhs!(f64; Tensor4 [D0, D1, D3, D4], { D0 * (D1 * (D3 * D4)) });
@@ -1,7 +1,9 @@
 use crate::storage::HasStorage;
 use crate::storage::naive_cpu::NaiveCpu;
-use crate::tensor_ops::const_ops::{ConstAdd, ConstDiv, ConstMul};
-use core::ops::{Add, Div, Mul};
+use crate::tensor_ops::const_ops::{ConstAdd, ConstDiv, ConstMax, ConstMin, ConstMul, ConstSub};
+use crate::tensor_ops::{nan_max, nan_min};
+use core::cmp::PartialOrd;
+use core::ops::{Add, Div, Mul, Sub};
 
 impl<T> ConstAdd<T> for NaiveCpu
 where
@@ -20,6 +22,57 @@ where
             *d = *s + k;
         }
     }
+
+    fn constadd_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        Self: HasStorage<T, N>,
+    {
+        for v in <Self as HasStorage<T, N>>::as_mut_slice(a).iter_mut() {
+            *v = *v + k;
+        }
+    }
+}
+
+impl<T> ConstSub<T> for NaiveCpu
+where
+    T: Copy + Default + Sub<Output = T>,
+{
+    fn constsub<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for (d, s) in dst.iter_mut().zip(src.iter()) {
+            *d = *s - k;
+        }
+    }
+
+    fn constsub_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        Self: HasStorage<T, N>,
+    {
+        for v in <Self as HasStorage<T, N>>::as_mut_slice(a).iter_mut() {
+            *v = *v - k;
+        }
+    }
+
+    fn rconstsub<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for (d, s) in dst.iter_mut().zip(src.iter()) {
+            *d = k - *s;
+        }
+    }
 }
 
 impl<T> ConstMul<T> for NaiveCpu
@@ -39,6 +92,15 @@ where
             *d = *s * k;
         }
     }
+
+    fn constmul_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        Self: HasStorage<T, N>,
+    {
+        for v in <Self as HasStorage<T, N>>::as_mut_slice(a).iter_mut() {
+            *v = *v * k;
+        }
+    }
 }
 
 impl<T> ConstDiv<T> for NaiveCpu
@@ -58,4 +120,122 @@ where
             *d = *s / k;
         }
     }
+
+    fn constdiv_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        Self: HasStorage<T, N>,
+    {
+        for v in <Self as HasStorage<T, N>>::as_mut_slice(a).iter_mut() {
+            *v = *v / k;
+        }
+    }
+
+    fn rconstdiv<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for (d, s) in dst.iter_mut().zip(src.iter()) {
+            *d = k / *s;
+        }
+    }
+}
+
+impl<T> ConstMax<T> for NaiveCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn constmax<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for (d, s) in dst.iter_mut().zip(src.iter()) {
+            *d = nan_max(*s, k);
+        }
+    }
+}
+
+impl<T> ConstMin<T> for NaiveCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn constmin<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for (d, s) in dst.iter_mut().zip(src.iter()) {
+            *d = nan_min(*s, k);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Tensor2, Tensor3, Tensor4};
+    use crate::tensor_ops::elemwise::{Maximum, Minimum};
+
+    #[test]
+    fn test_scalar_assign_ops() {
+        let mut t = Tensor2::<f32, 2, 2, NaiveCpu>::new([1.0, 2.0, 3.0, 4.0]);
+        t += 1.0;
+        assert_eq!(t.as_slice(), &[2.0, 3.0, 4.0, 5.0]);
+        t -= 2.0;
+        assert_eq!(t.as_slice(), &[0.0, 1.0, 2.0, 3.0]);
+        t *= 4.0;
+        assert_eq!(t.as_slice(), &[0.0, 4.0, 8.0, 12.0]);
+        t /= 2.0;
+        assert_eq!(t.as_slice(), &[0.0, 2.0, 4.0, 6.0]);
+
+        let mut u = Tensor4::<i32, 1, 1, 2, 2, NaiveCpu>::new([1, 2, 3, 4]);
+        u -= 1;
+        u *= 3;
+        assert_eq!(u.as_slice(), &[0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn test_scalar_sub_and_reversed_operands() {
+        let t = Tensor2::<f32, 1, 3, NaiveCpu>::new([1.0, 2.0, 4.0]);
+        assert_eq!((t - 1.0).as_slice(), &[0.0, 1.0, 3.0]);
+        assert_eq!((1.0 - t).as_slice(), &[0.0, -1.0, -3.0]);
+        assert_eq!((2.0 * t).as_slice(), &[2.0, 4.0, 8.0]);
+        assert_eq!((1.0 / &t).as_slice(), &[1.0, 0.5, 0.25]);
+        assert_eq!((0.5 + t).as_slice(), &[1.5, 2.5, 4.5]);
+
+        let u = Tensor4::<f64, 1, 1, 1, 2, NaiveCpu>::new([2.0, 8.0]);
+        assert_eq!((16.0 / u).as_slice(), &[8.0, 2.0]);
+    }
+
+    #[test]
+    fn test_clamp_and_scalar_min_max() {
+        let t = Tensor3::<i32, 1, 1, 5, NaiveCpu>::new([-5, -1, 0, 3, 9]);
+        assert_eq!(t.clamp(-2, 4).as_slice(), &[-2, -1, 0, 3, 4]);
+        assert_eq!(t.maximum(0).as_slice(), &[0, 0, 0, 3, 9]);
+        assert_eq!((&t).minimum(0).as_slice(), &[-5, -1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_clamp_propagates_nan() {
+        let t = Tensor2::<f32, 1, 3, NaiveCpu>::new([f32::NAN, -3.0, 3.0]);
+        let c = t.clamp(-1.0, 1.0);
+        assert!(c.as_slice()[0].is_nan());
+        assert_eq!(&c.as_slice()[1..], &[-1.0, 1.0]);
+        assert!(t.maximum(0.0).as_slice()[0].is_nan());
+        assert!(t.minimum(0.0).as_slice()[0].is_nan());
+        assert!(t.maximum(f32::NAN).as_slice().iter().all(|x| x.is_nan()));
+    }
 }
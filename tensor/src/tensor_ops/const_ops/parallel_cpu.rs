@@ -0,0 +1,145 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::const_ops::{ConstAdd, ConstDiv, ConstMax, ConstMin, ConstMul, ConstSub};
+use core::cmp::PartialOrd;
+use core::ops::{Add, Div, Mul, Sub};
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> ConstAdd<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T>,
+{
+    fn constadd<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstAdd<T>>::constadd::<N>(naive::<T, N>(a), k, naive_mut::<T, N>(out))
+    }
+
+    fn constadd_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstAdd<T>>::constadd_assign::<N>(naive_mut::<T, N>(a), k)
+    }
+}
+
+impl<T> ConstSub<T> for ParallelCpu
+where
+    T: Copy + Default + Sub<Output = T>,
+{
+    fn constsub<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstSub<T>>::constsub::<N>(naive::<T, N>(a), k, naive_mut::<T, N>(out))
+    }
+
+    fn constsub_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstSub<T>>::constsub_assign::<N>(naive_mut::<T, N>(a), k)
+    }
+
+    fn rconstsub<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstSub<T>>::rconstsub::<N>(naive::<T, N>(a), k, naive_mut::<T, N>(out))
+    }
+}
+
+impl<T> ConstMul<T> for ParallelCpu
+where
+    T: Copy + Default + Mul<Output = T>,
+{
+    fn constmul<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstMul<T>>::constmul::<N>(naive::<T, N>(a), k, naive_mut::<T, N>(out))
+    }
+
+    fn constmul_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstMul<T>>::constmul_assign::<N>(naive_mut::<T, N>(a), k)
+    }
+}
+
+impl<T> ConstDiv<T> for ParallelCpu
+where
+    T: Copy + Default + Div<Output = T>,
+{
+    fn constdiv<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstDiv<T>>::constdiv::<N>(naive::<T, N>(a), k, naive_mut::<T, N>(out))
+    }
+
+    fn constdiv_assign<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage, k: T)
+    where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstDiv<T>>::constdiv_assign::<N>(naive_mut::<T, N>(a), k)
+    }
+
+    fn rconstdiv<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstDiv<T>>::rconstdiv::<N>(naive::<T, N>(a), k, naive_mut::<T, N>(out))
+    }
+}
+
+impl<T> ConstMax<T> for ParallelCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn constmax<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstMax<T>>::constmax::<N>(naive::<T, N>(a), k, naive_mut::<T, N>(out))
+    }
+}
+
+impl<T> ConstMin<T> for ParallelCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn constmin<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        k: T,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as ConstMin<T>>::constmin::<N>(naive::<T, N>(a), k, naive_mut::<T, N>(out))
+    }
+}
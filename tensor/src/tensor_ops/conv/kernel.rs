@@ -0,0 +1,331 @@
+//! 2-D convolution loops shared by the CPU backends.
+//!
+//! The single-image loops work on a band of rows and the NCHW ones on a run of
+//! whole output planes, so callers can split the work across threads; passing
+//! everything gives the plain convolution.
+
+use crate::tensor_ops::conv::{Padding, padded_out_len};
+use crate::tensor_ops::conv2d::Conv2dShape;
+use core::ops::{Add, Mul};
+
+/// Geometry of a single-channel 2-D convolution: strides `sh` and `sw`,
+/// `pt`, `pb`, `pl` and `pr` zeroes on the top, bottom, left and right, and
+/// the same dilation along both axes.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Conv2Geom {
+    pub h: usize,
+    pub w: usize,
+    pub kh: usize,
+    pub kw: usize,
+    pub sh: usize,
+    pub sw: usize,
+    pub pt: usize,
+    pub pb: usize,
+    pub pl: usize,
+    pub pr: usize,
+    pub dilation: usize,
+}
+
+impl Conv2Geom {
+    pub fn new(
+        [h, w]: [usize; 2],
+        [kh, kw]: [usize; 2],
+        [sh, sw]: [usize; 2],
+        dilation: usize,
+        padding: Padding,
+    ) -> Self {
+        Conv2Geom {
+            h,
+            w,
+            kh,
+            kw,
+            sh,
+            sw,
+            pt: padding.top(h, kh, sh, dilation),
+            pb: padding.bottom(h, kh, sh, dilation),
+            pl: padding.left(w, kw, sw, dilation),
+            pr: padding.right(w, kw, sw, dilation),
+            dilation,
+        }
+    }
+
+    #[inline]
+    pub fn out_h(&self) -> usize {
+        padded_out_len(self.h, self.kh, self.sh, self.pt, self.pb, self.dilation)
+    }
+
+    #[inline]
+    pub fn out_w(&self) -> usize {
+        padded_out_len(self.w, self.kw, self.sw, self.pl, self.pr, self.dilation)
+    }
+}
+
+/// Computes output rows `first..` of one image into `out`, which holds a
+/// whole number of output rows.
+pub(crate) fn conv2_rows<T>(g: &Conv2Geom, inp: &[T], ker: &[T], first: usize, out: &mut [T])
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    let out_w = g.out_w();
+    for (r, row) in out.chunks_mut(out_w).enumerate() {
+        let i = first + r;
+        for (j, o) in row.iter_mut().enumerate() {
+            let mut acc = T::default();
+            for ki in 0..g.kh {
+                for kj in 0..g.kw {
+                    let hi = i * g.sh + ki * g.dilation;
+                    let wj = j * g.sw + kj * g.dilation;
+                    if hi >= g.pt && hi < g.h + g.pt && wj >= g.pl && wj < g.w + g.pl {
+                        acc = acc + inp[(hi - g.pt) * g.w + (wj - g.pl)] * ker[ki * g.kw + kj];
+                    }
+                }
+            }
+            *o = acc;
+        }
+    }
+}
+
+/// Computes input-gradient rows `first..` of one image into `grad_in`, which
+/// holds a whole number of input rows.
+///
+/// Each input pixel gathers from the outputs it contributed to, so bands of
+/// rows are independent. Kernel offsets run backwards so contributions are
+/// summed in increasing output order.
+pub(crate) fn conv2_backward_rows<T>(
+    g: &Conv2Geom,
+    ker: &[T],
+    grad_out: &[T],
+    first: usize,
+    grad_in: &mut [T],
+) where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    let (out_h, out_w) = (g.out_h(), g.out_w());
+    for (r, row) in grad_in.chunks_mut(g.w).enumerate() {
+        let hp = first + r + g.pt;
+        for (x, gi) in row.iter_mut().enumerate() {
+            let wp = x + g.pl;
+            let mut acc = T::default();
+            for ki in (0..g.kh).rev() {
+                let Some(i) = source(hp, ki * g.dilation, g.sh, out_h) else {
+                    continue;
+                };
+                for kj in (0..g.kw).rev() {
+                    let Some(j) = source(wp, kj * g.dilation, g.sw, out_w) else {
+                        continue;
+                    };
+                    acc = acc + ker[ki * g.kw + kj] * grad_out[i * out_w + j];
+                }
+            }
+            *gi = acc;
+        }
+    }
+}
+
+/// The output position whose tap at `offset` reads padded input position
+/// `pos`, if there is one.
+#[inline]
+fn source(pos: usize, offset: usize, stride: usize, out_len: usize) -> Option<usize> {
+    let span = pos.checked_sub(offset)?;
+    (span % stride == 0 && span / stride < out_len).then_some(span / stride)
+}
+
+/// Computes kernel-gradient rows `first..` into `grad_ker`, which holds a
+/// whole number of kernel rows, summing over the `batch` images in `inp` and
+/// their output gradients in `grad_out`.
+pub(crate) fn conv2_kernel_grad_rows<T>(
+    g: &Conv2Geom,
+    inp: &[T],
+    grad_out: &[T],
+    batch: usize,
+    first: usize,
+    grad_ker: &mut [T],
+) where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    let (out_h, out_w) = (g.out_h(), g.out_w());
+    for (r, row) in grad_ker.chunks_mut(g.kw).enumerate() {
+        let ki = first + r;
+        for (kj, gk) in row.iter_mut().enumerate() {
+            let mut acc = T::default();
+            for b in 0..batch {
+                let image = &inp[b * g.h * g.w..][..g.h * g.w];
+                let go = &grad_out[b * out_h * out_w..][..out_h * out_w];
+                let tap = [ki * g.dilation, kj * g.dilation];
+                let (stride, pad) = ([g.sh, g.sw], [g.pt, g.pl]);
+                acc = acc + tap_grad(stride, pad, [g.h, g.w], tap, image, go, out_w);
+            }
+            *gk = acc;
+        }
+    }
+}
+
+/// `sum_{i, j} grad_out[i, j] * image[i * sh + ki - pt, j * sw + kj - pl]`
+/// over the in-bounds input positions: the gradient of the kernel tap at
+/// (already dilated) offset `[ki, kj]`.
+fn tap_grad<T>(
+    [sh, sw]: [usize; 2],
+    [pt, pl]: [usize; 2],
+    [h, w]: [usize; 2],
+    [ki, kj]: [usize; 2],
+    image: &[T],
+    grad_out: &[T],
+    out_w: usize,
+) -> T
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    let mut acc = T::default();
+    for (i, go_row) in grad_out.chunks(out_w).enumerate() {
+        let hi = i * sh + ki;
+        if hi < pt || hi >= h + pt {
+            continue;
+        }
+        let in_row = &image[(hi - pt) * w..][..w];
+        for (j, &go) in go_row.iter().enumerate() {
+            let wj = j * sw + kj;
+            if wj >= pl && wj < w + pl {
+                acc = acc + go * in_row[wj - pl];
+            }
+        }
+    }
+    acc
+}
+
+/// Computes NCHW output planes `first..` into `out`, which holds a whole
+/// number of `out_h x out_w` planes. Plane `p` is image `p / c_out`, output
+/// channel `p % c_out`, which only sees the input channels of its group.
+pub(crate) fn conv2d_planes<T>(
+    s: &Conv2dShape,
+    inp: &[T],
+    weight: &[T],
+    bias: Option<&[T]>,
+    first: usize,
+    out: &mut [T],
+) where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    let (out_h, out_w) = (s.out_h(), s.out_w());
+    let (c_in_g, c_out_g) = (s.c_in / s.groups, s.c_out / s.groups);
+    for (q, plane) in out.chunks_mut(out_h * out_w).enumerate() {
+        let (n, co) = ((first + q) / s.c_out, (first + q) % s.c_out);
+        let group = co / c_out_g;
+        let start = bias.map_or(T::default(), |b| b[co]);
+        for (o, v) in plane.iter_mut().enumerate() {
+            let (i, j) = (o / out_w, o % out_w);
+            let mut acc = start;
+            for cg in 0..c_in_g {
+                let ci = group * c_in_g + cg;
+                let img = &inp[(n * s.c_in + ci) * s.h * s.w..][..s.h * s.w];
+                let ker = &weight[(co * c_in_g + cg) * s.kh * s.kw..][..s.kh * s.kw];
+                for ki in 0..s.kh {
+                    let hi = i * s.stride + ki * s.dilation;
+                    if hi < s.pad || hi >= s.h + s.pad {
+                        continue;
+                    }
+                    for kj in 0..s.kw {
+                        let wj = j * s.stride + kj * s.dilation;
+                        if wj >= s.pad && wj < s.w + s.pad {
+                            let x = img[(hi - s.pad) * s.w + (wj - s.pad)];
+                            acc = acc + x * ker[ki * s.kw + kj];
+                        }
+                    }
+                }
+            }
+            *v = acc;
+        }
+    }
+}
+
+/// Computes NCHW input-gradient planes `first..` into `grad_in`, which holds
+/// a whole number of `h x w` planes. Plane `p` is image `p / c_in`, input
+/// channel `p % c_in`.
+///
+/// Like [`conv2_backward_rows`], each input pixel gathers from the outputs it
+/// contributed to, so planes are independent.
+pub(crate) fn conv2d_backward_planes<T>(
+    s: &Conv2dShape,
+    weight: &[T],
+    grad_out: &[T],
+    first: usize,
+    grad_in: &mut [T],
+) where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    let (out_h, out_w) = (s.out_h(), s.out_w());
+    let (c_in_g, c_out_g) = (s.c_in / s.groups, s.c_out / s.groups);
+    for (q, plane) in grad_in.chunks_mut(s.h * s.w).enumerate() {
+        let (n, ci) = ((first + q) / s.c_in, (first + q) % s.c_in);
+        let (group, cg) = (ci / c_in_g, ci % c_in_g);
+        for (p, gi) in plane.iter_mut().enumerate() {
+            let (hp, wp) = (p / s.w + s.pad, p % s.w + s.pad);
+            let mut acc = T::default();
+            for co in group * c_out_g..(group + 1) * c_out_g {
+                let go = &grad_out[(n * s.c_out + co) * out_h * out_w..][..out_h * out_w];
+                let ker = &weight[(co * c_in_g + cg) * s.kh * s.kw..][..s.kh * s.kw];
+                for ki in (0..s.kh).rev() {
+                    let Some(i) = source(hp, ki * s.dilation, s.stride, out_h) else {
+                        continue;
+                    };
+                    for kj in (0..s.kw).rev() {
+                        let Some(j) = source(wp, kj * s.dilation, s.stride, out_w) else {
+                            continue;
+                        };
+                        acc = acc + ker[ki * s.kw + kj] * go[i * out_w + j];
+                    }
+                }
+            }
+            *gi = acc;
+        }
+    }
+}
+
+/// Computes NCHW weight-gradient planes `first..` into `grad_w`, which holds
+/// a whole number of `kh x kw` planes, summing over the batch. Plane `p` is
+/// output channel `p / (c_in / groups)` and the `p % (c_in / groups)`-th
+/// input channel of its group.
+pub(crate) fn conv2d_weight_grad_planes<T>(
+    s: &Conv2dShape,
+    inp: &[T],
+    grad_out: &[T],
+    first: usize,
+    grad_w: &mut [T],
+) where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    let (out_h, out_w) = (s.out_h(), s.out_w());
+    let (c_in_g, c_out_g) = (s.c_in / s.groups, s.c_out / s.groups);
+    for (q, plane) in grad_w.chunks_mut(s.kh * s.kw).enumerate() {
+        let (co, cg) = ((first + q) / c_in_g, (first + q) % c_in_g);
+        let ci = co / c_out_g * c_in_g + cg;
+        for (k, gw) in plane.iter_mut().enumerate() {
+            let tap = [k / s.kw * s.dilation, k % s.kw * s.dilation];
+            let mut acc = T::default();
+            for n in 0..s.batch {
+                let image = &inp[(n * s.c_in + ci) * s.h * s.w..][..s.h * s.w];
+                let go = &grad_out[(n * s.c_out + co) * out_h * out_w..][..out_h * out_w];
+                let (stride, pad) = ([s.stride; 2], [s.pad; 2]);
+                acc = acc + tap_grad(stride, pad, [s.h, s.w], tap, image, go, out_w);
+            }
+            *gw = acc;
+        }
+    }
+}
+
+/// `grad_b[c] = sum_{n, p} grad_out[n, c, p]` for `batch` images of
+/// `channels` planes of `plane` elements.
+pub(crate) fn conv2d_bias_grad<T>(
+    batch: usize,
+    channels: usize,
+    plane: usize,
+    grad_out: &[T],
+    grad_b: &mut [T],
+) where
+    T: Copy + Default + Add<Output = T>,
+{
+    for (c, gb) in grad_b[..channels].iter_mut().enumerate() {
+        *gb = (0..batch)
+            .flat_map(|n| &grad_out[(n * channels + c) * plane..][..plane])
+            .fold(T::default(), |acc, &v| acc + v);
+    }
+}
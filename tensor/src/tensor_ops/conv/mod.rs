@@ -1,43 +1,180 @@
 use crate::storage::HasStorage;
 use crate::tensor::Tensor2;
+use core::marker::ConstParamTy;
 use core::ops::{Add, Mul};
 
+pub(crate) mod kernel;
 pub mod naive_cpu;
+pub mod parallel_cpu;
 
+/// Output length of a convolution over `len` input positions: a kernel of
+/// `kernel` taps spaced `dilation` apart spans `dilation * (kernel - 1) + 1`
+/// positions of the padded input.
+pub const fn dilated_out_len(
+    len: usize,
+    kernel: usize,
+    stride: usize,
+    pad: usize,
+    dilation: usize,
+) -> usize {
+    padded_out_len(len, kernel, stride, pad, pad, dilation)
+}
+
+/// [`dilated_out_len`] with `before` and `after` zeroes on either end instead of
+/// `pad` on both.
+pub const fn padded_out_len(
+    len: usize,
+    kernel: usize,
+    stride: usize,
+    before: usize,
+    after: usize,
+    dilation: usize,
+) -> usize {
+    (len + before + after - dilation * (kernel - 1) - 1) / stride + 1
+}
+
+/// How much zero padding a convolution adds around its input.
+///
+/// Used as a const generic, so the padding on every side, and with it the
+/// output shape, is worked out at compile time from the input, kernel,
+/// stride and dilation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ConstParamTy)]
+pub enum Padding {
+    /// No padding: only positions where the whole kernel fits.
+    Valid,
+    /// Just enough padding for `ceil(len / stride)` outputs per axis, split
+    /// evenly with any odd zero going after (bottom or right).
+    Same,
+    /// The given number of zeroes on each side.
+    Explicit {
+        top: usize,
+        bottom: usize,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl Padding {
+    /// Zero rows added above an `h`-row input.
+    pub const fn top(self, h: usize, kh: usize, sh: usize, dilation: usize) -> usize {
+        match self {
+            Padding::Valid => 0,
+            Padding::Same => same_pad(h, kh, sh, dilation) / 2,
+            Padding::Explicit { top, .. } => top,
+        }
+    }
+
+    /// Zero rows added below an `h`-row input.
+    pub const fn bottom(self, h: usize, kh: usize, sh: usize, dilation: usize) -> usize {
+        match self {
+            Padding::Valid => 0,
+            Padding::Same => same_pad(h, kh, sh, dilation) - same_pad(h, kh, sh, dilation) / 2,
+            Padding::Explicit { bottom, .. } => bottom,
+        }
+    }
+
+    /// Zero columns added left of a `w`-column input.
+    pub const fn left(self, w: usize, kw: usize, sw: usize, dilation: usize) -> usize {
+        match self {
+            Padding::Valid => 0,
+            Padding::Same => same_pad(w, kw, sw, dilation) / 2,
+            Padding::Explicit { left, .. } => left,
+        }
+    }
+
+    /// Zero columns added right of a `w`-column input.
+    pub const fn right(self, w: usize, kw: usize, sw: usize, dilation: usize) -> usize {
+        match self {
+            Padding::Valid => 0,
+            Padding::Same => same_pad(w, kw, sw, dilation) - same_pad(w, kw, sw, dilation) / 2,
+            Padding::Explicit { right, .. } => right,
+        }
+    }
+
+    /// The same `pad` zeroes on every side.
+    pub const fn symmetric(pad: usize) -> Self {
+        Padding::Explicit { top: pad, bottom: pad, left: pad, right: pad }
+    }
+
+    /// Output rows of a convolution over an `h`-row input.
+    pub const fn out_h(self, h: usize, kh: usize, sh: usize, dilation: usize) -> usize {
+        let (top, bottom) = (self.top(h, kh, sh, dilation), self.bottom(h, kh, sh, dilation));
+        padded_out_len(h, kh, sh, top, bottom, dilation)
+    }
+
+    /// Output columns of a convolution over a `w`-column input.
+    pub const fn out_w(self, w: usize, kw: usize, sw: usize, dilation: usize) -> usize {
+        let (left, right) = (self.left(w, kw, sw, dilation), self.right(w, kw, sw, dilation));
+        padded_out_len(w, kw, sw, left, right, dilation)
+    }
+}
+
+/// Total padding along one axis for [`Padding::Same`].
+const fn same_pad(len: usize, kernel: usize, stride: usize, dilation: usize) -> usize {
+    let span = (len.div_ceil(stride) - 1) * stride + dilation * (kernel - 1) + 1;
+    span.saturating_sub(len)
+}
+
+/// Single-channel 2-D convolution with strides `SH` and `SW` along the rows
+/// and columns, `DILATION` (the spacing between kernel taps, `1` for a dense
+/// kernel) on both axes and the zero padding on each side given by `PADDING`.
 pub trait Conv2<T: Copy + Default>: Sized {
     fn conv2<
         const H: usize,
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         input: &<Self as HasStorage<T, { H * W }>>::Storage,
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
         output:
-            &mut <Self as HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>>::Storage,
+            &mut <Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::Storage,
     ) where
         Self: HasStorage<T, { H * W }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>;
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>;
 
     fn conv2_backward<
         const H: usize,
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
         grad_output:
-            &<Self as HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>>::Storage,
+            &<Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::Storage,
         grad_input: &mut <Self as HasStorage<T, { H * W }>>::Storage,
     ) where
         Self: HasStorage<T, { H * W }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>;
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>;
+
+    /// Gradient of [`Self::conv2`] with respect to its kernel.
+    fn conv2_backward_kernel<
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { H * W }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::Storage,
+        grad_kernel: &mut <Self as HasStorage<T, { KH * KW }>>::Storage,
+    ) where
+        Self: HasStorage<T, { H * W }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>;
 }
 
 impl<T, const H: usize, const W: usize, B> Tensor2<T, H, W, B>
@@ -45,17 +182,20 @@ where
     T: Copy + Default + Add<Output = T> + Mul<Output = T>,
     B: HasStorage<T, { H * W }>,
 {
+    /// Convolves `self` with `kernel` using stride `STRIDE` and `PAD` zeroes on
+    /// every side. Dilated kernels and per-axis strides or padding go through
+    /// [`Self::convolve_padded`].
     pub fn convolve<const KH: usize, const KW: usize, const STRIDE: usize, const PAD: usize>(
         &self,
         kernel: &Tensor2<T, KH, KW, B>,
-    ) -> Tensor2<T, { (H + 2 * PAD - KH) / STRIDE + 1 }, { (W + 2 * PAD - KW) / STRIDE + 1 }, B>
+    ) -> Tensor2<T, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) }, { Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }, B>
     where
         B: Conv2<T>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>,
+            + HasStorage<T, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }>,
     {
-        let mut out = <B as HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>>::storage_uninit();
-        B::conv2::<H, W, KH, KW, STRIDE, PAD>(&self.storage, &kernel.storage, &mut out);
+        let mut out = <B as HasStorage<T, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }>>::storage_uninit();
+        B::conv2::<H, W, KH, KW, STRIDE, STRIDE, 1, { Padding::symmetric(PAD) }>(&self.storage, &kernel.storage, &mut out);
         Tensor2 {
             storage: out,
             _p: core::marker::PhantomData,
@@ -63,16 +203,16 @@ where
     }
 
     pub fn conv2_backward<const KH: usize, const KW: usize, const STRIDE: usize, const PAD: usize>(
-        grad_output: &Tensor2<T, { (H + 2 * PAD - KH) / STRIDE + 1 }, { (W + 2 * PAD - KW) / STRIDE + 1 }, B>,
+        grad_output: &Tensor2<T, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) }, { Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }, B>,
         kernel: &Tensor2<T, KH, KW, B>,
     ) -> Self
     where
         B: Conv2<T>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>,
+            + HasStorage<T, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }>,
     {
         let mut grad_input = <B as HasStorage<T, { H * W }>>::storage_uninit();
-        B::conv2_backward::<H, W, KH, KW, STRIDE, PAD>(
+        B::conv2_backward::<H, W, KH, KW, STRIDE, STRIDE, 1, { Padding::symmetric(PAD) }>(
             &kernel.storage,
             &grad_output.storage,
             &mut grad_input,
@@ -82,4 +222,112 @@ where
             _p: core::marker::PhantomData,
         }
     }
+
+    /// Gradient of [`Self::convolve`] with respect to its kernel, where `self`
+    /// is the forward input.
+    pub fn conv2_backward_kernel<const KH: usize, const KW: usize, const STRIDE: usize, const PAD: usize>(
+        &self,
+        grad_output: &Tensor2<T, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) }, { Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }, B>,
+    ) -> Tensor2<T, KH, KW, B>
+    where
+        B: Conv2<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { Padding::symmetric(PAD).out_h(H, KH, STRIDE, 1) * Padding::symmetric(PAD).out_w(W, KW, STRIDE, 1) }>,
+    {
+        let mut grad_kernel = <B as HasStorage<T, { KH * KW }>>::storage_uninit();
+        B::conv2_backward_kernel::<H, W, KH, KW, STRIDE, STRIDE, 1, { Padding::symmetric(PAD) }>(
+            &self.storage,
+            &grad_output.storage,
+            &mut grad_kernel,
+        );
+        Tensor2 {
+            storage: grad_kernel,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// [`Self::convolve`] with strides `SH` and `SW` along the rows and
+    /// columns and the zero padding on each side given by `PADDING`.
+    pub fn convolve_padded<
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        &self,
+        kernel: &Tensor2<T, KH, KW, B>,
+    ) -> Tensor2<T, { PADDING.out_h(H, KH, SH, DILATION) }, { PADDING.out_w(W, KW, SW, DILATION) }, B>
+    where
+        B: Conv2<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>,
+    {
+        let mut out = <B as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::storage_uninit();
+        B::conv2::<H, W, KH, KW, SH, SW, DILATION, PADDING>(&self.storage, &kernel.storage, &mut out);
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::convolve_padded`] with respect to its input.
+    pub fn conv2_backward_padded<
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        grad_output: &Tensor2<T, { PADDING.out_h(H, KH, SH, DILATION) }, { PADDING.out_w(W, KW, SW, DILATION) }, B>,
+        kernel: &Tensor2<T, KH, KW, B>,
+    ) -> Self
+    where
+        B: Conv2<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>,
+    {
+        let mut grad_input = <B as HasStorage<T, { H * W }>>::storage_uninit();
+        B::conv2_backward::<H, W, KH, KW, SH, SW, DILATION, PADDING>(
+            &kernel.storage,
+            &grad_output.storage,
+            &mut grad_input,
+        );
+        Tensor2 {
+            storage: grad_input,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::convolve_padded`] with respect to its kernel, where
+    /// `self` is the forward input.
+    pub fn conv2_backward_kernel_padded<
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        &self,
+        grad_output: &Tensor2<T, { PADDING.out_h(H, KH, SH, DILATION) }, { PADDING.out_w(W, KW, SW, DILATION) }, B>,
+    ) -> Tensor2<T, KH, KW, B>
+    where
+        B: Conv2<T>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>,
+    {
+        let mut grad_kernel = <B as HasStorage<T, { KH * KW }>>::storage_uninit();
+        B::conv2_backward_kernel::<H, W, KH, KW, SH, SW, DILATION, PADDING>(
+            &self.storage,
+            &grad_output.storage,
+            &mut grad_kernel,
+        );
+        Tensor2 {
+            storage: grad_kernel,
+            _p: core::marker::PhantomData,
+        }
+    }
 }
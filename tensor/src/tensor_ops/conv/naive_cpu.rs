@@ -2,7 +2,10 @@
 
 use crate::storage::HasStorage;
 use crate::storage::naive_cpu::NaiveCpu;
-use crate::tensor_ops::conv::Conv2;
+use crate::tensor_ops::conv::{Conv2, Padding};
+use crate::tensor_ops::conv::kernel::{
+    Conv2Geom, conv2_backward_rows, conv2_kernel_grad_rows, conv2_rows,
+};
 use core::ops::{Add, Mul};
 
 impl<T> Conv2<T> for NaiveCpu
@@ -14,40 +17,26 @@ where
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         input: &<Self as HasStorage<T, { H * W }>>::Storage,
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
         output:
-            &mut <Self as HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>>::Storage,
+            &mut <Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::Storage,
     ) where
         Self: HasStorage<T, { H * W }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>,
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>,
     {
         let inp = <Self as HasStorage<T, { H * W }>>::as_slice(input);
         let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
-        let out = <Self as HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>>::as_mut_slice(output);
-
-        let out_h = (H + 2 * PAD - KH) / STRIDE + 1;
-        let out_w = (W + 2 * PAD - KW) / STRIDE + 1;
-
-        for i in 0..out_h {
-            for j in 0..out_w {
-                let mut acc = T::default();
-                for ki in 0..KH {
-                    for kj in 0..KW {
-                        let hi = i * STRIDE + ki;
-                        let wj = j * STRIDE + kj;
-                        if hi >= PAD && hi < H + PAD && wj >= PAD && wj < W + PAD {
-                            acc = acc + inp[(hi - PAD) * W + (wj - PAD)] * ker[ki * KW + kj];
-                        }
-                    }
-                }
-                out[i * out_w + j] = acc;
-            }
-        }
+        let out = <Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::as_mut_slice(output);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        conv2_rows(&g, inp, ker, 0, out);
     }
 
     fn conv2_backward<
@@ -55,43 +44,164 @@ where
         const W: usize,
         const KH: usize,
         const KW: usize,
-        const STRIDE: usize,
-        const PAD: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
     >(
         kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
-        grad_output: &<Self as HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::Storage,
         grad_input: &mut <Self as HasStorage<T, { H * W }>>::Storage,
     ) where
         Self: HasStorage<T, { H * W }>
             + HasStorage<T, { KH * KW }>
-            + HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>,
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>,
     {
         let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
-        let grad_out = <Self as HasStorage<T, { ((H + 2 * PAD - KH) / STRIDE + 1) * ((W + 2 * PAD - KW) / STRIDE + 1) }>>::as_slice(grad_output);
+        let grad_out = <Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::as_slice(grad_output);
         let grad_in = <Self as HasStorage<T, { H * W }>>::as_mut_slice(grad_input);
 
-        // zero initialize grad_in
-        for v in grad_in.iter_mut() {
-            *v = T::default();
-        }
-
-        let out_h = (H + 2 * PAD - KH) / STRIDE + 1;
-        let out_w = (W + 2 * PAD - KW) / STRIDE + 1;
-
-        for i in 0..out_h {
-            for j in 0..out_w {
-                let go = grad_out[i * out_w + j];
-                for ki in 0..KH {
-                    for kj in 0..KW {
-                        let hi = i * STRIDE + ki;
-                        let wj = j * STRIDE + kj;
-                        if hi >= PAD && hi < H + PAD && wj >= PAD && wj < W + PAD {
-                            grad_in[(hi - PAD) * W + (wj - PAD)] =
-                                grad_in[(hi - PAD) * W + (wj - PAD)] + ker[ki * KW + kj] * go;
-                        }
-                    }
-                }
-            }
-        }
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        conv2_backward_rows(&g, ker, grad_out, 0, grad_in);
+    }
+
+    fn conv2_backward_kernel<
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { H * W }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::Storage,
+        grad_kernel: &mut <Self as HasStorage<T, { KH * KW }>>::Storage,
+    ) where
+        Self: HasStorage<T, { H * W }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>,
+    {
+        let inp = <Self as HasStorage<T, { H * W }>>::as_slice(input);
+        let grad_out = <Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::as_slice(grad_output);
+        let grad_ker = <Self as HasStorage<T, { KH * KW }>>::as_mut_slice(grad_kernel);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        conv2_kernel_grad_rows(&g, inp, grad_out, 1, 0, grad_ker);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::tensor::Tensor2;
+    use crate::tensor_ops::conv::Padding;
+
+    #[test]
+    fn test_conv2_forward() {
+        let x = Tensor2::<i32, 3, 3, NaiveCpu>::new([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let k = Tensor2::<i32, 2, 2, NaiveCpu>::new([1, 0, 0, -1]);
+        assert_eq!(x.convolve::<2, 2, 1, 0>(&k).as_slice(), &[-4, -4, -4, -4]);
+        // Padding 1 and stride 2 read the zero border.
+        assert_eq!(x.convolve::<2, 2, 2, 1>(&k).as_slice(), &[-1, -3, -7, -4]);
+    }
+
+    #[test]
+    fn test_conv2_backward_is_adjoint() {
+        // <conv(x), g> == <x, conv_backward(g)> for every x and g.
+        let x = Tensor2::<i32, 5, 4, NaiveCpu>::new(core::array::from_fn(|i| i as i32 % 7 - 3));
+        let k = Tensor2::<i32, 3, 2, NaiveCpu>::new([2, -1, 0, 3, 1, -2]);
+        let y = x.convolve::<3, 2, 2, 1>(&k);
+        let g = Tensor2::<i32, 3, 3, NaiveCpu>::new([1, -2, 3, 0, 4, -1, 2, 2, -3]);
+        let dx = Tensor2::<i32, 5, 4, NaiveCpu>::conv2_backward::<3, 2, 2, 1>(&g, &k);
+
+        let lhs: i32 = y.as_slice().iter().zip(g.as_slice()).map(|(a, b)| a * b).sum();
+        let rhs: i32 = x.as_slice().iter().zip(dx.as_slice()).map(|(a, b)| a * b).sum();
+        assert_eq!(lhs, rhs);
+    }
+
+    #[test]
+    fn test_conv2_kernel_gradient_is_adjoint() {
+        // conv(x, k) is linear in k, so <conv(x, k), g> == <k, grad_kernel(x, g)>.
+        let x = Tensor2::<i32, 5, 4, NaiveCpu>::new(core::array::from_fn(|i| i as i32 % 5 - 2));
+        let k = Tensor2::<i32, 3, 2, NaiveCpu>::new([1, -2, 0, 3, 2, -1]);
+        let y = x.convolve::<3, 2, 2, 1>(&k);
+        let g = Tensor2::<i32, 3, 3, NaiveCpu>::new([2, -1, 0, 1, 3, -2, 0, 1, 1]);
+        let dk = x.conv2_backward_kernel::<3, 2, 2, 1>(&g);
+
+        let lhs: i32 = y.as_slice().iter().zip(g.as_slice()).map(|(a, b)| a * b).sum();
+        let rhs: i32 = k.as_slice().iter().zip(dk.as_slice()).map(|(a, b)| a * b).sum();
+        assert_eq!(lhs, rhs);
+    }
+
+    #[test]
+    fn test_dilated_conv2() {
+        // With dilation 2 the 2x2 kernel taps x[i, j] and x[i + 2, j + 2].
+        let x = Tensor2::<i32, 5, 5, NaiveCpu>::new(core::array::from_fn(|i| i as i32 + 1));
+        let k = Tensor2::<i32, 2, 2, NaiveCpu>::new([1, 0, 0, -1]);
+        let y = x.convolve_padded::<2, 2, 1, 1, 2, { Padding::symmetric(0) }>(&k);
+        assert_eq!(y.as_slice(), &[-12; 9]);
+
+        // Both gradients stay adjoint with dilation, stride and padding combined.
+        let x = Tensor2::<i32, 6, 5, NaiveCpu>::new(core::array::from_fn(|i| i as i32 % 7 - 3));
+        let k = Tensor2::<i32, 3, 2, NaiveCpu>::new([2, -1, 0, 3, 1, -2]);
+        let y = x.convolve_padded::<3, 2, 2, 2, 2, { Padding::symmetric(1) }>(&k);
+        let g = Tensor2::<i32, 2, 3, NaiveCpu>::new([1, -2, 3, 0, 4, -1]);
+        let dx = Tensor2::<i32, 6, 5, NaiveCpu>::conv2_backward_padded::<
+            3,
+            2,
+            2,
+            2,
+            2,
+            { Padding::symmetric(1) },
+        >(&g, &k);
+        let dk = x.conv2_backward_kernel_padded::<3, 2, 2, 2, 2, { Padding::symmetric(1) }>(&g);
+
+        let dot = |a: &[i32], b: &[i32]| -> i32 { a.iter().zip(b).map(|(x, y)| x * y).sum() };
+        assert_eq!(dot(y.as_slice(), g.as_slice()), dot(x.as_slice(), dx.as_slice()));
+        assert_eq!(dot(y.as_slice(), g.as_slice()), dot(k.as_slice(), dk.as_slice()));
+    }
+
+    #[test]
+    fn test_conv2_padding_modes() {
+        let x = Tensor2::<i32, 3, 3, NaiveCpu>::new([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let k = Tensor2::<i32, 2, 2, NaiveCpu>::new([1, 0, 0, -1]);
+        // "Same" keeps the 3x3 shape, with the odd zero row and column after.
+        let same = x.convolve_padded::<2, 2, 1, 1, 1, { Padding::Same }>(&k);
+        assert_eq!(same.as_slice(), &[-4, -4, 3, -4, -4, 6, 7, 8, 9]);
+        let valid = x.convolve_padded::<2, 2, 1, 1, 1, { Padding::Valid }>(&k);
+        assert_eq!(valid.as_slice(), x.convolve::<2, 2, 1, 0>(&k).as_slice());
+
+        // With an odd kernel, "same" at stride 2 is one zero on every side.
+        let x = Tensor2::<i32, 5, 5, NaiveCpu>::new(core::array::from_fn(|i| i as i32 % 7 - 3));
+        let k = Tensor2::<i32, 3, 3, NaiveCpu>::new([1, -1, 2, 0, 3, -2, 1, 0, -1]);
+        let same = x.convolve_padded::<3, 3, 2, 2, 1, { Padding::Same }>(&k);
+        assert_eq!(same.as_slice(), x.convolve::<3, 3, 2, 1>(&k).as_slice());
+
+        // A 1x3 kernel only needs its columns padded.
+        let row = Tensor2::<i32, 1, 3, NaiveCpu>::new([1, 2, 1]);
+        let same: Tensor2<i32, 5, 5, NaiveCpu> =
+            x.convolve_padded::<1, 3, 1, 1, 1, { Padding::Same }>(&row);
+        const COLUMNS: Padding = Padding::Explicit { top: 0, bottom: 0, left: 1, right: 1 };
+        let explicit = x.convolve_padded::<1, 3, 1, 1, 1, COLUMNS>(&row);
+        assert_eq!(same.as_slice(), explicit.as_slice());
+    }
+
+    #[test]
+    fn test_conv2_padded_gradients_are_adjoint() {
+        // Stride 2 down the rows and 1 across, and lopsided padding on both axes.
+        const PAD: Padding = Padding::Explicit { top: 1, bottom: 0, left: 0, right: 2 };
+        let x = Tensor2::<i32, 6, 5, NaiveCpu>::new(core::array::from_fn(|i| i as i32 % 7 - 3));
+        let k = Tensor2::<i32, 3, 2, NaiveCpu>::new([2, -1, 0, 3, 1, -2]);
+        let y: Tensor2<i32, 3, 6, NaiveCpu> = x.convolve_padded::<3, 2, 2, 1, 1, PAD>(&k);
+        let g = Tensor2::<i32, 3, 6, NaiveCpu>::new(core::array::from_fn(|i| i as i32 % 5 - 2));
+        let dx =
+            Tensor2::<i32, 6, 5, NaiveCpu>::conv2_backward_padded::<3, 2, 2, 1, 1, PAD>(&g, &k);
+        let dk = x.conv2_backward_kernel_padded::<3, 2, 2, 1, 1, PAD>(&g);
+
+        let dot = |a: &[i32], b: &[i32]| -> i32 { a.iter().zip(b).map(|(x, y)| x * y).sum() };
+        assert_eq!(dot(y.as_slice(), g.as_slice()), dot(x.as_slice(), dx.as_slice()));
+        assert_eq!(dot(y.as_slice(), g.as_slice()), dot(k.as_slice(), dk.as_slice()));
     }
 }
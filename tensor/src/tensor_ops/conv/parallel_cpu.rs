@@ -0,0 +1,181 @@
+use crate::storage::HasStorage;
+use crate::storage::parallel_cpu::{ParallelCpu, for_each_chunk};
+use crate::tensor_ops::conv::{Conv2, Padding};
+use crate::tensor_ops::conv::kernel::{
+    Conv2Geom, conv2_backward_rows, conv2_kernel_grad_rows, conv2_rows,
+};
+use core::ops::{Add, Mul};
+
+/// Fewest image rows worth giving a thread of their own.
+const MIN_ROWS_PER_THREAD: usize = 4;
+
+impl<T> Conv2<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    fn conv2<
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { H * W }>>::Storage,
+        kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
+        output:
+            &mut <Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { H * W }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>,
+    {
+        let inp = <Self as HasStorage<T, { H * W }>>::as_slice(input);
+        let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
+        let out = <Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::as_mut_slice(output);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        for_each_chunk(out, g.out_w(), MIN_ROWS_PER_THREAD, |first, band| {
+            conv2_rows(&g, inp, ker, first, band);
+        });
+    }
+
+    fn conv2_backward<
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        kernel: &<Self as HasStorage<T, { KH * KW }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::Storage,
+        grad_input: &mut <Self as HasStorage<T, { H * W }>>::Storage,
+    ) where
+        Self: HasStorage<T, { H * W }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>,
+    {
+        let ker = <Self as HasStorage<T, { KH * KW }>>::as_slice(kernel);
+        let grad_out = <Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::as_slice(grad_output);
+        let grad_in = <Self as HasStorage<T, { H * W }>>::as_mut_slice(grad_input);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        for_each_chunk(grad_in, W, MIN_ROWS_PER_THREAD, |first, band| {
+            conv2_backward_rows(&g, ker, grad_out, first, band);
+        });
+    }
+
+    fn conv2_backward_kernel<
+        const H: usize,
+        const W: usize,
+        const KH: usize,
+        const KW: usize,
+        const SH: usize,
+        const SW: usize,
+        const DILATION: usize,
+        const PADDING: Padding,
+    >(
+        input: &<Self as HasStorage<T, { H * W }>>::Storage,
+        grad_output: &<Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::Storage,
+        grad_kernel: &mut <Self as HasStorage<T, { KH * KW }>>::Storage,
+    ) where
+        Self: HasStorage<T, { H * W }>
+            + HasStorage<T, { KH * KW }>
+            + HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>,
+    {
+        let inp = <Self as HasStorage<T, { H * W }>>::as_slice(input);
+        let grad_out = <Self as HasStorage<T, { PADDING.out_h(H, KH, SH, DILATION) * PADDING.out_w(W, KW, SW, DILATION) }>>::as_slice(grad_output);
+        let grad_ker = <Self as HasStorage<T, { KH * KW }>>::as_mut_slice(grad_kernel);
+
+        let g = Conv2Geom::new([H, W], [KH, KW], [SH, SW], DILATION, PADDING);
+        for_each_chunk(grad_ker, KW, 1, |first, rows| {
+            conv2_kernel_grad_rows(&g, inp, grad_out, 1, first, rows);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::storage::parallel_cpu::ParallelCpu;
+    use crate::tensor::{Tensor2, Tensor3};
+    use crate::tensor_ops::conv::Padding;
+
+    #[test]
+    fn test_matches_naive_cpu() {
+        ParallelCpu::with_num_threads(3, || {
+            let x: [f32; 24 * 10] = core::array::from_fn(|i| (i % 11) as f32 * 0.5 - 2.0);
+            let k: [f32; 9] = [0.5, -1.0, 0.25, 2.0, 1.0, -0.5, 0.0, 1.5, -2.0];
+
+            let want = Tensor2::<f32, 24, 10, NaiveCpu>::new(x)
+                .convolve::<3, 3, 1, 1>(&Tensor2::<f32, 3, 3, NaiveCpu>::new(k));
+            let got = Tensor2::<f32, 24, 10, ParallelCpu>::new(x)
+                .convolve::<3, 3, 1, 1>(&Tensor2::<f32, 3, 3, ParallelCpu>::new(k));
+            assert_eq!(got.as_slice(), want.as_slice());
+
+            let g: [f32; 12 * 5] = core::array::from_fn(|i| (i % 5) as f32 - 2.0);
+            let want = Tensor2::<f32, 24, 10, NaiveCpu>::conv2_backward::<3, 3, 2, 1>(
+                &Tensor2::<f32, 12, 5, NaiveCpu>::new(g),
+                &Tensor2::<f32, 3, 3, NaiveCpu>::new(k),
+            );
+            let got = Tensor2::<f32, 24, 10, ParallelCpu>::conv2_backward::<3, 3, 2, 1>(
+                &Tensor2::<f32, 12, 5, ParallelCpu>::new(g),
+                &Tensor2::<f32, 3, 3, ParallelCpu>::new(k),
+            );
+            assert_eq!(got.as_slice(), want.as_slice());
+
+            let want = Tensor3::<f32, 4, 6, 10, NaiveCpu>::new(x)
+                .convolve::<3, 3, 1, 0>(&Tensor2::<f32, 3, 3, NaiveCpu>::new(k));
+            let got = Tensor3::<f32, 4, 6, 10, ParallelCpu>::new(x)
+                .convolve::<3, 3, 1, 0>(&Tensor2::<f32, 3, 3, ParallelCpu>::new(k));
+            assert_eq!(got.as_slice(), want.as_slice());
+
+            let want = Tensor2::<f32, 24, 10, NaiveCpu>::new(x)
+                .conv2_backward_kernel::<3, 3, 2, 1>(&Tensor2::<f32, 12, 5, NaiveCpu>::new(g));
+            let got = Tensor2::<f32, 24, 10, ParallelCpu>::new(x)
+                .conv2_backward_kernel::<3, 3, 2, 1>(&Tensor2::<f32, 12, 5, ParallelCpu>::new(g));
+            assert_eq!(got.as_slice(), want.as_slice());
+        });
+    }
+
+    #[test]
+    fn test_padded_matches_naive_cpu() {
+        ParallelCpu::with_num_threads(3, || {
+            const PAD: Padding = Padding::Same;
+            let x: [f32; 24 * 10] = core::array::from_fn(|i| (i % 11) as f32 * 0.5 - 2.0);
+            let k: [f32; 8] = [0.5, -1.0, 0.25, 2.0, 1.0, -0.5, 1.5, -2.0];
+            let g: [f32; 12 * 10] = core::array::from_fn(|i| (i % 5) as f32 - 2.0);
+
+            let want = Tensor2::<f32, 24, 10, NaiveCpu>::new(x)
+                .convolve_padded::<4, 2, 2, 1, 1, PAD>(&Tensor2::<f32, 4, 2, NaiveCpu>::new(k));
+            let got = Tensor2::<f32, 24, 10, ParallelCpu>::new(x)
+                .convolve_padded::<4, 2, 2, 1, 1, PAD>(&Tensor2::<f32, 4, 2, ParallelCpu>::new(k));
+            assert_eq!(got.as_slice(), want.as_slice());
+
+            let want = Tensor2::<f32, 24, 10, NaiveCpu>::conv2_backward_padded::<4, 2, 2, 1, 1, PAD>(
+                &Tensor2::<f32, 12, 10, NaiveCpu>::new(g),
+                &Tensor2::<f32, 4, 2, NaiveCpu>::new(k),
+            );
+            let got = Tensor2::<f32, 24, 10, ParallelCpu>::conv2_backward_padded::<4, 2, 2, 1, 1, PAD>(
+                &Tensor2::<f32, 12, 10, ParallelCpu>::new(g),
+                &Tensor2::<f32, 4, 2, ParallelCpu>::new(k),
+            );
+            assert_eq!(got.as_slice(), want.as_slice());
+
+            let want = Tensor2::<f32, 24, 10, NaiveCpu>::new(x)
+                .conv2_backward_kernel_padded::<4, 2, 2, 1, 1, PAD>(
+                    &Tensor2::<f32, 12, 10, NaiveCpu>::new(g),
+                );
+            let got = Tensor2::<f32, 24, 10, ParallelCpu>::new(x)
+                .conv2_backward_kernel_padded::<4, 2, 2, 1, 1, PAD>(
+                    &Tensor2::<f32, 12, 10, ParallelCpu>::new(g),
+                );
+            assert_eq!(got.as_slice(), want.as_slice());
+        });
+    }
+}
@@ -0,0 +1,781 @@
+//! Multi-channel NCHW convolution: `input [N, C_in, H, W]` against a filter
+//! bank `weight [C_out, C_in / GROUPS, KH, KW]`, plus an optional
+//! `bias [C_out]`, gives `[N, C_out, OH, OW]`.
+//!
+//! The `conv2d_grouped*` variants add `DILATION` and `GROUPS`: with
+//! `GROUPS > 1` the channels are split into `GROUPS` independent groups and
+//! each output channel only sees the input channels of its own group;
+//! `GROUPS = C_in = C_out` is a depthwise convolution, which has its own
+//! `depthwise_conv2d*` shorthands.
+//!
+//! Output sizes are computed at compile time from the const generics; the
+//! backend trait takes the same geometry at runtime as a [`Conv2dShape`].
+//! Backend implementers should implement [`Conv2d`].
+
+pub mod naive_cpu;
+pub mod parallel_cpu;
+
+use crate::storage::HasStorage;
+use crate::tensor::{Tensor1, Tensor4};
+use crate::tensor_ops::conv::dilated_out_len;
+use core::ops::{Add, Mul};
+
+/// Output length of an undilated convolution over `len` input positions.
+pub const fn conv_out_len(len: usize, kernel: usize, stride: usize, pad: usize) -> usize {
+    dilated_out_len(len, kernel, stride, pad, 1)
+}
+
+/// Checks that `groups` divides both channel counts and returns `0`.
+///
+/// Used as a `[(); channel_groups(CIN, COUT, GROUPS)]:` bound so a grouping
+/// that doesn't split the channels evenly fails compilation.
+pub const fn channel_groups(c_in: usize, c_out: usize, groups: usize) -> usize {
+    assert!(
+        groups > 0 && c_in % groups == 0 && c_out % groups == 0,
+        "GROUPS must divide both the input and output channel counts"
+    );
+    0
+}
+
+/// Geometry of an NCHW convolution with square stride and dilation and
+/// symmetric zero padding. The weight is `[c_out, c_in / groups, kh, kw]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conv2dShape {
+    pub batch: usize,
+    pub c_in: usize,
+    pub c_out: usize,
+    pub h: usize,
+    pub w: usize,
+    pub kh: usize,
+    pub kw: usize,
+    pub stride: usize,
+    pub pad: usize,
+    pub dilation: usize,
+    pub groups: usize,
+}
+
+impl Conv2dShape {
+    #[inline]
+    pub fn out_h(&self) -> usize {
+        dilated_out_len(self.h, self.kh, self.stride, self.pad, self.dilation)
+    }
+
+    #[inline]
+    pub fn out_w(&self) -> usize {
+        dilated_out_len(self.w, self.kw, self.stride, self.pad, self.dilation)
+    }
+}
+
+/// Backend trait for NCHW convolution. `NI`, `NK`, `NB` and `NO` are the
+/// storage lengths of the input, weight, bias and output.
+pub trait Conv2d<T: Copy + Default + Add<Output = T> + Mul<Output = T>>: Sized {
+    /// `out[n, co] = bias[co] + sum_ci input[n, ci] ⋆ weight[co, ci]`, with
+    /// `ci` running over the input channels of `co`'s group and the bias
+    /// taken as zero when absent.
+    fn conv2d<const NI: usize, const NK: usize, const NB: usize, const NO: usize>(
+        shape: &Conv2dShape,
+        input: &<Self as HasStorage<T, NI>>::Storage,
+        weight: &<Self as HasStorage<T, NK>>::Storage,
+        bias: Option<&<Self as HasStorage<T, NB>>::Storage>,
+        output: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NI> + HasStorage<T, NK> + HasStorage<T, NB> + HasStorage<T, NO>;
+
+    /// Gradient of [`Self::conv2d`] with respect to its input.
+    fn conv2d_backward<const NI: usize, const NK: usize, const NO: usize>(
+        shape: &Conv2dShape,
+        weight: &<Self as HasStorage<T, NK>>::Storage,
+        grad_output: &<Self as HasStorage<T, NO>>::Storage,
+        grad_input: &mut <Self as HasStorage<T, NI>>::Storage,
+    ) where
+        Self: HasStorage<T, NI> + HasStorage<T, NK> + HasStorage<T, NO>;
+
+    /// Gradient of [`Self::conv2d`] with respect to its weight, summed over
+    /// the batch.
+    fn conv2d_backward_weight<const NI: usize, const NK: usize, const NO: usize>(
+        shape: &Conv2dShape,
+        input: &<Self as HasStorage<T, NI>>::Storage,
+        grad_output: &<Self as HasStorage<T, NO>>::Storage,
+        grad_weight: &mut <Self as HasStorage<T, NK>>::Storage,
+    ) where
+        Self: HasStorage<T, NI> + HasStorage<T, NK> + HasStorage<T, NO>;
+
+    /// Gradient of [`Self::conv2d`] with respect to its bias: `grad_output`
+    /// (`batch x channels x plane`) summed over everything but the channel.
+    fn conv2d_backward_bias<const NO: usize, const NB: usize>(
+        batch: usize,
+        channels: usize,
+        plane: usize,
+        grad_output: &<Self as HasStorage<T, NO>>::Storage,
+        grad_bias: &mut <Self as HasStorage<T, NB>>::Storage,
+    ) where
+        Self: HasStorage<T, NO> + HasStorage<T, NB>;
+}
+
+impl<T, const N: usize, const CIN: usize, const H: usize, const W: usize, B>
+    Tensor4<T, N, CIN, H, W, B>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    B: Conv2d<T> + HasStorage<T, { N * (CIN * (H * W)) }>,
+{
+    /// Convolves the `[N, CIN, H, W]` batch with a `[COUT, CIN, KH, KW]`
+    /// filter bank. Grouped and dilated convolutions go through
+    /// [`Self::conv2d_grouped`].
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    pub fn conv2d<
+        const COUT: usize,
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+    >(
+        &self,
+        weight: &Tensor4<T, COUT, CIN, KH, KW, B>,
+    ) -> Tensor4<
+        T,
+        N,
+        COUT,
+        { conv_out_len(H, KH, STRIDE, PAD) },
+        { conv_out_len(W, KW, STRIDE, PAD) },
+        B,
+    >
+    where
+        B: HasStorage<T, { COUT * (CIN * (KH * KW)) }>
+            + HasStorage<T, COUT>
+            + HasStorage<
+                T,
+                {
+                    N * (COUT
+                        * (conv_out_len(H, KH, STRIDE, PAD) * conv_out_len(W, KW, STRIDE, PAD)))
+                },
+            >,
+    {
+        self.conv2d_impl::<COUT, KH, KW, STRIDE, PAD>(weight, None)
+    }
+
+    /// [`Self::conv2d`] plus a per-output-channel `bias`.
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    pub fn conv2d_bias<
+        const COUT: usize,
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+    >(
+        &self,
+        weight: &Tensor4<T, COUT, CIN, KH, KW, B>,
+        bias: &Tensor1<T, COUT, B>,
+    ) -> Tensor4<
+        T,
+        N,
+        COUT,
+        { conv_out_len(H, KH, STRIDE, PAD) },
+        { conv_out_len(W, KW, STRIDE, PAD) },
+        B,
+    >
+    where
+        B: HasStorage<T, { COUT * (CIN * (KH * KW)) }>
+            + HasStorage<T, COUT>
+            + HasStorage<
+                T,
+                {
+                    N * (COUT
+                        * (conv_out_len(H, KH, STRIDE, PAD) * conv_out_len(W, KW, STRIDE, PAD)))
+                },
+            >,
+    {
+        self.conv2d_impl::<COUT, KH, KW, STRIDE, PAD>(weight, Some(bias))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn conv2d_impl<
+        const COUT: usize,
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+    >(
+        &self,
+        weight: &Tensor4<T, COUT, CIN, KH, KW, B>,
+        bias: Option<&Tensor1<T, COUT, B>>,
+    ) -> Tensor4<
+        T,
+        N,
+        COUT,
+        { conv_out_len(H, KH, STRIDE, PAD) },
+        { conv_out_len(W, KW, STRIDE, PAD) },
+        B,
+    >
+    where
+        B: HasStorage<T, { COUT * (CIN * (KH * KW)) }>
+            + HasStorage<T, COUT>
+            + HasStorage<
+                T,
+                {
+                    N * (COUT
+                        * (conv_out_len(H, KH, STRIDE, PAD) * conv_out_len(W, KW, STRIDE, PAD)))
+                },
+            >,
+    {
+        let shape = Self::shape(COUT, [KH, KW], [STRIDE, PAD, 1], 1);
+        let mut out = <B as HasStorage<
+            T,
+            {
+                N * (COUT * (conv_out_len(H, KH, STRIDE, PAD) * conv_out_len(W, KW, STRIDE, PAD)))
+            },
+        >>::storage_uninit();
+        B::conv2d::<
+            { N * (CIN * (H * W)) },
+            { COUT * (CIN * (KH * KW)) },
+            COUT,
+            {
+                N * (COUT * (conv_out_len(H, KH, STRIDE, PAD) * conv_out_len(W, KW, STRIDE, PAD)))
+            },
+        >(&shape, &self.storage, &weight.storage, bias.map(|b| &b.storage), &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::conv2d`] (or [`Self::conv2d_bias`]) with respect
+    /// to its input, given the gradient of its output.
+    #[inline]
+    pub fn conv2d_backward<
+        const COUT: usize,
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+    >(
+        grad_output: &Tensor4<
+            T,
+            N,
+            COUT,
+            { conv_out_len(H, KH, STRIDE, PAD) },
+            { conv_out_len(W, KW, STRIDE, PAD) },
+            B,
+        >,
+        weight: &Tensor4<T, COUT, CIN, KH, KW, B>,
+    ) -> Self
+    where
+        B: HasStorage<T, { COUT * (CIN * (KH * KW)) }>
+            + HasStorage<
+                T,
+                {
+                    N * (COUT
+                        * (conv_out_len(H, KH, STRIDE, PAD) * conv_out_len(W, KW, STRIDE, PAD)))
+                },
+            >,
+    {
+        let shape = Self::shape(COUT, [KH, KW], [STRIDE, PAD, 1], 1);
+        let mut grad_input = <B as HasStorage<T, { N * (CIN * (H * W)) }>>::storage_uninit();
+        B::conv2d_backward::<
+            { N * (CIN * (H * W)) },
+            { COUT * (CIN * (KH * KW)) },
+            {
+                N * (COUT * (conv_out_len(H, KH, STRIDE, PAD) * conv_out_len(W, KW, STRIDE, PAD)))
+            },
+        >(&shape, &weight.storage, &grad_output.storage, &mut grad_input);
+        Tensor4 {
+            storage: grad_input,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::conv2d`] (or [`Self::conv2d_bias`]) with respect
+    /// to its weight, where `self` is the forward input. Summed over the
+    /// batch.
+    #[inline]
+    pub fn conv2d_backward_weight<
+        const COUT: usize,
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+    >(
+        &self,
+        grad_output: &Tensor4<
+            T,
+            N,
+            COUT,
+            { conv_out_len(H, KH, STRIDE, PAD) },
+            { conv_out_len(W, KW, STRIDE, PAD) },
+            B,
+        >,
+    ) -> Tensor4<T, COUT, CIN, KH, KW, B>
+    where
+        B: HasStorage<T, { COUT * (CIN * (KH * KW)) }>
+            + HasStorage<
+                T,
+                {
+                    N * (COUT
+                        * (conv_out_len(H, KH, STRIDE, PAD) * conv_out_len(W, KW, STRIDE, PAD)))
+                },
+            >,
+    {
+        let shape = Self::shape(COUT, [KH, KW], [STRIDE, PAD, 1], 1);
+        let mut grad_weight =
+            <B as HasStorage<T, { COUT * (CIN * (KH * KW)) }>>::storage_uninit();
+        B::conv2d_backward_weight::<
+            { N * (CIN * (H * W)) },
+            { COUT * (CIN * (KH * KW)) },
+            {
+                N * (COUT * (conv_out_len(H, KH, STRIDE, PAD) * conv_out_len(W, KW, STRIDE, PAD)))
+            },
+        >(&shape, &self.storage, &grad_output.storage, &mut grad_weight);
+        Tensor4 {
+            storage: grad_weight,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// [`Self::conv2d`] with a `[COUT, CIN / GROUPS, KH, KW]` filter bank.
+    /// `DILATION` spaces the kernel taps apart (`1` for a dense kernel) and
+    /// `GROUPS` splits the channels (`1` for a full convolution).
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    pub fn conv2d_grouped<
+        const COUT: usize,
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+        const DILATION: usize,
+        const GROUPS: usize,
+    >(
+        &self,
+        weight: &Tensor4<T, COUT, { CIN / GROUPS }, KH, KW, B>,
+    ) -> Tensor4<
+        T,
+        N,
+        COUT,
+        { dilated_out_len(H, KH, STRIDE, PAD, DILATION) },
+        { dilated_out_len(W, KW, STRIDE, PAD, DILATION) },
+        B,
+    >
+    where
+        [(); channel_groups(CIN, COUT, GROUPS)]:,
+        B: HasStorage<T, { COUT * ((CIN / GROUPS) * (KH * KW)) }>
+            + HasStorage<T, COUT>
+            + HasStorage<
+                T,
+                {
+                    N * (COUT
+                        * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                            * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+                },
+            >,
+    {
+        self.conv2d_grouped_impl::<COUT, KH, KW, STRIDE, PAD, DILATION, GROUPS>(weight, None)
+    }
+
+    /// [`Self::conv2d_grouped`] plus a per-output-channel `bias`.
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    pub fn conv2d_grouped_bias<
+        const COUT: usize,
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+        const DILATION: usize,
+        const GROUPS: usize,
+    >(
+        &self,
+        weight: &Tensor4<T, COUT, { CIN / GROUPS }, KH, KW, B>,
+        bias: &Tensor1<T, COUT, B>,
+    ) -> Tensor4<
+        T,
+        N,
+        COUT,
+        { dilated_out_len(H, KH, STRIDE, PAD, DILATION) },
+        { dilated_out_len(W, KW, STRIDE, PAD, DILATION) },
+        B,
+    >
+    where
+        [(); channel_groups(CIN, COUT, GROUPS)]:,
+        B: HasStorage<T, { COUT * ((CIN / GROUPS) * (KH * KW)) }>
+            + HasStorage<T, COUT>
+            + HasStorage<
+                T,
+                {
+                    N * (COUT
+                        * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                            * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+                },
+            >,
+    {
+        self.conv2d_grouped_impl::<COUT, KH, KW, STRIDE, PAD, DILATION, GROUPS>(weight, Some(bias))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn conv2d_grouped_impl<
+        const COUT: usize,
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+        const DILATION: usize,
+        const GROUPS: usize,
+    >(
+        &self,
+        weight: &Tensor4<T, COUT, { CIN / GROUPS }, KH, KW, B>,
+        bias: Option<&Tensor1<T, COUT, B>>,
+    ) -> Tensor4<
+        T,
+        N,
+        COUT,
+        { dilated_out_len(H, KH, STRIDE, PAD, DILATION) },
+        { dilated_out_len(W, KW, STRIDE, PAD, DILATION) },
+        B,
+    >
+    where
+        B: HasStorage<T, { COUT * ((CIN / GROUPS) * (KH * KW)) }>
+            + HasStorage<T, COUT>
+            + HasStorage<
+                T,
+                {
+                    N * (COUT
+                        * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                            * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+                },
+            >,
+    {
+        let shape = Self::shape(COUT, [KH, KW], [STRIDE, PAD, DILATION], GROUPS);
+        let mut out = <B as HasStorage<
+            T,
+            {
+                N * (COUT
+                    * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                        * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+            },
+        >>::storage_uninit();
+        B::conv2d::<
+            { N * (CIN * (H * W)) },
+            { COUT * ((CIN / GROUPS) * (KH * KW)) },
+            COUT,
+            {
+                N * (COUT
+                    * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                        * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+            },
+        >(&shape, &self.storage, &weight.storage, bias.map(|b| &b.storage), &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::conv2d_grouped`] (or [`Self::conv2d_grouped_bias`])
+    /// with respect to its input, given the gradient of its output.
+    #[inline]
+    pub fn conv2d_grouped_backward<
+        const COUT: usize,
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+        const DILATION: usize,
+        const GROUPS: usize,
+    >(
+        grad_output: &Tensor4<
+            T,
+            N,
+            COUT,
+            { dilated_out_len(H, KH, STRIDE, PAD, DILATION) },
+            { dilated_out_len(W, KW, STRIDE, PAD, DILATION) },
+            B,
+        >,
+        weight: &Tensor4<T, COUT, { CIN / GROUPS }, KH, KW, B>,
+    ) -> Self
+    where
+        [(); channel_groups(CIN, COUT, GROUPS)]:,
+        B: HasStorage<T, { COUT * ((CIN / GROUPS) * (KH * KW)) }>
+            + HasStorage<
+                T,
+                {
+                    N * (COUT
+                        * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                            * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+                },
+            >,
+    {
+        let shape = Self::shape(COUT, [KH, KW], [STRIDE, PAD, DILATION], GROUPS);
+        let mut grad_input = <B as HasStorage<T, { N * (CIN * (H * W)) }>>::storage_uninit();
+        B::conv2d_backward::<
+            { N * (CIN * (H * W)) },
+            { COUT * ((CIN / GROUPS) * (KH * KW)) },
+            {
+                N * (COUT
+                    * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                        * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+            },
+        >(&shape, &weight.storage, &grad_output.storage, &mut grad_input);
+        Tensor4 {
+            storage: grad_input,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::conv2d_grouped`] (or [`Self::conv2d_grouped_bias`])
+    /// with respect to its weight, where `self` is the forward input. Summed
+    /// over the batch.
+    #[inline]
+    pub fn conv2d_grouped_backward_weight<
+        const COUT: usize,
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+        const DILATION: usize,
+        const GROUPS: usize,
+    >(
+        &self,
+        grad_output: &Tensor4<
+            T,
+            N,
+            COUT,
+            { dilated_out_len(H, KH, STRIDE, PAD, DILATION) },
+            { dilated_out_len(W, KW, STRIDE, PAD, DILATION) },
+            B,
+        >,
+    ) -> Tensor4<T, COUT, { CIN / GROUPS }, KH, KW, B>
+    where
+        [(); channel_groups(CIN, COUT, GROUPS)]:,
+        B: HasStorage<T, { COUT * ((CIN / GROUPS) * (KH * KW)) }>
+            + HasStorage<
+                T,
+                {
+                    N * (COUT
+                        * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                            * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+                },
+            >,
+    {
+        let shape = Self::shape(COUT, [KH, KW], [STRIDE, PAD, DILATION], GROUPS);
+        let mut grad_weight =
+            <B as HasStorage<T, { COUT * ((CIN / GROUPS) * (KH * KW)) }>>::storage_uninit();
+        B::conv2d_backward_weight::<
+            { N * (CIN * (H * W)) },
+            { COUT * ((CIN / GROUPS) * (KH * KW)) },
+            {
+                N * (COUT
+                    * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                        * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+            },
+        >(&shape, &self.storage, &grad_output.storage, &mut grad_weight);
+        Tensor4 {
+            storage: grad_weight,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Depthwise convolution: each of the `CIN` channels is convolved with
+    /// its own `[KH, KW]` filter from the `[CIN, 1, KH, KW]` bank, i.e.
+    /// [`Self::conv2d_grouped`] with `COUT = GROUPS = CIN`.
+    ///
+    /// The `1 *` in the weight's storage length spells out the
+    /// `[CIN, 1, KH, KW]` shape so it matches the tensor's own bound.
+    #[allow(clippy::type_complexity, clippy::identity_op)]
+    #[inline]
+    pub fn depthwise_conv2d<
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+        const DILATION: usize,
+    >(
+        &self,
+        weight: &Tensor4<T, CIN, 1, KH, KW, B>,
+    ) -> Tensor4<
+        T,
+        N,
+        CIN,
+        { dilated_out_len(H, KH, STRIDE, PAD, DILATION) },
+        { dilated_out_len(W, KW, STRIDE, PAD, DILATION) },
+        B,
+    >
+    where
+        B: HasStorage<T, { CIN * (1 * (KH * KW)) }>
+            + HasStorage<T, CIN>
+            + HasStorage<
+                T,
+                {
+                    N * (CIN
+                        * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                            * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+                },
+            >,
+    {
+        let shape = Self::shape(CIN, [KH, KW], [STRIDE, PAD, DILATION], CIN);
+        let mut out = <B as HasStorage<
+            T,
+            {
+                N * (CIN
+                    * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                        * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+            },
+        >>::storage_uninit();
+        B::conv2d::<
+            { N * (CIN * (H * W)) },
+            { CIN * (1 * (KH * KW)) },
+            CIN,
+            {
+                N * (CIN
+                    * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                        * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+            },
+        >(&shape, &self.storage, &weight.storage, None, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::depthwise_conv2d`] with respect to its input.
+    #[allow(clippy::identity_op)]
+    #[inline]
+    pub fn depthwise_conv2d_backward<
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+        const DILATION: usize,
+    >(
+        grad_output: &Tensor4<
+            T,
+            N,
+            CIN,
+            { dilated_out_len(H, KH, STRIDE, PAD, DILATION) },
+            { dilated_out_len(W, KW, STRIDE, PAD, DILATION) },
+            B,
+        >,
+        weight: &Tensor4<T, CIN, 1, KH, KW, B>,
+    ) -> Self
+    where
+        B: HasStorage<T, { CIN * (1 * (KH * KW)) }>
+            + HasStorage<
+                T,
+                {
+                    N * (CIN
+                        * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                            * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+                },
+            >,
+    {
+        let shape = Self::shape(CIN, [KH, KW], [STRIDE, PAD, DILATION], CIN);
+        let mut grad_input = <B as HasStorage<T, { N * (CIN * (H * W)) }>>::storage_uninit();
+        B::conv2d_backward::<
+            { N * (CIN * (H * W)) },
+            { CIN * (1 * (KH * KW)) },
+            {
+                N * (CIN
+                    * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                        * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+            },
+        >(&shape, &weight.storage, &grad_output.storage, &mut grad_input);
+        Tensor4 {
+            storage: grad_input,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Gradient of [`Self::depthwise_conv2d`] with respect to its weight,
+    /// where `self` is the forward input. Summed over the batch.
+    #[allow(clippy::identity_op)]
+    #[inline]
+    pub fn depthwise_conv2d_backward_weight<
+        const KH: usize,
+        const KW: usize,
+        const STRIDE: usize,
+        const PAD: usize,
+        const DILATION: usize,
+    >(
+        &self,
+        grad_output: &Tensor4<
+            T,
+            N,
+            CIN,
+            { dilated_out_len(H, KH, STRIDE, PAD, DILATION) },
+            { dilated_out_len(W, KW, STRIDE, PAD, DILATION) },
+            B,
+        >,
+    ) -> Tensor4<T, CIN, 1, KH, KW, B>
+    where
+        B: HasStorage<T, { CIN * (1 * (KH * KW)) }>
+            + HasStorage<
+                T,
+                {
+                    N * (CIN
+                        * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                            * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+                },
+            >,
+    {
+        let shape = Self::shape(CIN, [KH, KW], [STRIDE, PAD, DILATION], CIN);
+        let mut grad_weight = <B as HasStorage<T, { CIN * (1 * (KH * KW)) }>>::storage_uninit();
+        B::conv2d_backward_weight::<
+            { N * (CIN * (H * W)) },
+            { CIN * (1 * (KH * KW)) },
+            {
+                N * (CIN
+                    * (dilated_out_len(H, KH, STRIDE, PAD, DILATION)
+                        * dilated_out_len(W, KW, STRIDE, PAD, DILATION)))
+            },
+        >(&shape, &self.storage, &grad_output.storage, &mut grad_weight);
+        Tensor4 {
+            storage: grad_weight,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// The runtime geometry of convolving `self` with a `[c_out, CIN /
+    /// groups, kh, kw]` bank.
+    fn shape(
+        c_out: usize,
+        [kh, kw]: [usize; 2],
+        [stride, pad, dilation]: [usize; 3],
+        groups: usize,
+    ) -> Conv2dShape {
+        Conv2dShape {
+            batch: N,
+            c_in: CIN,
+            c_out,
+            h: H,
+            w: W,
+            kh,
+            kw,
+            stride,
+            pad,
+            dilation,
+            groups,
+        }
+    }
+}
+impl<T, const N: usize, const C: usize, const OH: usize, const OW: usize, B>
+    Tensor4<T, N, C, OH, OW, B>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    B: Conv2d<T> + HasStorage<T, { N * (C * (OH * OW)) }> + HasStorage<T, C>,
+{
+    /// Gradient of [`Self::conv2d_bias`] with respect to its bias, where
+    /// `self` is the gradient of its `[N, C, OH, OW]` output.
+    #[inline]
+    pub fn conv2d_backward_bias(&self) -> Tensor1<T, C, B> {
+        let mut grad_bias = <B as HasStorage<T, C>>::storage_uninit();
+        B::conv2d_backward_bias::<{ N * (C * (OH * OW)) }, C>(
+            N,
+            C,
+            OH * OW,
+            &self.storage,
+            &mut grad_bias,
+        );
+        Tensor1 {
+            storage: grad_bias,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
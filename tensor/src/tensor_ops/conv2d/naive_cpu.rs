@@ -0,0 +1,215 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::tensor_ops::conv::kernel::{
+    conv2d_backward_planes, conv2d_bias_grad, conv2d_planes, conv2d_weight_grad_planes,
+};
+use crate::tensor_ops::conv2d::{Conv2d, Conv2dShape};
+use core::ops::{Add, Mul};
+
+impl<T> Conv2d<T> for NaiveCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    fn conv2d<const NI: usize, const NK: usize, const NB: usize, const NO: usize>(
+        shape: &Conv2dShape,
+        input: &<Self as HasStorage<T, NI>>::Storage,
+        weight: &<Self as HasStorage<T, NK>>::Storage,
+        bias: Option<&<Self as HasStorage<T, NB>>::Storage>,
+        output: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NI> + HasStorage<T, NK> + HasStorage<T, NB> + HasStorage<T, NO>,
+    {
+        let inp = <Self as HasStorage<T, NI>>::as_slice(input);
+        let weight = <Self as HasStorage<T, NK>>::as_slice(weight);
+        let bias = bias.map(<Self as HasStorage<T, NB>>::as_slice);
+        let out = <Self as HasStorage<T, NO>>::as_mut_slice(output);
+        conv2d_planes(shape, inp, weight, bias, 0, out);
+    }
+
+    fn conv2d_backward<const NI: usize, const NK: usize, const NO: usize>(
+        shape: &Conv2dShape,
+        weight: &<Self as HasStorage<T, NK>>::Storage,
+        grad_output: &<Self as HasStorage<T, NO>>::Storage,
+        grad_input: &mut <Self as HasStorage<T, NI>>::Storage,
+    ) where
+        Self: HasStorage<T, NI> + HasStorage<T, NK> + HasStorage<T, NO>,
+    {
+        let weight = <Self as HasStorage<T, NK>>::as_slice(weight);
+        let grad_out = <Self as HasStorage<T, NO>>::as_slice(grad_output);
+        let grad_in = <Self as HasStorage<T, NI>>::as_mut_slice(grad_input);
+        conv2d_backward_planes(shape, weight, grad_out, 0, grad_in);
+    }
+
+    fn conv2d_backward_weight<const NI: usize, const NK: usize, const NO: usize>(
+        shape: &Conv2dShape,
+        input: &<Self as HasStorage<T, NI>>::Storage,
+        grad_output: &<Self as HasStorage<T, NO>>::Storage,
+        grad_weight: &mut <Self as HasStorage<T, NK>>::Storage,
+    ) where
+        Self: HasStorage<T, NI> + HasStorage<T, NK> + HasStorage<T, NO>,
+    {
+        let inp = <Self as HasStorage<T, NI>>::as_slice(input);
+        let grad_out = <Self as HasStorage<T, NO>>::as_slice(grad_output);
+        let grad_w = <Self as HasStorage<T, NK>>::as_mut_slice(grad_weight);
+        conv2d_weight_grad_planes(shape, inp, grad_out, 0, grad_w);
+    }
+
+    fn conv2d_backward_bias<const NO: usize, const NB: usize>(
+        batch: usize,
+        channels: usize,
+        plane: usize,
+        grad_output: &<Self as HasStorage<T, NO>>::Storage,
+        grad_bias: &mut <Self as HasStorage<T, NB>>::Storage,
+    ) where
+        Self: HasStorage<T, NO> + HasStorage<T, NB>,
+    {
+        let grad_out = <Self as HasStorage<T, NO>>::as_slice(grad_output);
+        let grad_b = <Self as HasStorage<T, NB>>::as_mut_slice(grad_bias);
+        conv2d_bias_grad(batch, channels, plane, grad_out, grad_b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::tensor::{Tensor1, Tensor2, Tensor4};
+    use crate::tensor_ops::conv::Padding;
+
+    #[test]
+    fn test_conv2d_sums_channels_per_filter() {
+        // Two 3x3 input channels; filter 0 reads channel 0 only, filter 1
+        // subtracts channel 1 from channel 0.
+        let plane: [i32; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut x = [0; 18];
+        x[..9].copy_from_slice(&plane);
+        x[9..].copy_from_slice(&[1; 9]);
+        let x = Tensor4::<i32, 1, 2, 3, 3, NaiveCpu>::new(x);
+        let k = [1, 0, 0, -1];
+        let w = Tensor4::<i32, 2, 2, 2, 2, NaiveCpu>::new([
+            1, 0, 0, -1, 0, 0, 0, 0, //
+            1, 0, 0, -1, -1, -1, -1, -1,
+        ]);
+        let bias = Tensor1::<i32, 2, NaiveCpu>::new([10, 0]);
+
+        let y = x.conv2d::<2, 2, 2, 1, 0>(&w);
+        // Filter 0 matches the single-channel convolution of channel 0.
+        let single = Tensor2::<i32, 3, 3, NaiveCpu>::new(plane)
+            .convolve::<2, 2, 1, 0>(&Tensor2::<i32, 2, 2, NaiveCpu>::new(k));
+        assert_eq!(&y.as_slice()[..4], single.as_slice());
+        assert_eq!(&y.as_slice()[4..], &[-8; 4]);
+
+        let yb = x.conv2d_bias::<2, 2, 2, 1, 0>(&w, &bias);
+        assert_eq!(yb.as_slice(), &[6, 6, 6, 6, -8, -8, -8, -8]);
+    }
+
+    #[test]
+    fn test_conv2d_backward_is_adjoint() {
+        // <conv(x), g> == <x, conv_backward(g)> for every x and g.
+        let x = Tensor4::<i32, 2, 3, 5, 4, NaiveCpu>::new(core::array::from_fn(|i| {
+            (i * 5 % 11) as i32 - 5
+        }));
+        let w = Tensor4::<i32, 2, 3, 3, 2, NaiveCpu>::new(core::array::from_fn(|i| {
+            (i * 3 % 7) as i32 - 3
+        }));
+        let y = x.conv2d::<2, 3, 2, 2, 1>(&w);
+        let g = Tensor4::<i32, 2, 2, 3, 3, NaiveCpu>::new(core::array::from_fn(|i| {
+            (i * 7 % 5) as i32 - 2
+        }));
+        let dx =
+            Tensor4::<i32, 2, 3, 5, 4, NaiveCpu>::conv2d_backward::<2, 3, 2, 2, 1>(&g, &w);
+
+        let lhs: i32 = y.as_slice().iter().zip(g.as_slice()).map(|(a, b)| a * b).sum();
+        let rhs: i32 = x.as_slice().iter().zip(dx.as_slice()).map(|(a, b)| a * b).sum();
+        assert_eq!(lhs, rhs);
+    }
+
+    #[test]
+    fn test_conv2d_weight_and_bias_gradients_are_adjoint() {
+        let x = Tensor4::<i32, 2, 3, 5, 4, NaiveCpu>::new(core::array::from_fn(|i| {
+            (i * 5 % 11) as i32 - 5
+        }));
+        let w = Tensor4::<i32, 2, 3, 3, 2, NaiveCpu>::new(core::array::from_fn(|i| {
+            (i * 3 % 7) as i32 - 3
+        }));
+        let b = Tensor1::<i32, 2, NaiveCpu>::new([4, -3]);
+        let g = Tensor4::<i32, 2, 2, 3, 3, NaiveCpu>::new(core::array::from_fn(|i| {
+            (i * 7 % 5) as i32 - 2
+        }));
+        let dot = |a: &[i32], b: &[i32]| -> i32 { a.iter().zip(b).map(|(x, y)| x * y).sum() };
+
+        // The output is linear in the weight, and affine in the bias.
+        let y = x.conv2d::<2, 3, 2, 2, 1>(&w);
+        let dw = x.conv2d_backward_weight::<2, 3, 2, 2, 1>(&g);
+        assert_eq!(dot(y.as_slice(), g.as_slice()), dot(w.as_slice(), dw.as_slice()));
+
+        let yb = x.conv2d_bias::<2, 3, 2, 2, 1>(&w, &b);
+        let shift: Vec<i32> = yb.as_slice().iter().zip(y.as_slice()).map(|(p, q)| p - q).collect();
+        let db = g.conv2d_backward_bias();
+        assert_eq!(dot(&shift, g.as_slice()), dot(b.as_slice(), db.as_slice()));
+    }
+
+    #[test]
+    fn test_grouped_conv2d_matches_block_diagonal_filter_bank() {
+        // Two groups of two channels are a dense convolution whose filter
+        // bank is zero outside each output channel's own group.
+        let x = Tensor4::<i32, 2, 4, 5, 5, NaiveCpu>::new(core::array::from_fn(|i| {
+            (i * 5 % 13) as i32 - 6
+        }));
+        let w: [i32; 4 * 2 * 2 * 2] = core::array::from_fn(|i| (i * 3 % 7) as i32 - 3);
+        let mut dense = [0; 4 * 4 * 2 * 2];
+        for co in 0..4 {
+            let block = &w[co * 8..][..8];
+            dense[co * 16 + co / 2 * 8..][..8].copy_from_slice(block);
+        }
+        let grouped = Tensor4::<i32, 4, 2, 2, 2, NaiveCpu>::new(w);
+        let dense = Tensor4::<i32, 4, 4, 2, 2, NaiveCpu>::new(dense);
+
+        let y = x.conv2d_grouped::<4, 2, 2, 2, 1, 2, 2>(&grouped);
+        assert_eq!(y.as_slice(), x.conv2d_grouped::<4, 2, 2, 2, 1, 2, 1>(&dense).as_slice());
+
+        let g = Tensor4::<i32, 2, 4, 3, 3, NaiveCpu>::new(core::array::from_fn(|i| {
+            (i * 7 % 5) as i32 - 2
+        }));
+        let dx = Tensor4::<i32, 2, 4, 5, 5, NaiveCpu>::conv2d_grouped_backward::<4, 2, 2, 2, 1, 2, 2>(
+            &g, &grouped,
+        );
+        let dx_dense = Tensor4::<i32, 2, 4, 5, 5, NaiveCpu>::conv2d_grouped_backward::<4, 2, 2, 2, 1, 2, 1>(
+            &g, &dense,
+        );
+        assert_eq!(dx.as_slice(), dx_dense.as_slice());
+
+        let dw = x.conv2d_grouped_backward_weight::<4, 2, 2, 2, 1, 2, 2>(&g);
+        let dw_dense = x.conv2d_grouped_backward_weight::<4, 2, 2, 2, 1, 2, 1>(&g);
+        for co in 0..4 {
+            let block = &dw_dense.as_slice()[co * 16 + co / 2 * 8..][..8];
+            assert_eq!(&dw.as_slice()[co * 8..][..8], block);
+        }
+    }
+
+    #[test]
+    fn test_depthwise_conv2d_convolves_each_channel_alone() {
+        let x = Tensor4::<i32, 1, 2, 5, 5, NaiveCpu>::new(core::array::from_fn(|i| i as i32));
+        let w = Tensor4::<i32, 2, 1, 2, 2, NaiveCpu>::new([1, 0, 0, -1, 0, 1, 2, 0]);
+        // Dilation 2 spreads each 2x2 kernel over a 3x3 window.
+        let y = x.depthwise_conv2d::<2, 2, 1, 0, 2>(&w);
+        for c in 0..2 {
+            let plane = &x.as_slice()[c * 25..][..25];
+            let plane = Tensor2::<i32, 5, 5, NaiveCpu>::new_from_slice(plane);
+            let ker = Tensor2::<i32, 2, 2, NaiveCpu>::new_from_slice(&w.as_slice()[c * 4..][..4]);
+            let want = plane.convolve_padded::<2, 2, 1, 1, 2, { Padding::symmetric(0) }>(&ker);
+            assert_eq!(&y.as_slice()[c * 9..][..9], want.as_slice());
+        }
+        assert_eq!(&y.as_slice()[..9], &[-12; 9]);
+
+        let g = Tensor4::<i32, 1, 2, 3, 3, NaiveCpu>::new(core::array::from_fn(|i| {
+            (i * 7 % 5) as i32 - 2
+        }));
+        let dot = |a: &[i32], b: &[i32]| -> i32 { a.iter().zip(b).map(|(x, y)| x * y).sum() };
+        let dx = Tensor4::<i32, 1, 2, 5, 5, NaiveCpu>::depthwise_conv2d_backward::<2, 2, 1, 0, 2>(
+            &g, &w,
+        );
+        let dw = x.depthwise_conv2d_backward_weight::<2, 2, 1, 0, 2>(&g);
+        assert_eq!(dot(y.as_slice(), g.as_slice()), dot(x.as_slice(), dx.as_slice()));
+        assert_eq!(dot(y.as_slice(), g.as_slice()), dot(w.as_slice(), dw.as_slice()));
+    }
+}
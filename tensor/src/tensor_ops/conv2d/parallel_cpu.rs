@@ -0,0 +1,159 @@
+use crate::storage::HasStorage;
+use crate::storage::parallel_cpu::{ParallelCpu, for_each_chunk};
+use crate::tensor_ops::conv::kernel::{
+    conv2d_backward_planes, conv2d_bias_grad, conv2d_planes, conv2d_weight_grad_planes,
+};
+use crate::tensor_ops::conv2d::{Conv2d, Conv2dShape};
+use core::ops::{Add, Mul};
+
+/// Fewest planes worth giving a thread of their own.
+const MIN_PLANES_PER_THREAD: usize = 1;
+
+impl<T> Conv2d<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    fn conv2d<const NI: usize, const NK: usize, const NB: usize, const NO: usize>(
+        shape: &Conv2dShape,
+        input: &<Self as HasStorage<T, NI>>::Storage,
+        weight: &<Self as HasStorage<T, NK>>::Storage,
+        bias: Option<&<Self as HasStorage<T, NB>>::Storage>,
+        output: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NI> + HasStorage<T, NK> + HasStorage<T, NB> + HasStorage<T, NO>,
+    {
+        let inp = <Self as HasStorage<T, NI>>::as_slice(input);
+        let weight = <Self as HasStorage<T, NK>>::as_slice(weight);
+        let bias = bias.map(<Self as HasStorage<T, NB>>::as_slice);
+        let out = <Self as HasStorage<T, NO>>::as_mut_slice(output);
+        let plane = shape.out_h() * shape.out_w();
+        for_each_chunk(out, plane, MIN_PLANES_PER_THREAD, |first, run| {
+            conv2d_planes(shape, inp, weight, bias, first, run);
+        });
+    }
+
+    fn conv2d_backward<const NI: usize, const NK: usize, const NO: usize>(
+        shape: &Conv2dShape,
+        weight: &<Self as HasStorage<T, NK>>::Storage,
+        grad_output: &<Self as HasStorage<T, NO>>::Storage,
+        grad_input: &mut <Self as HasStorage<T, NI>>::Storage,
+    ) where
+        Self: HasStorage<T, NI> + HasStorage<T, NK> + HasStorage<T, NO>,
+    {
+        let weight = <Self as HasStorage<T, NK>>::as_slice(weight);
+        let grad_out = <Self as HasStorage<T, NO>>::as_slice(grad_output);
+        let grad_in = <Self as HasStorage<T, NI>>::as_mut_slice(grad_input);
+        for_each_chunk(grad_in, shape.h * shape.w, MIN_PLANES_PER_THREAD, |first, run| {
+            conv2d_backward_planes(shape, weight, grad_out, first, run);
+        });
+    }
+
+    fn conv2d_backward_weight<const NI: usize, const NK: usize, const NO: usize>(
+        shape: &Conv2dShape,
+        input: &<Self as HasStorage<T, NI>>::Storage,
+        grad_output: &<Self as HasStorage<T, NO>>::Storage,
+        grad_weight: &mut <Self as HasStorage<T, NK>>::Storage,
+    ) where
+        Self: HasStorage<T, NI> + HasStorage<T, NK> + HasStorage<T, NO>,
+    {
+        let inp = <Self as HasStorage<T, NI>>::as_slice(input);
+        let grad_out = <Self as HasStorage<T, NO>>::as_slice(grad_output);
+        let grad_w = <Self as HasStorage<T, NK>>::as_mut_slice(grad_weight);
+        for_each_chunk(grad_w, shape.kh * shape.kw, MIN_PLANES_PER_THREAD, |first, run| {
+            conv2d_weight_grad_planes(shape, inp, grad_out, first, run);
+        });
+    }
+
+    fn conv2d_backward_bias<const NO: usize, const NB: usize>(
+        batch: usize,
+        channels: usize,
+        plane: usize,
+        grad_output: &<Self as HasStorage<T, NO>>::Storage,
+        grad_bias: &mut <Self as HasStorage<T, NB>>::Storage,
+    ) where
+        Self: HasStorage<T, NO> + HasStorage<T, NB>,
+    {
+        let grad_out = <Self as HasStorage<T, NO>>::as_slice(grad_output);
+        let grad_b = <Self as HasStorage<T, NB>>::as_mut_slice(grad_bias);
+        conv2d_bias_grad(batch, channels, plane, grad_out, grad_b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::storage::parallel_cpu::ParallelCpu;
+    use crate::tensor::{Tensor1, Tensor4};
+
+    #[test]
+    fn test_matches_naive_cpu() {
+        ParallelCpu::with_num_threads(3, || {
+            let x: [f32; 2 * 3 * 6 * 5] = core::array::from_fn(|i| (i % 13) as f32 * 0.5 - 3.0);
+            let w: [f32; 4 * 3 * 3 * 3] = core::array::from_fn(|i| (i % 7) as f32 - 3.0);
+            let b = [0.5f32, -1.0, 2.0, 0.0];
+
+            let want = Tensor4::<f32, 2, 3, 6, 5, NaiveCpu>::new(x)
+                .conv2d_bias::<4, 3, 3, 2, 1>(
+                    &Tensor4::<f32, 4, 3, 3, 3, NaiveCpu>::new(w),
+                    &Tensor1::<f32, 4, NaiveCpu>::new(b),
+                );
+            let got = Tensor4::<f32, 2, 3, 6, 5, ParallelCpu>::new(x)
+                .conv2d_bias::<4, 3, 3, 2, 1>(
+                    &Tensor4::<f32, 4, 3, 3, 3, ParallelCpu>::new(w),
+                    &Tensor1::<f32, 4, ParallelCpu>::new(b),
+                );
+            assert_eq!(got.as_slice(), want.as_slice());
+
+            let g = Tensor4::<f32, 2, 4, 3, 3, NaiveCpu>::new(want.as_slice().try_into().unwrap());
+            let want = Tensor4::<f32, 2, 3, 6, 5, NaiveCpu>::conv2d_backward::<4, 3, 3, 2, 1>(
+                &g,
+                &Tensor4::<f32, 4, 3, 3, 3, NaiveCpu>::new(w),
+            );
+            let g = Tensor4::<f32, 2, 4, 3, 3, ParallelCpu>::new(g.as_slice().try_into().unwrap());
+            let got = Tensor4::<f32, 2, 3, 6, 5, ParallelCpu>::conv2d_backward::<4, 3, 3, 2, 1>(
+                &g,
+                &Tensor4::<f32, 4, 3, 3, 3, ParallelCpu>::new(w),
+            );
+            assert_eq!(got.as_slice(), want.as_slice());
+
+            let want = Tensor4::<f32, 2, 3, 6, 5, NaiveCpu>::new(x)
+                .conv2d_backward_weight::<4, 3, 3, 2, 1>(
+                    &Tensor4::<f32, 2, 4, 3, 3, NaiveCpu>::new(g.as_slice().try_into().unwrap()),
+                );
+            let got = Tensor4::<f32, 2, 3, 6, 5, ParallelCpu>::new(x)
+                .conv2d_backward_weight::<4, 3, 3, 2, 1>(&g);
+            assert_eq!(got.as_slice(), want.as_slice());
+        });
+    }
+
+    #[test]
+    fn test_grouped_dilated_matches_naive_cpu() {
+        ParallelCpu::with_num_threads(3, || {
+            let x: [f32; 2 * 4 * 7 * 6] = core::array::from_fn(|i| (i % 11) as f32 * 0.5 - 2.5);
+            let w: [f32; 6 * 2 * 3 * 2] = core::array::from_fn(|i| (i % 5) as f32 - 2.0);
+
+            let want = Tensor4::<f32, 2, 4, 7, 6, NaiveCpu>::new(x)
+                .conv2d_grouped::<6, 3, 2, 1, 1, 2, 2>(&Tensor4::<f32, 6, 2, 3, 2, NaiveCpu>::new(w));
+            let got = Tensor4::<f32, 2, 4, 7, 6, ParallelCpu>::new(x)
+                .conv2d_grouped::<6, 3, 2, 1, 1, 2, 2>(&Tensor4::<f32, 6, 2, 3, 2, ParallelCpu>::new(w));
+            assert_eq!(got.as_slice(), want.as_slice());
+
+            let g = Tensor4::<f32, 2, 6, 5, 6, NaiveCpu>::new(want.as_slice().try_into().unwrap());
+            let want = Tensor4::<f32, 2, 4, 7, 6, NaiveCpu>::conv2d_grouped_backward::<6, 3, 2, 1, 1, 2, 2>(
+                &g,
+                &Tensor4::<f32, 6, 2, 3, 2, NaiveCpu>::new(w),
+            );
+            let want_w = Tensor4::<f32, 2, 4, 7, 6, NaiveCpu>::new(x)
+                .conv2d_grouped_backward_weight::<6, 3, 2, 1, 1, 2, 2>(&g);
+            let g = Tensor4::<f32, 2, 6, 5, 6, ParallelCpu>::new(g.as_slice().try_into().unwrap());
+            let got = Tensor4::<f32, 2, 4, 7, 6, ParallelCpu>::conv2d_grouped_backward::<6, 3, 2, 1, 1, 2, 2>(
+                &g,
+                &Tensor4::<f32, 6, 2, 3, 2, ParallelCpu>::new(w),
+            );
+            let got_w = Tensor4::<f32, 2, 4, 7, 6, ParallelCpu>::new(x)
+                .conv2d_grouped_backward_weight::<6, 3, 2, 1, 1, 2, 2>(&g);
+            assert_eq!(got.as_slice(), want.as_slice());
+            assert_eq!(got_w.as_slice(), want_w.as_slice());
+        });
+    }
+}
@@ -0,0 +1,522 @@
+//! `einsum!`: two-operand tensor contractions written in Einstein notation.
+//!
+//! ```ignore
+//! let scores: Tensor3<f32, H, S, S, NaiveCpu> = einsum!("hqd,hkd->hqk", q, k);
+//! ```
+//!
+//! The spec names every axis of both operands and of the output with a
+//! lowercase letter; letters missing from the output are summed over. The
+//! spec, operand ranks, sizes of shared indices and the output shape are all
+//! checked at compile time (when the call is monomorphized).
+//!
+//! Specs with the shape of a contraction that already has a dedicated trait
+//! lower to it, whatever letters they use; [`classify`] decides which. With
+//! `P` a run of batch indices shared by the operands and the output:
+//!
+//! - `P i j, j k -> P i k` and `P i j, P j k -> P i k` (up to two batch
+//!   indices) use the (broadcast or batched) matmul, so `ik,kj->ij` and
+//!   `bqd,bdk->bqk` are plain `*`;
+//! - `P i j, P j -> P i`, `P i, P i j -> P j`, `P i, P i -> P` and
+//!   `P i, P j -> P i j` (at most one batch index) go through
+//!   [`MatVec`](crate::tensor_ops::matvec::MatVec).
+//!
+//! Lowered specs are checked the same way, so an operand whose rank does not
+//! match its term is rejected even when the dedicated op would accept it:
+//!
+//! ```
+//! # #![feature(generic_const_exprs, adt_const_params)]
+//! # #![allow(incomplete_features)]
+//! use tensor::einsum;
+//! use tensor::storage::naive_cpu::NaiveCpu;
+//! use tensor::tensor::{Tensor2, Tensor3};
+//!
+//! let a = Tensor2::<f32, 2, 3, NaiveCpu>::ones();
+//! let b = Tensor2::<f32, 3, 4, NaiveCpu>::ones();
+//! let _: Tensor2<f32, 2, 4, NaiveCpu> = einsum!("ij,jk->ik", a, b);
+//! ```
+//!
+//! ```compile_fail
+//! # #![feature(generic_const_exprs, adt_const_params)]
+//! # #![allow(incomplete_features)]
+//! use tensor::einsum;
+//! use tensor::storage::naive_cpu::NaiveCpu;
+//! use tensor::tensor::{Tensor2, Tensor3};
+//!
+//! let a = Tensor3::<f32, 5, 2, 3, NaiveCpu>::ones();
+//! let b = Tensor2::<f32, 3, 4, NaiveCpu>::ones();
+//! let _ = einsum!("ij,jk->ik", a, b);
+//! ```
+//!
+//! Every other spec runs the generic contraction loop of the backend's
+//! [`Einsum`] implementation. A full contraction to a scalar is only
+//! supported for the dot product of two vectors (`i,i->` under any index
+//! name), which returns `T`; other specs with an empty output, such as
+//! `ij,ij->`, do not compile.
+
+pub mod naive_cpu;
+pub mod parallel_cpu;
+
+use crate::storage::HasStorage;
+use crate::tensor::{Tensor1, Tensor2, Tensor3, Tensor4};
+use crate::tensor_ops::matvec::MatVec;
+use core::marker::{ConstParamTy, PhantomData};
+use core::ops::{Add, Mul};
+
+/// Contracts two tensors according to an Einstein-notation spec.
+///
+/// See the [module docs](crate::tensor_ops::einsum) for the accepted specs
+/// and which of them lower to dedicated traits. The output type is inferred,
+/// so the generic path usually needs an annotation on the binding.
+#[macro_export]
+macro_rules! einsum {
+    ($spec:literal, $a:expr, $b:expr $(,)?) => {{
+        struct Spec;
+        impl $crate::tensor_ops::einsum::EinsumSpec for Spec {
+            const SPEC: &'static str = $spec;
+        }
+        <$crate::tensor_ops::einsum::Via<{ $crate::tensor_ops::einsum::classify($spec) }> as
+            $crate::tensor_ops::einsum::Lower<Spec, _, _, _, _, _>>::lower(&$a, &$b)
+    }};
+}
+
+/// A spec string lifted into a type, so it can be checked in a constant.
+/// Implemented by [`einsum!`](crate::einsum) for each call site.
+pub trait EinsumSpec {
+    const SPEC: &'static str;
+}
+
+/// A tensor that can take part in a generic contraction.
+pub trait EinsumTensor<T, B>: Sized {
+    /// Row-major dimensions, outermost first.
+    const SHAPE: &'static [usize];
+
+    fn elements(&self) -> &[T];
+
+    fn elements_mut(&mut self) -> &mut [T];
+
+    /// A tensor whose contents will be overwritten.
+    fn uninit() -> Self;
+}
+
+/// Backend trait for the generic contraction loop.
+pub trait Einsum<T: Copy + Default + Add<Output = T> + Mul<Output = T>>: Sized {
+    /// Writes every element of `out` as described by `plan`.
+    fn contract(plan: &ContractionPlan, a: &[T], b: &[T], out: &mut [T]);
+}
+
+/// An einsum spec resolved against concrete shapes.
+///
+/// Every distinct index gets a slot: the output indices first, in output
+/// order, then the summed ones. `dims[l]` is the size of index `l`, and
+/// `strides[t][l]` how far operand `t` (`0`, `1`, or `2` for the output)
+/// moves per step of index `l`, zero when the index does not appear in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractionPlan {
+    pub dims: Vec<usize>,
+    pub strides: [Vec<usize>; 3],
+    /// How many of the leading slots are output indices.
+    pub out_rank: usize,
+}
+
+impl ContractionPlan {
+    /// Builds the plan for a spec that has passed [`validate`].
+    pub fn new(spec: &str, shapes: [&[usize]; 3]) -> Self {
+        let bytes = spec.as_bytes();
+        let terms = terms(bytes);
+        let labels = |t: usize| &bytes[terms[t].0..terms[t].1];
+
+        let mut order: Vec<u8> = labels(2).to_vec();
+        for &l in labels(0).iter().chain(labels(1)) {
+            if !order.contains(&l) {
+                order.push(l);
+            }
+        }
+
+        let mut dims = vec![0; order.len()];
+        let mut strides = [vec![0; order.len()], vec![0; order.len()], vec![0; order.len()]];
+        for (t, shape) in shapes.iter().enumerate() {
+            let mut stride = 1;
+            for (p, &l) in labels(t).iter().enumerate().rev() {
+                let slot = order.iter().position(|&o| o == l).unwrap();
+                dims[slot] = shape[p];
+                // A repeated index walks the diagonal.
+                strides[t][slot] += stride;
+                stride *= shape[p];
+            }
+        }
+        Self {
+            dims,
+            strides,
+            out_rank: terms[2].1 - terms[2].0,
+        }
+    }
+}
+
+/// Splits `spec` into the `[start, end)` byte ranges of its two operand terms
+/// and its output term, panicking on malformed input.
+const fn terms(spec: &[u8]) -> [(usize, usize); 3] {
+    let mut comma = usize::MAX;
+    let mut arrow = usize::MAX;
+    let mut i = 0;
+    while i < spec.len() {
+        let c = spec[i];
+        if c == b',' {
+            assert!(comma == usize::MAX, "einsum: expected exactly two operands");
+            comma = i;
+        } else if c == b'-' {
+            assert!(i + 1 < spec.len() && spec[i + 1] == b'>', "einsum: expected `->`");
+            assert!(arrow == usize::MAX && comma != usize::MAX, "einsum: misplaced `->`");
+            arrow = i;
+            i += 1;
+        } else {
+            assert!(c.is_ascii_lowercase(), "einsum: indices must be lowercase letters");
+        }
+        i += 1;
+    }
+    assert!(comma != usize::MAX, "einsum: expected exactly two operands");
+    assert!(arrow != usize::MAX, "einsum: the output must be spelled out after `->`");
+    [(0, comma), (comma + 1, arrow), (arrow + 2, spec.len())]
+}
+
+/// How [`einsum!`](crate::einsum) evaluates a spec; see [`classify`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ConstParamTy)]
+pub enum Lowering {
+    /// `P i j, j k -> P i k` or `P i j, P j k -> P i k`, `P` at most two
+    /// batch indices.
+    MatMul,
+    /// `P i j, P j -> P i`, `P` at most one batch index.
+    MatVec,
+    /// `P i, P i j -> P j`, `P` at most one batch index.
+    VecMat,
+    /// `P i, P i -> P`, `P` at most one batch index.
+    Dot,
+    /// `P i, P j -> P i j`, `P` at most one batch index.
+    Outer,
+    /// Anything else: the backend's [`Einsum`] loop.
+    Generic,
+}
+
+/// Sorts `spec` by the positions of its batch, free and contracted indices
+/// into the dedicated op it can lower to, if any.
+pub const fn classify(spec: &str) -> Lowering {
+    let s = spec.as_bytes();
+    let [a, b, o] = terms(s);
+    let (ra, rb, ro) = (a.1 - a.0, b.1 - b.0, o.1 - o.0);
+    // A repeated index reads a diagonal, which none of the dedicated ops do.
+    if has_repeat(s, a) || has_repeat(s, b) || has_repeat(s, o) {
+        return Lowering::Generic;
+    }
+
+    // With every term free of repeats, matching positions pins down the
+    // roles: e.g. for matmul `j` is missing from the output because `o`
+    // holds `P`, `i` and `k`, all distinct from `j` since `a` and `b` are.
+    if ra >= 2 && ra <= 4 && ro == ra && (rb == 2 || rb == ra) {
+        let p = ra - 2;
+        let (i, j, k) = (s[a.0 + p], s[a.0 + p + 1], s[b.1 - 1]);
+        if same(s, a.0, o.0, p)
+            && (rb == 2 || same(s, a.0, b.0, p))
+            && s[b.1 - 2] == j
+            && s[o.0 + p] == i
+            && s[o.0 + p + 1] == k
+        {
+            return Lowering::MatMul;
+        }
+    }
+    if ra == rb + 1 && ro == rb && rb <= 2 {
+        let p = rb - 1;
+        if same(s, a.0, b.0, p)
+            && same(s, a.0, o.0, p)
+            && s[a.0 + p + 1] == s[b.0 + p]
+            && s[a.0 + p] == s[o.0 + p]
+        {
+            return Lowering::MatVec;
+        }
+    }
+    if rb == ra + 1 && ro == ra && ra <= 2 {
+        let p = ra - 1;
+        if same(s, a.0, b.0, p)
+            && same(s, a.0, o.0, p)
+            && s[a.0 + p] == s[b.0 + p]
+            && s[b.0 + p + 1] == s[o.0 + p]
+        {
+            return Lowering::VecMat;
+        }
+    }
+    if ra == rb && ra >= 1 && ra <= 2 && same(s, a.0, b.0, ra - 1) && same(s, a.0, o.0, ra - 1) {
+        let p = ra - 1;
+        if ro == p && s[a.0 + p] == s[b.0 + p] {
+            return Lowering::Dot;
+        }
+        if ro == ra + 1 && s[o.0 + p] == s[a.0 + p] && s[o.0 + p + 1] == s[b.0 + p] {
+            return Lowering::Outer;
+        }
+    }
+    Lowering::Generic
+}
+
+/// Whether the `len` indices starting at `x` and at `y` are the same.
+const fn same(s: &[u8], x: usize, y: usize, len: usize) -> bool {
+    let mut i = 0;
+    while i < len {
+        if s[x + i] != s[y + i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Whether some index occurs twice in the term `t`.
+const fn has_repeat(s: &[u8], t: (usize, usize)) -> bool {
+    let mut p = t.0;
+    while p < t.1 {
+        let mut q = p + 1;
+        while q < t.1 {
+            if s[p] == s[q] {
+                return true;
+            }
+            q += 1;
+        }
+        p += 1;
+    }
+    false
+}
+
+/// Checks `spec` against the operand and output shapes, panicking (a
+/// compile error when evaluated in a constant) on any mismatch.
+pub const fn validate(spec: &str, shapes: [&[usize]; 3]) {
+    let bytes = spec.as_bytes();
+    let terms = terms(bytes);
+
+    let mut t = 0;
+    while t < 3 {
+        let (start, end) = terms[t];
+        assert!(end - start == shapes[t].len(), "einsum: index count does not match rank");
+        let mut p = start;
+        while p < end {
+            let label = bytes[p];
+            let dim = shapes[t][p - start];
+            // Compare against every other occurrence of the same index.
+            let mut u = 0;
+            let mut in_input = false;
+            while u < 3 {
+                let mut q = terms[u].0;
+                while q < terms[u].1 {
+                    if bytes[q] == label {
+                        assert!(shapes[u][q - terms[u].0] == dim, "einsum: index sizes differ");
+                        assert!(
+                            t != 2 || u != 2 || q == p,
+                            "einsum: an output index appears twice"
+                        );
+                        in_input |= u < 2;
+                    }
+                    q += 1;
+                }
+                u += 1;
+            }
+            assert!(in_input, "einsum: an output index is missing from both operands");
+            p += 1;
+        }
+        t += 1;
+    }
+}
+
+struct Check<S, A, Bt, O, T, B>(PhantomData<(S, A, Bt, O, T, B)>);
+
+impl<S, A, Bt, O, T, B> Check<S, A, Bt, O, T, B>
+where
+    S: EinsumSpec,
+    A: EinsumTensor<T, B>,
+    Bt: EinsumTensor<T, B>,
+    O: EinsumTensor<T, B>,
+{
+    const VALID: () = validate(S::SPEC, [A::SHAPE, Bt::SHAPE, O::SHAPE]);
+}
+
+impl<S, A, Bt, O, T, B> Check<S, A, Bt, O, T, B>
+where
+    S: EinsumSpec,
+    A: EinsumTensor<T, B>,
+    Bt: EinsumTensor<T, B>,
+{
+    /// [`Self::VALID`] for a full contraction, whose output is a bare `T`.
+    const VALID_SCALAR: () = validate(S::SPEC, [A::SHAPE, Bt::SHAPE, &[]]);
+}
+
+/// The generic path of [`einsum!`](crate::einsum).
+pub fn contract<S, A, Bt, O, T, B>(a: &A, b: &Bt) -> O
+where
+    S: EinsumSpec,
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    B: Einsum<T>,
+    A: EinsumTensor<T, B>,
+    Bt: EinsumTensor<T, B>,
+    O: EinsumTensor<T, B>,
+{
+    #[allow(clippy::let_unit_value)]
+    let () = Check::<S, A, Bt, O, T, B>::VALID;
+    let plan = ContractionPlan::new(S::SPEC, [A::SHAPE, Bt::SHAPE, O::SHAPE]);
+    let mut out = O::uninit();
+    B::contract(&plan, a.elements(), b.elements(), out.elements_mut());
+    out
+}
+
+/// [`Lowering`] as a type, so each strategy gets its own impls of [`Lower`].
+pub struct Via<const L: Lowering>;
+
+/// Evaluates the spec `S` for operands `A` and `Bt` into `O`, the way the
+/// strategy implementing it prescribes. Called by [`einsum!`](crate::einsum).
+pub trait Lower<S, T, B, A, Bt, O> {
+    fn lower(a: &A, b: &Bt) -> O;
+}
+
+impl<S, T, B, A, Bt, O> Lower<S, T, B, A, Bt, O> for Via<{ Lowering::Generic }>
+where
+    S: EinsumSpec,
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    B: Einsum<T>,
+    A: EinsumTensor<T, B>,
+    Bt: EinsumTensor<T, B>,
+    O: EinsumTensor<T, B>,
+{
+    #[inline]
+    fn lower(a: &A, b: &Bt) -> O {
+        contract::<S, A, Bt, O, T, B>(a, b)
+    }
+}
+
+impl<S, T, B, A, Bt, O> Lower<S, T, B, A, Bt, O> for Via<{ Lowering::MatMul }>
+where
+    S: EinsumSpec,
+    A: EinsumTensor<T, B>,
+    Bt: EinsumTensor<T, B>,
+    O: EinsumTensor<T, B>,
+    for<'x> &'x A: Mul<&'x Bt, Output = O>,
+{
+    #[inline]
+    fn lower(a: &A, b: &Bt) -> O {
+        // `*` picks its impl from the operand types alone, so the spec still
+        // has to be checked against them.
+        #[allow(clippy::let_unit_value)]
+        let () = Check::<S, A, Bt, O, T, B>::VALID;
+        a * b
+    }
+}
+
+macro_rules! impl_lower {
+    (
+        $via:ident <$($c:ident),+> ($a:ty, $b:ty) -> T
+        where [$($bound:tt)*]
+        |$x:ident, $y:ident| $body:expr
+    ) => {
+        impl_lower!(@impl VALID_SCALAR [] $via <$($c),+> ($a, $b) -> T
+            where [$($bound)*] |$x, $y| $body);
+    };
+    (
+        $via:ident <$($c:ident),+> ($a:ty, $b:ty) -> $o:ty
+        where [$($bound:tt)*]
+        |$x:ident, $y:ident| $body:expr
+    ) => {
+        impl_lower!(@impl VALID [$o: EinsumTensor<T, B>,] $via <$($c),+> ($a, $b) -> $o
+            where [$($bound)*] |$x, $y| $body);
+    };
+    (
+        @impl $valid:ident [$($out_bound:tt)*] $via:ident <$($c:ident),+> ($a:ty, $b:ty) -> $o:ty
+        where [$($bound:tt)*]
+        |$x:ident, $y:ident| $body:expr
+    ) => {
+        impl<S, T, $(const $c: usize,)+ B> Lower<S, T, B, $a, $b, $o> for Via<{ Lowering::$via }>
+        where
+            S: EinsumSpec,
+            T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+            B: MatVec<T> + $($bound)*,
+            $a: EinsumTensor<T, B>,
+            $b: EinsumTensor<T, B>,
+            $($out_bound)*
+        {
+            #[inline]
+            fn lower($x: &$a, $y: &$b) -> $o {
+                #[allow(clippy::let_unit_value)]
+                let () = Check::<S, $a, $b, $o, T, B>::$valid;
+                $body
+            }
+        }
+    };
+}
+
+impl_lower!(MatVec<R, C> (Tensor2<T, R, C, B>, Tensor1<T, C, B>) -> Tensor1<T, R, B>
+    where [HasStorage<T, { R * C }> + HasStorage<T, C> + HasStorage<T, R>]
+    |a, x| a.matvec(x));
+impl_lower!(MatVec<N, R, C> (Tensor3<T, N, R, C, B>, Tensor2<T, N, C, B>) -> Tensor2<T, N, R, B>
+    where [HasStorage<T, { N * (R * C) }> + HasStorage<T, { N * C }> + HasStorage<T, { N * R }>]
+    |a, x| a.batch_matvec(x));
+impl_lower!(VecMat<R, C> (Tensor1<T, R, B>, Tensor2<T, R, C, B>) -> Tensor1<T, C, B>
+    where [HasStorage<T, R> + HasStorage<T, { R * C }> + HasStorage<T, C>]
+    |x, a| x.vecmat(a));
+impl_lower!(VecMat<N, R, C> (Tensor2<T, N, R, B>, Tensor3<T, N, R, C, B>) -> Tensor2<T, N, C, B>
+    where [HasStorage<T, { N * (R * C) }> + HasStorage<T, { N * C }> + HasStorage<T, { N * R }>]
+    |x, a| a.batch_vecmat(x));
+impl_lower!(Dot<N> (Tensor1<T, N, B>, Tensor1<T, N, B>) -> T
+    where [HasStorage<T, N> + HasStorage<T, 1>]
+    |x, y| x.dot(y));
+impl_lower!(Dot<N, C> (Tensor2<T, N, C, B>, Tensor2<T, N, C, B>) -> Tensor1<T, N, B>
+    where [HasStorage<T, { N * C }> + HasStorage<T, N>]
+    |x, y| {
+        // One dot per row; `Tensor3::batch_dot` would need a unit middle axis.
+        let mut out = <B as HasStorage<T, N>>::storage_uninit();
+        B::dot::<{ N * C }, N>(&x.storage, &y.storage, N, C, &mut out);
+        Tensor1 {
+            storage: out,
+            _p: PhantomData,
+        }
+    });
+impl_lower!(Outer<R, C> (Tensor1<T, R, B>, Tensor1<T, C, B>) -> Tensor2<T, R, C, B>
+    where [HasStorage<T, R> + HasStorage<T, C> + HasStorage<T, { R * C }>]
+    |x, y| x.outer(y));
+impl_lower!(Outer<N, R, C> (Tensor2<T, N, R, B>, Tensor2<T, N, C, B>) -> Tensor3<T, N, R, C, B>
+    where [HasStorage<T, { N * (R * C) }> + HasStorage<T, { N * C }> + HasStorage<T, { N * R }>]
+    |x, y| Tensor3::batch_outer(x, y));
+
+macro_rules! impl_einsum_tensor {
+    ($name:ident [$($d:ident),+]) => {
+        impl<T, $(const $d: usize,)+ B> EinsumTensor<T, B> for $name<T, $($d,)+ B>
+        where
+            T: Copy + Default,
+            B: HasStorage<T, { impl_einsum_tensor!(@prod $($d),+) }>,
+        {
+            const SHAPE: &'static [usize] = &[$($d),+];
+
+            #[inline]
+            fn elements(&self) -> &[T] {
+                <B as HasStorage<T, { impl_einsum_tensor!(@prod $($d),+) }>>::as_slice(
+                    &self.storage,
+                )
+            }
+
+            #[inline]
+            fn elements_mut(&mut self) -> &mut [T] {
+                <B as HasStorage<T, { impl_einsum_tensor!(@prod $($d),+) }>>::as_mut_slice(
+                    &mut self.storage,
+                )
+            }
+
+            #[inline]
+            fn uninit() -> Self {
+                let storage =
+                    <B as HasStorage<T, { impl_einsum_tensor!(@prod $($d),+) }>>::storage_uninit();
+                $name {
+                    storage,
+                    _p: PhantomData,
+                }
+            }
+        }
+    };
+
+    (@prod $first:expr $(,$rest:expr)+) => { $first * impl_einsum_tensor!(@prod $($rest),+) };
+    (@prod $only:expr) => { $only };
+}
+
+impl_einsum_tensor!(Tensor1 [N]);
+impl_einsum_tensor!(Tensor2 [R, C]);
+impl_einsum_tensor!(Tensor3 [D0, D1, D2]);
+impl_einsum_tensor!(Tensor4 [D0, D1, D2, D3]);
@@ -0,0 +1,142 @@
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::tensor_ops::einsum::{ContractionPlan, Einsum};
+use core::ops::{Add, Mul};
+
+impl<T> Einsum<T> for NaiveCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    fn contract(plan: &ContractionPlan, a: &[T], b: &[T], out: &mut [T]) {
+        let [sa, sb, _] = &plan.strides;
+        let (out_dims, sum_dims) = plan.dims.split_at(plan.out_rank);
+        let sum_len: usize = sum_dims.iter().product();
+
+        // `out` is row-major over the output indices, so its elements are
+        // visited in storage order.
+        let mut idx = vec![0; plan.dims.len()];
+        for o in out.iter_mut() {
+            let mut acc = T::default();
+            idx[plan.out_rank..].fill(0);
+            for _ in 0..sum_len {
+                let (mut ia, mut ib) = (0, 0);
+                for (l, &i) in idx.iter().enumerate() {
+                    ia += i * sa[l];
+                    ib += i * sb[l];
+                }
+                acc = acc + a[ia] * b[ib];
+                advance(&mut idx[plan.out_rank..], sum_dims);
+            }
+            *o = acc;
+            advance(&mut idx[..plan.out_rank], out_dims);
+        }
+    }
+}
+
+/// Steps the row-major multi-index `idx` over `dims`, wrapping to zero.
+fn advance(idx: &mut [usize], dims: &[usize]) {
+    for (i, &d) in idx.iter_mut().zip(dims).rev() {
+        *i += 1;
+        if *i < d {
+            return;
+        }
+        *i = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::einsum;
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::tensor_ops::einsum::{EinsumSpec, Lowering, classify, contract};
+    use crate::tensor::{Tensor1, Tensor2, Tensor3, Tensor4};
+
+    fn seq<const N: usize>(scale: f64) -> [f64; N] {
+        core::array::from_fn(|i| ((i * 7) % 11) as f64 * scale - 2.0)
+    }
+
+    /// The generic contraction loop, bypassing any lowering.
+    macro_rules! generic {
+        ($spec:literal, $a:expr, $b:expr) => {{
+            struct Spec;
+            impl EinsumSpec for Spec {
+                const SPEC: &'static str = $spec;
+            }
+            contract::<Spec, _, _, _, _, _>(&$a, &$b)
+        }};
+    }
+
+    #[test]
+    fn test_specs_lower_by_structure() {
+        use Lowering::*;
+        let specs = [
+            "ij,jk->ik",
+            "ik,kj->ij",
+            "bqd,bdk->bqk",
+            "nxy,yz->nxz",
+            "abij,abjk->abik",
+            "rc,c->r",
+            "bi,bij->bj",
+            "x,x->",
+            "p,q->pq",
+            "bi,bj->bij",
+            "hqd,hkd->hqk",
+            "ij,jk->ki",
+            "jj,j->j",
+            "ij,bjk->bik",
+            "abcij,abcjk->abcik",
+        ];
+        let want = [
+            MatMul, MatMul, MatMul, MatMul, MatMul, MatVec, VecMat, Dot, Outer, Outer, Generic,
+            Generic, Generic, Generic, Generic,
+        ];
+        assert_eq!(specs.map(classify), want);
+    }
+
+    #[test]
+    fn test_lowered_specs_match_generic_loop() {
+        let a = Tensor3::<f64, 2, 3, 4, NaiveCpu>::new(seq(0.5));
+        let b = Tensor3::<f64, 2, 4, 5, NaiveCpu>::new(seq(0.25));
+        let m = Tensor2::<f64, 4, 5, NaiveCpu>::new(seq(1.0));
+        let n = Tensor2::<f64, 3, 4, NaiveCpu>::new(seq(0.75));
+
+        let generic: Tensor3<f64, 2, 3, 5, NaiveCpu> = generic!("bqd,bdk->bqk", a, b);
+        assert_eq!(einsum!("bqd,bdk->bqk", a, b).as_slice(), generic.as_slice());
+
+        let generic: Tensor3<f64, 2, 3, 5, NaiveCpu> = generic!("bxy,yz->bxz", a, m);
+        assert_eq!(einsum!("bxy,yz->bxz", a, m).as_slice(), generic.as_slice());
+
+        let generic: Tensor2<f64, 3, 5, NaiveCpu> = generic!("ik,kj->ij", n, m);
+        assert_eq!(einsum!("ik,kj->ij", n, m).as_slice(), generic.as_slice());
+
+        let x = Tensor1::<f64, 4, NaiveCpu>::new(seq(1.5));
+        let y = Tensor1::<f64, 4, NaiveCpu>::new(seq(0.5));
+        assert_eq!(einsum!("x,x->", x, y), x.dot(&y));
+        let generic: Tensor1<f64, 3, NaiveCpu> = generic!("rc,c->r", n, x);
+        assert_eq!(einsum!("rc,c->r", n, x).as_slice(), generic.as_slice());
+        let generic: Tensor2<f64, 4, 4, NaiveCpu> = generic!("p,q->pq", x, y);
+        assert_eq!(einsum!("p,q->pq", x, y).as_slice(), generic.as_slice());
+    }
+
+    #[test]
+    fn test_generic_contractions() {
+        // Attention scores against untransposed keys:
+        // out[h, q, k] = sum_d q[h, q, d] * k[h, k, d].
+        let q = Tensor3::<f64, 1, 2, 3, NaiveCpu>::new([1.0, 0.0, 2.0, 0.0, 1.0, -1.0]);
+        let k = Tensor3::<f64, 1, 2, 3, NaiveCpu>::new([1.0, 1.0, 1.0, 2.0, 0.0, 1.0]);
+        let scores: Tensor3<f64, 1, 2, 2, NaiveCpu> = einsum!("hqd,hkd->hqk", q, k);
+        assert_eq!(scores.as_slice(), &[3.0, 4.0, 0.0, -1.0]);
+
+        // A repeated index reads the diagonal: out[j] = a[j, j] * b[j].
+        let a = Tensor2::<f64, 2, 2, NaiveCpu>::new([1.0, 2.0, 3.0, 4.0]);
+        let b = Tensor1::<f64, 2, NaiveCpu>::new([10.0, 100.0]);
+        let diag: Tensor1<f64, 2, NaiveCpu> = einsum!("jj,j->j", a, b);
+        assert_eq!(diag.as_slice(), &[10.0, 400.0]);
+
+        // Permuted output with summed batches:
+        // out[k, i] = sum_x sum_b sum_j a[x, b, i, j] * c[b, j, k].
+        let a = Tensor4::<f64, 1, 2, 1, 2, NaiveCpu>::new([1.0, 2.0, 3.0, 4.0]);
+        let c = Tensor3::<f64, 2, 2, 1, NaiveCpu>::new([1.0, 1.0, 2.0, 0.0]);
+        let out: Tensor2<f64, 1, 1, NaiveCpu> = einsum!("xbij,bjk->ki", a, c);
+        assert_eq!(out.as_slice(), &[3.0 + 6.0]);
+    }
+}
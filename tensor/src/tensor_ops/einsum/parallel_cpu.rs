@@ -0,0 +1,14 @@
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::ParallelCpu;
+use crate::tensor_ops::einsum::{ContractionPlan, Einsum};
+use core::ops::{Add, Mul};
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> Einsum<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    fn contract(plan: &ContractionPlan, a: &[T], b: &[T], out: &mut [T]) {
+        <NaiveCpu as Einsum<T>>::contract(plan, a, b, out)
+    }
+}
@@ -1,69 +1,177 @@
-//! Element-wise tensor operations between two tensors of the same size.
+//! Element-wise tensor operations between two tensors of the same shape.
 //!
-//! Backend implementers should implement [`ElemAdd`], [`ElemSub`], and
-//! [`ElemMul`] for their backend.
+//! `+`, `-` and `/` between two tensors of the same shape return `Self`.
+//! Tensors of different shape broadcast through
+//! [`broadcast_const_ops`](crate::tensor_ops::broadcast_const_ops).
+//!
+//! `+=`, `-=` and `/=` update the left operand in place. There is no tensor
+//! `*=`: `a *= b` would read as `a = a * b`, and `*` between same-rank tensors
+//! is a matrix product, so an elementwise `*=` would silently disagree with it.
+//! Use the in-place `elem_mul_` instead.
+//!
+//! ```
+//! # #![feature(generic_const_exprs)]
+//! # #![allow(incomplete_features)]
+//! use tensor::storage::naive_cpu::NaiveCpu;
+//! use tensor::tensor::Tensor3;
+//!
+//! let mut x = Tensor3::<f32, 2, 3, 3, NaiveCpu>::ones();
+//! let y = Tensor3::<f32, 2, 3, 3, NaiveCpu>::ones();
+//! x += y;
+//! x.elem_mul_(&y);
+//! ```
+//!
+//! ```compile_fail
+//! # #![feature(generic_const_exprs)]
+//! # #![allow(incomplete_features)]
+//! use tensor::storage::naive_cpu::NaiveCpu;
+//! use tensor::tensor::Tensor3;
+//!
+//! let mut x = Tensor3::<f32, 2, 3, 3, NaiveCpu>::ones();
+//! let y = Tensor3::<f32, 2, 3, 3, NaiveCpu>::ones();
+//! x *= y;
+//! ```
+//!
+//! Backend implementers should implement [`ElemAdd`], [`ElemSub`], [`ElemMul`],
+//! [`ElemDiv`], [`ElemMax`] and [`ElemMin`] for their backend.
 
 pub mod naive_cpu;
+pub mod parallel_cpu;
 
 use crate::storage::HasStorage;
-use crate::tensor::Tensor2;
-use core::ops::{Add, Div, Mul, Sub};
+use crate::tensor::{Tensor2, Tensor3, Tensor4};
+use core::cmp::PartialOrd;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, Sub, SubAssign};
 
 /// Trait for backends that support element-wise tensor addition.
+///
+/// Backs `+` between two tensors of the same shape, on every rank.
 pub trait ElemAdd<T: Copy + Default>: Sized {
-    fn elem_add<const R: usize, const C: usize>(
-        a: &<Self as HasStorage<T, { R * C }>>::Storage,
-        b: &<Self as HasStorage<T, { R * C }>>::Storage,
-        out: &mut <Self as HasStorage<T, { R * C }>>::Storage,
+    fn elem_add<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        T: Add<Output = T>,
+        Self: HasStorage<T, N>;
+
+    /// In-place variant: `a = a + b`.
+    fn elem_add_assign<const N: usize>(
+        a: &mut <Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
     ) where
         T: Add<Output = T>,
-        Self: HasStorage<T, { R * C }>;
+        Self: HasStorage<T, N>;
 }
 
 /// Trait for backends that support element-wise tensor subtraction.
+///
+/// Backs `-` between two tensors of the same shape, on every rank.
 pub trait ElemSub<T: Copy + Default>: Sized {
-    fn elem_sub<const R: usize, const C: usize>(
-        a: &<Self as HasStorage<T, { R * C }>>::Storage,
-        b: &<Self as HasStorage<T, { R * C }>>::Storage,
-        out: &mut <Self as HasStorage<T, { R * C }>>::Storage,
+    fn elem_sub<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
     ) where
         T: Sub<Output = T>,
-        Self: HasStorage<T, { R * C }>;
+        Self: HasStorage<T, N>;
+
+    /// In-place variant: `a = a - b`.
+    fn elem_sub_assign<const N: usize>(
+        a: &mut <Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+    ) where
+        T: Sub<Output = T>,
+        Self: HasStorage<T, N>;
 }
 
 /// Trait for backends that support element-wise tensor multiplication.
 pub trait ElemMul<T: Copy + Default>: Sized {
-    fn elem_mul<const R: usize, const C: usize>(
-        a: &<Self as HasStorage<T, { R * C }>>::Storage,
-        b: &<Self as HasStorage<T, { R * C }>>::Storage,
-        out: &mut <Self as HasStorage<T, { R * C }>>::Storage,
+    fn elem_mul<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        T: Mul<Output = T>,
+        Self: HasStorage<T, N>;
+
+    /// In-place variant: `a = a * b`.
+    fn elem_mul_assign<const N: usize>(
+        a: &mut <Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
     ) where
         T: Mul<Output = T>,
-        Self: HasStorage<T, { R * C }>;
+        Self: HasStorage<T, N>;
 }
 
 /// Trait for backends that support element-wise tensor division.
+///
+/// Backs `/` between two tensors of the same shape, on every rank.
 pub trait ElemDiv<T: Copy + Default>: Sized {
-    fn elem_div<const R: usize, const C: usize>(
-        a: &<Self as HasStorage<T, { R * C }>>::Storage,
-        b: &<Self as HasStorage<T, { R * C }>>::Storage,
-        out: &mut <Self as HasStorage<T, { R * C }>>::Storage,
+    fn elem_div<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        T: Div<Output = T>,
+        Self: HasStorage<T, N>;
+
+    /// In-place variant: `a = a / b`.
+    fn elem_div_assign<const N: usize>(
+        a: &mut <Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
     ) where
         T: Div<Output = T>,
-        Self: HasStorage<T, { R * C }>;
+        Self: HasStorage<T, N>;
 }
 
-impl<T, const R: usize, const C: usize, B> Add<Tensor2<T, R, C, B>> for Tensor2<T, R, C, B>
-where
-    T: Copy + Default + Add<Output = T>,
-    B: ElemAdd<T> + HasStorage<T, { R * C }>,
-{
-    type Output = Tensor2<T, R, C, B>;
+/// Trait for backends that support the element-wise maximum of two tensors.
+///
+/// NaN in either operand gives NaN.
+pub trait ElemMax<T: Copy + Default + PartialOrd>: Sized {
+    fn elem_max<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+}
 
-    #[inline]
-    fn add(self, rhs: Tensor2<T, R, C, B>) -> Self::Output {
+/// Trait for backends that support the element-wise minimum of two tensors.
+///
+/// NaN in either operand gives NaN.
+pub trait ElemMin<T: Copy + Default + PartialOrd>: Sized {
+    fn elem_min<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+}
+
+/// Element-wise maximum against another tensor of the same shape or a scalar.
+pub trait Maximum<Rhs> {
+    type Output;
+    fn maximum(self, rhs: Rhs) -> Self::Output;
+}
+
+/// Element-wise minimum against another tensor of the same shape or a scalar.
+pub trait Minimum<Rhs> {
+    type Output;
+    fn minimum(self, rhs: Rhs) -> Self::Output;
+}
+
+impl_binop! {
+    impl<[T, const R: usize, const C: usize, B]>
+    Add::add(Tensor2<T, R, C, B>, Tensor2<T, R, C, B>)
+        -> Tensor2<T, R, C, B>
+    where {
+        T: Copy + Default + Add<Output = T>,
+        B: ElemAdd<T> + HasStorage<T, { R * C }>,
+    }
+    |a, b| {
         let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
-        B::elem_add::<R, C>(&self.storage, &rhs.storage, &mut out);
+        B::elem_add::<{ R * C }>(&a.storage, &b.storage, &mut out);
         Tensor2 {
             storage: out,
             _p: core::marker::PhantomData,
@@ -71,17 +179,35 @@ where
     }
 }
 
-impl<T, const R: usize, const C: usize, B> Sub<Tensor2<T, R, C, B>> for Tensor2<T, R, C, B>
-where
-    T: Copy + Default + Sub<Output = T>,
-    B: ElemSub<T> + HasStorage<T, { R * C }>,
-{
-    type Output = Tensor2<T, R, C, B>;
+impl_binop! {
+    impl<[T, const R: usize, const C: usize, B]>
+    Sub::sub(Tensor2<T, R, C, B>, Tensor2<T, R, C, B>)
+        -> Tensor2<T, R, C, B>
+    where {
+        T: Copy + Default + Sub<Output = T>,
+        B: ElemSub<T> + HasStorage<T, { R * C }>,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::elem_sub::<{ R * C }>(&a.storage, &b.storage, &mut out);
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
 
-    #[inline]
-    fn sub(self, rhs: Tensor2<T, R, C, B>) -> Self::Output {
+impl_binop! {
+    impl<[T, const R: usize, const C: usize, B]>
+    Div::div(Tensor2<T, R, C, B>, Tensor2<T, R, C, B>)
+        -> Tensor2<T, R, C, B>
+    where {
+        T: Copy + Default + Div<Output = T>,
+        B: ElemDiv<T> + HasStorage<T, { R * C }>,
+    }
+    |a, b| {
         let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
-        B::elem_sub::<R, C>(&self.storage, &rhs.storage, &mut out);
+        B::elem_div::<{ R * C }>(&a.storage, &b.storage, &mut out);
         Tensor2 {
             storage: out,
             _p: core::marker::PhantomData,
@@ -97,12 +223,18 @@ where
     #[inline]
     pub fn elem_mul(self, rhs: Tensor2<T, R, C, B>) -> Self {
         let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
-        B::elem_mul::<R, C>(&self.storage, &rhs.storage, &mut out);
+        B::elem_mul::<{ R * C }>(&self.storage, &rhs.storage, &mut out);
         Self {
             storage: out,
             _p: core::marker::PhantomData,
         }
     }
+
+    /// In-place variant of [`Self::elem_mul`].
+    #[inline]
+    pub fn elem_mul_(&mut self, rhs: &Tensor2<T, R, C, B>) {
+        B::elem_mul_assign::<{ R * C }>(&mut self.storage, &rhs.storage);
+    }
 }
 
 impl<T, const R: usize, const C: usize, B> Tensor2<T, R, C, B>
@@ -113,10 +245,432 @@ where
     #[inline]
     pub fn elem_div(self, rhs: Tensor2<T, R, C, B>) -> Self {
         let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
-        B::elem_div::<R, C>(&self.storage, &rhs.storage, &mut out);
+        B::elem_div::<{ R * C }>(&self.storage, &rhs.storage, &mut out);
+        Self {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    Add::add(Tensor3<T, D0, D1, D2, B>, Tensor3<T, D0, D1, D2, B>)
+        -> Tensor3<T, D0, D1, D2, B>
+    where {
+        T: Copy + Default + Add<Output = T>,
+        B: ElemAdd<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::elem_add::<{ D0 * (D1 * D2) }>(&a.storage, &b.storage, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    Sub::sub(Tensor3<T, D0, D1, D2, B>, Tensor3<T, D0, D1, D2, B>)
+        -> Tensor3<T, D0, D1, D2, B>
+    where {
+        T: Copy + Default + Sub<Output = T>,
+        B: ElemSub<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::elem_sub::<{ D0 * (D1 * D2) }>(&a.storage, &b.storage, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    Div::div(Tensor3<T, D0, D1, D2, B>, Tensor3<T, D0, D1, D2, B>)
+        -> Tensor3<T, D0, D1, D2, B>
+    where {
+        T: Copy + Default + Div<Output = T>,
+        B: ElemDiv<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::elem_div::<{ D0 * (D1 * D2) }>(&a.storage, &b.storage, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
+where
+    T: Copy + Default + Mul<Output = T>,
+    B: ElemMul<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+    [(); D0 * (D1 * D2)]:,
+{
+    #[inline]
+    pub fn elem_mul(self, rhs: Tensor3<T, D0, D1, D2, B>) -> Self {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::elem_mul::<{ D0 * (D1 * D2) }>(&self.storage, &rhs.storage, &mut out);
+        Self {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// In-place variant of [`Self::elem_mul`].
+    #[inline]
+    pub fn elem_mul_(&mut self, rhs: &Tensor3<T, D0, D1, D2, B>) {
+        B::elem_mul_assign::<{ D0 * (D1 * D2) }>(&mut self.storage, &rhs.storage);
+    }
+}
+
+impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
+where
+    T: Copy + Default + Div<Output = T>,
+    B: ElemDiv<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+    [(); D0 * (D1 * D2)]:,
+{
+    #[inline]
+    pub fn elem_div(self, rhs: Tensor3<T, D0, D1, D2, B>) -> Self {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::elem_div::<{ D0 * (D1 * D2) }>(&self.storage, &rhs.storage, &mut out);
+        Self {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Add::add(Tensor4<T, D0, D1, D3, D4, B>, Tensor4<T, D0, D1, D3, D4, B>)
+        -> Tensor4<T, D0, D1, D3, D4, B>
+    where {
+        T: Copy + Default + Add<Output = T>,
+        B: ElemAdd<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::elem_add::<{ D0 * (D1 * (D3 * D4)) }>(&a.storage, &b.storage, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Sub::sub(Tensor4<T, D0, D1, D3, D4, B>, Tensor4<T, D0, D1, D3, D4, B>)
+        -> Tensor4<T, D0, D1, D3, D4, B>
+    where {
+        T: Copy + Default + Sub<Output = T>,
+        B: ElemSub<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::elem_sub::<{ D0 * (D1 * (D3 * D4)) }>(&a.storage, &b.storage, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Div::div(Tensor4<T, D0, D1, D3, D4, B>, Tensor4<T, D0, D1, D3, D4, B>)
+        -> Tensor4<T, D0, D1, D3, D4, B>
+    where {
+        T: Copy + Default + Div<Output = T>,
+        B: ElemDiv<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::elem_div::<{ D0 * (D1 * (D3 * D4)) }>(&a.storage, &b.storage, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
+    Tensor4<T, D0, D1, D3, D4, B>
+where
+    T: Copy + Default + Mul<Output = T>,
+    B: ElemMul<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+    [(); D0 * (D1 * (D3 * D4))]:,
+{
+    #[inline]
+    pub fn elem_mul(self, rhs: Tensor4<T, D0, D1, D3, D4, B>) -> Self {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::elem_mul::<{ D0 * (D1 * (D3 * D4)) }>(&self.storage, &rhs.storage, &mut out);
+        Self {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// In-place variant of [`Self::elem_mul`].
+    #[inline]
+    pub fn elem_mul_(&mut self, rhs: &Tensor4<T, D0, D1, D3, D4, B>) {
+        B::elem_mul_assign::<{ D0 * (D1 * (D3 * D4)) }>(&mut self.storage, &rhs.storage);
+    }
+}
+
+impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
+    Tensor4<T, D0, D1, D3, D4, B>
+where
+    T: Copy + Default + Div<Output = T>,
+    B: ElemDiv<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+    [(); D0 * (D1 * (D3 * D4))]:,
+{
+    #[inline]
+    pub fn elem_div(self, rhs: Tensor4<T, D0, D1, D3, D4, B>) -> Self {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::elem_div::<{ D0 * (D1 * (D3 * D4)) }>(&self.storage, &rhs.storage, &mut out);
         Self {
             storage: out,
             _p: core::marker::PhantomData,
         }
     }
 }
+
+impl_assign_op! {
+    impl<[T, const R: usize, const C: usize, B]>
+    AddAssign::add_assign(Tensor2<T, R, C, B>, Tensor2<T, R, C, B>)
+    where {
+        T: Copy + Default + Add<Output = T>,
+        B: ElemAdd<T> + HasStorage<T, { R * C }>,
+    }
+    |a, b| {
+        B::elem_add_assign::<{ R * C }>(&mut a.storage, &b.storage);
+    }
+}
+
+impl_assign_op! {
+    impl<[T, const R: usize, const C: usize, B]>
+    SubAssign::sub_assign(Tensor2<T, R, C, B>, Tensor2<T, R, C, B>)
+    where {
+        T: Copy + Default + Sub<Output = T>,
+        B: ElemSub<T> + HasStorage<T, { R * C }>,
+    }
+    |a, b| {
+        B::elem_sub_assign::<{ R * C }>(&mut a.storage, &b.storage);
+    }
+}
+
+impl_assign_op! {
+    impl<[T, const R: usize, const C: usize, B]>
+    DivAssign::div_assign(Tensor2<T, R, C, B>, Tensor2<T, R, C, B>)
+    where {
+        T: Copy + Default + Div<Output = T>,
+        B: ElemDiv<T> + HasStorage<T, { R * C }>,
+    }
+    |a, b| {
+        B::elem_div_assign::<{ R * C }>(&mut a.storage, &b.storage);
+    }
+}
+
+impl_assign_op! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    AddAssign::add_assign(Tensor3<T, D0, D1, D2, B>, Tensor3<T, D0, D1, D2, B>)
+    where {
+        T: Copy + Default + Add<Output = T>,
+        B: ElemAdd<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, b| {
+        B::elem_add_assign::<{ D0 * (D1 * D2) }>(&mut a.storage, &b.storage);
+    }
+}
+
+impl_assign_op! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    SubAssign::sub_assign(Tensor3<T, D0, D1, D2, B>, Tensor3<T, D0, D1, D2, B>)
+    where {
+        T: Copy + Default + Sub<Output = T>,
+        B: ElemSub<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, b| {
+        B::elem_sub_assign::<{ D0 * (D1 * D2) }>(&mut a.storage, &b.storage);
+    }
+}
+
+impl_assign_op! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    DivAssign::div_assign(Tensor3<T, D0, D1, D2, B>, Tensor3<T, D0, D1, D2, B>)
+    where {
+        T: Copy + Default + Div<Output = T>,
+        B: ElemDiv<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, b| {
+        B::elem_div_assign::<{ D0 * (D1 * D2) }>(&mut a.storage, &b.storage);
+    }
+}
+
+impl_assign_op! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    AddAssign::add_assign(Tensor4<T, D0, D1, D3, D4, B>, Tensor4<T, D0, D1, D3, D4, B>)
+    where {
+        T: Copy + Default + Add<Output = T>,
+        B: ElemAdd<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, b| {
+        B::elem_add_assign::<{ D0 * (D1 * (D3 * D4)) }>(&mut a.storage, &b.storage);
+    }
+}
+
+impl_assign_op! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    SubAssign::sub_assign(Tensor4<T, D0, D1, D3, D4, B>, Tensor4<T, D0, D1, D3, D4, B>)
+    where {
+        T: Copy + Default + Sub<Output = T>,
+        B: ElemSub<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, b| {
+        B::elem_sub_assign::<{ D0 * (D1 * (D3 * D4)) }>(&mut a.storage, &b.storage);
+    }
+}
+
+impl_assign_op! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    DivAssign::div_assign(Tensor4<T, D0, D1, D3, D4, B>, Tensor4<T, D0, D1, D3, D4, B>)
+    where {
+        T: Copy + Default + Div<Output = T>,
+        B: ElemDiv<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, b| {
+        B::elem_div_assign::<{ D0 * (D1 * (D3 * D4)) }>(&mut a.storage, &b.storage);
+    }
+}
+
+impl_binop! {
+    impl<[T, const R: usize, const C: usize, B]>
+    Maximum::maximum(Tensor2<T, R, C, B>, Tensor2<T, R, C, B>)
+        -> Tensor2<T, R, C, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ElemMax<T> + HasStorage<T, { R * C }>,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::elem_max::<{ R * C }>(&a.storage, &b.storage, &mut out);
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const R: usize, const C: usize, B]>
+    Minimum::minimum(Tensor2<T, R, C, B>, Tensor2<T, R, C, B>)
+        -> Tensor2<T, R, C, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ElemMin<T> + HasStorage<T, { R * C }>,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::elem_min::<{ R * C }>(&a.storage, &b.storage, &mut out);
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    Maximum::maximum(Tensor3<T, D0, D1, D2, B>, Tensor3<T, D0, D1, D2, B>)
+        -> Tensor3<T, D0, D1, D2, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ElemMax<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::elem_max::<{ D0 * (D1 * D2) }>(&a.storage, &b.storage, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]>
+    Minimum::minimum(Tensor3<T, D0, D1, D2, B>, Tensor3<T, D0, D1, D2, B>)
+        -> Tensor3<T, D0, D1, D2, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ElemMin<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::elem_min::<{ D0 * (D1 * D2) }>(&a.storage, &b.storage, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Maximum::maximum(Tensor4<T, D0, D1, D3, D4, B>, Tensor4<T, D0, D1, D3, D4, B>)
+        -> Tensor4<T, D0, D1, D3, D4, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ElemMax<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::elem_max::<{ D0 * (D1 * (D3 * D4)) }>(&a.storage, &b.storage, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl_binop! {
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Minimum::minimum(Tensor4<T, D0, D1, D3, D4, B>, Tensor4<T, D0, D1, D3, D4, B>)
+        -> Tensor4<T, D0, D1, D3, D4, B>
+    where {
+        T: Copy + Default + PartialOrd,
+        B: ElemMin<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+    |a, b| {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::elem_min::<{ D0 * (D1 * (D3 * D4)) }>(&a.storage, &b.storage, &mut out);
+        Tensor4 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
@@ -1,84 +1,258 @@
 use crate::storage::HasStorage;
 use crate::storage::naive_cpu::NaiveCpu;
-use crate::tensor_ops::elemwise::{ElemAdd, ElemDiv, ElemMul, ElemSub};
+use crate::tensor_ops::elemwise::{ElemAdd, ElemDiv, ElemMax, ElemMin, ElemMul, ElemSub};
+use crate::tensor_ops::{nan_max, nan_min};
+use core::cmp::PartialOrd;
 use core::ops::{Add, Div, Mul, Sub};
 
 impl<T> ElemAdd<T> for NaiveCpu
 where
     T: Copy + Default + Add<Output = T>,
 {
-    fn elem_add<const R: usize, const C: usize>(
-        a: &<Self as HasStorage<T, { R * C }>>::Storage,
-        b: &<Self as HasStorage<T, { R * C }>>::Storage,
-        out: &mut <Self as HasStorage<T, { R * C }>>::Storage,
+    fn elem_add<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
     ) where
-        Self: HasStorage<T, { R * C }>,
+        Self: HasStorage<T, N>,
     {
-        let a = <Self as HasStorage<T, { R * C }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { R * C }>>::as_slice(b);
-        let dst = <Self as HasStorage<T, { R * C }>>::as_mut_slice(out);
-        for i in 0..(R * C) {
+        let a = <Self as HasStorage<T, N>>::as_slice(a);
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for i in 0..N {
             dst[i] = a[i] + b[i];
         }
     }
+
+    fn elem_add_assign<const N: usize>(
+        a: &mut <Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(a);
+        for i in 0..N {
+            dst[i] = dst[i] + b[i];
+        }
+    }
 }
 
 impl<T> ElemSub<T> for NaiveCpu
 where
     T: Copy + Default + Sub<Output = T>,
 {
-    fn elem_sub<const R: usize, const C: usize>(
-        a: &<Self as HasStorage<T, { R * C }>>::Storage,
-        b: &<Self as HasStorage<T, { R * C }>>::Storage,
-        out: &mut <Self as HasStorage<T, { R * C }>>::Storage,
+    fn elem_sub<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
     ) where
-        Self: HasStorage<T, { R * C }>,
+        Self: HasStorage<T, N>,
     {
-        let a = <Self as HasStorage<T, { R * C }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { R * C }>>::as_slice(b);
-        let dst = <Self as HasStorage<T, { R * C }>>::as_mut_slice(out);
-        for i in 0..(R * C) {
+        let a = <Self as HasStorage<T, N>>::as_slice(a);
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for i in 0..N {
             dst[i] = a[i] - b[i];
         }
     }
+
+    fn elem_sub_assign<const N: usize>(
+        a: &mut <Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(a);
+        for i in 0..N {
+            dst[i] = dst[i] - b[i];
+        }
+    }
 }
 
 impl<T> ElemMul<T> for NaiveCpu
 where
     T: Copy + Default + Mul<Output = T>,
 {
-    fn elem_mul<const R: usize, const C: usize>(
-        a: &<Self as HasStorage<T, { R * C }>>::Storage,
-        b: &<Self as HasStorage<T, { R * C }>>::Storage,
-        out: &mut <Self as HasStorage<T, { R * C }>>::Storage,
+    fn elem_mul<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
     ) where
-        Self: HasStorage<T, { R * C }>,
+        Self: HasStorage<T, N>,
     {
-        let a = <Self as HasStorage<T, { R * C }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { R * C }>>::as_slice(b);
-        let dst = <Self as HasStorage<T, { R * C }>>::as_mut_slice(out);
-        for i in 0..(R * C) {
+        let a = <Self as HasStorage<T, N>>::as_slice(a);
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for i in 0..N {
             dst[i] = a[i] * b[i];
         }
     }
+
+    fn elem_mul_assign<const N: usize>(
+        a: &mut <Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(a);
+        for i in 0..N {
+            dst[i] = dst[i] * b[i];
+        }
+    }
 }
 
 impl<T> ElemDiv<T> for NaiveCpu
 where
     T: Copy + Default + Div<Output = T>,
 {
-    fn elem_div<const R: usize, const C: usize>(
-        a: &<Self as HasStorage<T, { R * C }>>::Storage,
-        b: &<Self as HasStorage<T, { R * C }>>::Storage,
-        out: &mut <Self as HasStorage<T, { R * C }>>::Storage,
+    fn elem_div<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
     ) where
-        Self: HasStorage<T, { R * C }>,
+        Self: HasStorage<T, N>,
     {
-        let a = <Self as HasStorage<T, { R * C }>>::as_slice(a);
-        let b = <Self as HasStorage<T, { R * C }>>::as_slice(b);
-        let dst = <Self as HasStorage<T, { R * C }>>::as_mut_slice(out);
-        for i in 0..(R * C) {
+        let a = <Self as HasStorage<T, N>>::as_slice(a);
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for i in 0..N {
             dst[i] = a[i] / b[i];
         }
     }
+
+    fn elem_div_assign<const N: usize>(
+        a: &mut <Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(a);
+        for i in 0..N {
+            dst[i] = dst[i] / b[i];
+        }
+    }
+}
+
+impl<T> ElemMax<T> for NaiveCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn elem_max<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let a = <Self as HasStorage<T, N>>::as_slice(a);
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for i in 0..N {
+            dst[i] = nan_max(a[i], b[i]);
+        }
+    }
+}
+
+impl<T> ElemMin<T> for NaiveCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn elem_min<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let a = <Self as HasStorage<T, N>>::as_slice(a);
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for i in 0..N {
+            dst[i] = nan_min(a[i], b[i]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Tensor3, Tensor4};
+    use crate::tensor_ops::elemwise::{Maximum, Minimum};
+
+    #[test]
+    fn test_elemwise_tensor3() {
+        let a = Tensor3::<i32, 1, 2, 2, NaiveCpu>::new([1, 2, 3, 4]);
+        let b = Tensor3::<i32, 1, 2, 2, NaiveCpu>::new([4, 3, 2, 1]);
+        assert_eq!((a + b).as_slice(), &[5, 5, 5, 5]);
+        assert_eq!((a - b).as_slice(), &[-3, -1, 1, 3]);
+        assert_eq!(a.elem_mul(b).as_slice(), &[4, 6, 6, 4]);
+        assert_eq!(a.elem_div(b).as_slice(), &[0, 0, 1, 4]);
+    }
+
+    #[test]
+    fn test_residual_add_tensor4() {
+        let x = Tensor4::<f32, 1, 2, 1, 2, NaiveCpu>::new([1.0, -2.0, 3.0, -4.0]);
+        let fx = Tensor4::<f32, 1, 2, 1, 2, NaiveCpu>::new([0.5, 0.5, 0.5, 0.5]);
+        assert_eq!((x + fx).as_slice(), &[1.5, -1.5, 3.5, -3.5]);
+        assert_eq!((x - fx).as_slice(), &[0.5, -2.5, 2.5, -4.5]);
+        assert_eq!(x.elem_mul(fx).as_slice(), &[0.5, -1.0, 1.5, -2.0]);
+        assert_eq!(x.elem_div(fx).as_slice(), &[2.0, -4.0, 6.0, -8.0]);
+    }
+
+    /// Same-shape operators need only the bounds of that one shape.
+    fn residual<T, const D0: usize, const D1: usize, const D2: usize, B>(
+        x: Tensor3<T, D0, D1, D2, B>,
+        fx: Tensor3<T, D0, D1, D2, B>,
+    ) -> Tensor3<T, D0, D1, D2, B>
+    where
+        T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+        B: ElemAdd<T> + ElemSub<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    {
+        (&x + &fx) - &x + x
+    }
+
+    #[test]
+    fn test_same_shape_ops_in_generic_code() {
+        let x = Tensor3::<i32, 1, 1, 3, NaiveCpu>::new([1, 2, 3]);
+        let fx = Tensor3::<i32, 1, 1, 3, NaiveCpu>::new([10, 20, 30]);
+        assert_eq!(residual(x, fx).as_slice(), &[11, 22, 33]);
+    }
+
+    #[test]
+    fn test_tensor_assign_ops() {
+        let mut w = Tensor3::<f32, 1, 1, 3, NaiveCpu>::new([1.0, 2.0, 3.0]);
+        let g = Tensor3::<f32, 1, 1, 3, NaiveCpu>::new([0.5, 0.5, 2.0]);
+        w -= g;
+        assert_eq!(w.as_slice(), &[0.5, 1.5, 1.0]);
+        w += g;
+        assert_eq!(w.as_slice(), &[1.0, 2.0, 3.0]);
+        w.elem_mul_(&g);
+        assert_eq!(w.as_slice(), &[0.5, 1.0, 6.0]);
+        w /= g;
+        assert_eq!(w.as_slice(), &[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_maximum_minimum() {
+        let a = Tensor3::<i32, 1, 2, 2, NaiveCpu>::new([1, 5, -3, 4]);
+        let b = Tensor3::<i32, 1, 2, 2, NaiveCpu>::new([2, 2, -4, 4]);
+        assert_eq!(a.maximum(b).as_slice(), &[2, 5, -3, 4]);
+        assert_eq!(a.minimum(&b).as_slice(), &[1, 2, -4, 4]);
+    }
+
+    #[test]
+    fn test_maximum_minimum_propagate_nan() {
+        let a = Tensor3::<f64, 1, 1, 3, NaiveCpu>::new([f64::NAN, 1.0, 2.0]);
+        let b = Tensor3::<f64, 1, 1, 3, NaiveCpu>::new([0.0, f64::NAN, 1.0]);
+        for out in [a.maximum(b), a.minimum(b), b.maximum(a), b.minimum(a)] {
+            assert!(out.as_slice()[0].is_nan() && out.as_slice()[1].is_nan());
+        }
+        assert_eq!(a.maximum(b).as_slice()[2], 2.0);
+        assert_eq!(a.minimum(b).as_slice()[2], 1.0);
+    }
 }
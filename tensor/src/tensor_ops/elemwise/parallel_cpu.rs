@@ -0,0 +1,131 @@
+use crate::storage::HasStorage;
+use crate::storage::parallel_cpu::{ParallelCpu, for_each_chunk};
+use crate::tensor_ops::elemwise::{ElemAdd, ElemDiv, ElemMax, ElemMin, ElemMul, ElemSub};
+use crate::tensor_ops::{nan_max, nan_min};
+use core::cmp::PartialOrd;
+use core::ops::{Add, Div, Mul, Sub};
+
+/// Fewest elements worth giving a thread of their own.
+const MIN_ELEMS_PER_THREAD: usize = 1 << 14;
+
+/// `out[i] = f(a[i], b[i])`, in chunks across threads.
+fn zip_map<T, F>(a: &[T], b: &[T], out: &mut [T], f: F)
+where
+    T: Copy + Send + Sync,
+    F: Fn(T, T) -> T + Sync,
+{
+    for_each_chunk(out, 1, MIN_ELEMS_PER_THREAD, |start, chunk| {
+        for (i, o) in chunk.iter_mut().enumerate() {
+            *o = f(a[start + i], b[start + i]);
+        }
+    });
+}
+
+/// `a[i] = f(a[i], b[i])`, in chunks across threads.
+fn zip_map_inplace<T, F>(a: &mut [T], b: &[T], f: F)
+where
+    T: Copy + Send + Sync,
+    F: Fn(T, T) -> T + Sync,
+{
+    for_each_chunk(a, 1, MIN_ELEMS_PER_THREAD, |start, chunk| {
+        for (i, o) in chunk.iter_mut().enumerate() {
+            *o = f(*o, b[start + i]);
+        }
+    });
+}
+
+macro_rules! impl_parallel_elemwise {
+    ($tr:ident, $bound:ident, $op:ident, $assign:ident, |$x:ident, $y:ident| $body:expr) => {
+        impl<T> $tr<T> for ParallelCpu
+        where
+            T: Copy + Default + $bound<Output = T> + Send + Sync,
+        {
+            fn $op<const N: usize>(
+                a: &<Self as HasStorage<T, N>>::Storage,
+                b: &<Self as HasStorage<T, N>>::Storage,
+                out: &mut <Self as HasStorage<T, N>>::Storage,
+            ) where
+                Self: HasStorage<T, N>,
+            {
+                let a = <Self as HasStorage<T, N>>::as_slice(a);
+                let b = <Self as HasStorage<T, N>>::as_slice(b);
+                let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+                zip_map(a, b, dst, |$x, $y| $body);
+            }
+
+            fn $assign<const N: usize>(
+                a: &mut <Self as HasStorage<T, N>>::Storage,
+                b: &<Self as HasStorage<T, N>>::Storage,
+            ) where
+                Self: HasStorage<T, N>,
+            {
+                let b = <Self as HasStorage<T, N>>::as_slice(b);
+                let dst = <Self as HasStorage<T, N>>::as_mut_slice(a);
+                zip_map_inplace(dst, b, |$x, $y| $body);
+            }
+        }
+    };
+}
+
+impl_parallel_elemwise!(ElemAdd, Add, elem_add, elem_add_assign, |x, y| x + y);
+impl_parallel_elemwise!(ElemSub, Sub, elem_sub, elem_sub_assign, |x, y| x - y);
+impl_parallel_elemwise!(ElemMul, Mul, elem_mul, elem_mul_assign, |x, y| x * y);
+impl_parallel_elemwise!(ElemDiv, Div, elem_div, elem_div_assign, |x, y| x / y);
+
+impl<T> ElemMax<T> for ParallelCpu
+where
+    T: Copy + Default + PartialOrd + Send + Sync,
+{
+    fn elem_max<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let a = <Self as HasStorage<T, N>>::as_slice(a);
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        zip_map(a, b, dst, nan_max);
+    }
+}
+
+impl<T> ElemMin<T> for ParallelCpu
+where
+    T: Copy + Default + PartialOrd + Send + Sync,
+{
+    fn elem_min<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        b: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let a = <Self as HasStorage<T, N>>::as_slice(a);
+        let b = <Self as HasStorage<T, N>>::as_slice(b);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        zip_map(a, b, dst, nan_min);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_cover_every_element() {
+        ParallelCpu::with_num_threads(4, || {
+            let n = 3 * MIN_ELEMS_PER_THREAD + 5;
+            let a: Vec<f32> = (0..n).map(|i| i as f32).collect();
+            let b: Vec<f32> = (0..n).map(|i| (i % 7) as f32 + 1.0).collect();
+
+            let mut out = vec![0.0; n];
+            zip_map(&a, &b, &mut out, |x, y| x / y);
+            assert!((0..n).all(|i| out[i] == a[i] / b[i]));
+
+            let mut acc = a.clone();
+            zip_map_inplace(&mut acc, &b, |x, y| x - y);
+            assert!((0..n).all(|i| acc[i] == a[i] - b[i]));
+        });
+    }
+}
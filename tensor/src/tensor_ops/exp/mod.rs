@@ -3,6 +3,7 @@
 //! Backend implementers should implement [`Exp`].
 
 pub mod naive_cpu;
+pub mod parallel_cpu;
 
 use crate::storage::HasStorage;
 use crate::tensor::{Tensor2, Tensor3, Tensor4};
@@ -34,6 +35,10 @@ pub trait Exp<T: ExpElem>: Sized {
         out: &mut <Self as HasStorage<T, N>>::Storage,
     ) where
         Self: HasStorage<T, N>;
+
+    fn exp_inplace<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage)
+    where
+        Self: HasStorage<T, N>;
 }
 
 impl<T, const R: usize, const C: usize, B> Tensor2<T, R, C, B>
@@ -50,6 +55,12 @@ where
             _p: core::marker::PhantomData,
         }
     }
+
+    /// In-place variant of [`Self::exp`].
+    #[inline]
+    pub fn exp_(&mut self) {
+        B::exp_inplace::<{ R * C }>(&mut self.storage);
+    }
 }
 
 impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
@@ -67,6 +78,12 @@ where
             _p: core::marker::PhantomData,
         }
     }
+
+    /// In-place variant of [`Self::exp`].
+    #[inline]
+    pub fn exp_(&mut self) {
+        B::exp_inplace::<{ D0 * (D1 * D2) }>(&mut self.storage);
+    }
 }
 
 impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
@@ -85,4 +102,10 @@ where
             _p: core::marker::PhantomData,
         }
     }
+
+    /// In-place variant of [`Self::exp`].
+    #[inline]
+    pub fn exp_(&mut self) {
+        B::exp_inplace::<{ D0 * (D1 * (D3 * D4)) }>(&mut self.storage);
+    }
 }
@@ -18,4 +18,13 @@ where
             dst[i] = src[i].exp();
         }
     }
+
+    fn exp_inplace<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage)
+    where
+        Self: HasStorage<T, N>,
+    {
+        for v in <Self as HasStorage<T, N>>::as_mut_slice(a).iter_mut() {
+            *v = v.exp();
+        }
+    }
 }
@@ -0,0 +1,26 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::exp::{Exp, ExpElem};
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> Exp<T> for ParallelCpu
+where
+    T: ExpElem,
+{
+    fn exp<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Exp<T>>::exp::<N>(naive::<T, N>(a), naive_mut::<T, N>(out))
+    }
+
+    fn exp_inplace<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage)
+    where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Exp<T>>::exp_inplace::<N>(naive_mut::<T, N>(a))
+    }
+}
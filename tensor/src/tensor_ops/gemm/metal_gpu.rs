@@ -0,0 +1,68 @@
+use crate::storage::HasStorage;
+use crate::storage::metal_gpu::{MetalGpu, MetalGpuStorage};
+use crate::tensor_ops::gemm::{Gemm, GemmParams};
+use objc2::AnyThread;
+use objc2_metal::{MTLCommandBuffer, MTLCommandQueue};
+use objc2_metal_performance_shaders::{
+    MPSDataType, MPSMatrix, MPSMatrixDescriptor, MPSMatrixMultiplication,
+};
+
+impl Gemm<f32> for MetalGpu {
+    fn gemm<const LA: usize, const LB: usize, const LC: usize>(
+        params: GemmParams<f32>,
+        a: &<Self as HasStorage<f32, LA>>::Storage,
+        b: &<Self as HasStorage<f32, LB>>::Storage,
+        c: &mut <Self as HasStorage<f32, LC>>::Storage,
+    ) where
+        Self: HasStorage<f32, LA> + HasStorage<f32, LB> + HasStorage<f32, LC>,
+    {
+        let GemmParams { m, n, k, trans_a, trans_b, alpha, beta } = params;
+        // Stored (rows, columns) of each operand.
+        let (a_rows, a_cols) = if trans_a { (k, m) } else { (m, k) };
+        let (b_rows, b_cols) = if trans_b { (n, k) } else { (k, n) };
+
+        unsafe {
+            let dev = &MetalGpu::shared().device;
+            let queue = &MetalGpu::shared().queue;
+
+            let descriptor = |rows: usize, cols: usize| {
+                let row_bytes = MPSMatrixDescriptor::rowBytesForColumns_dataType(
+                    cols as _,
+                    MPSDataType::Float32,
+                );
+                MPSMatrixDescriptor::matrixDescriptorWithRows_columns_rowBytes_dataType(
+                    rows as _,
+                    cols as _,
+                    row_bytes,
+                    MPSDataType::Float32,
+                )
+            };
+            let desc_a = descriptor(a_rows, a_cols);
+            let desc_b = descriptor(b_rows, b_cols);
+            let desc_c = descriptor(m, n);
+
+            let a_buf: &MetalGpuStorage = &*(a as *const _ as *const _);
+            let b_buf: &MetalGpuStorage = &*(b as *const _ as *const _);
+            let c_buf: &MetalGpuStorage = &*(c as *const _ as *const _);
+
+            let mat_a = MPSMatrix::alloc();
+            let mat_a = MPSMatrix::initWithBuffer_descriptor(mat_a, &a_buf.buffer, &desc_a);
+            let mat_b = MPSMatrix::alloc();
+            let mat_b = MPSMatrix::initWithBuffer_descriptor(mat_b, &b_buf.buffer, &desc_b);
+            let mat_c = MPSMatrix::alloc();
+            let mat_c = MPSMatrix::initWithBuffer_descriptor(mat_c, &c_buf.buffer, &desc_c);
+
+            let mm = MPSMatrixMultiplication::alloc();
+            let mm = MPSMatrixMultiplication::initWithDevice_transposeLeft_transposeRight_resultRows_resultColumns_interiorColumns_alpha_beta(
+                mm, dev, trans_a, trans_b, m as _, n as _, k as _, alpha as _, beta as _,
+            );
+
+            let cmd_buf = queue.commandBuffer().expect("cmd buffer");
+            mm.encodeToCommandBuffer_leftMatrix_rightMatrix_resultMatrix(
+                &cmd_buf, &mat_a, &mat_b, &mat_c,
+            );
+            cmd_buf.commit();
+            cmd_buf.waitUntilCompleted();
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! General matrix multiply, `C = alpha * op(A) * op(B) + beta * C`.
+//!
+//! `op(X)` is either `X` or its transpose. Transposes are never materialized:
+//! pass [`Tensor2::t`] instead of a tensor and the backend reads it with
+//! swapped strides. As in BLAS, `C` is not read when `beta` is zero, so it may
+//! hold anything, NaN included. Backend implementers should implement [`Gemm`].
+
+#[cfg(target_os = "macos")]
+mod metal_gpu;
+pub mod naive_cpu;
+pub mod parallel_cpu;
+
+use crate::storage::HasStorage;
+use crate::tensor::Tensor2;
+use core::ops::{Add, Mul};
+
+/// Shape, transposition and scaling of one GEMM call.
+///
+/// `op(A)` is `m x k`, `op(B)` is `k x n` and `C` is `m x n`, all row-major.
+/// With `trans_a` the stored `A` is `k x m`; with `trans_b` the stored `B` is
+/// `n x k`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GemmParams<T> {
+    pub m: usize,
+    pub n: usize,
+    pub k: usize,
+    pub trans_a: bool,
+    pub trans_b: bool,
+    pub alpha: T,
+    pub beta: T,
+}
+
+/// Backend trait for GEMM. `LA`, `LB` and `LC` are the stored lengths of
+/// `a`, `b` and `c`.
+pub trait Gemm<T: Copy + Default>: Sized {
+    fn gemm<const LA: usize, const LB: usize, const LC: usize>(
+        params: GemmParams<T>,
+        a: &<Self as HasStorage<T, LA>>::Storage,
+        b: &<Self as HasStorage<T, LB>>::Storage,
+        c: &mut <Self as HasStorage<T, LC>>::Storage,
+    ) where
+        Self: HasStorage<T, LA> + HasStorage<T, LB> + HasStorage<T, LC>;
+}
+
+/// A borrowed, lazily transposed view of a [`Tensor2`], created by
+/// [`Tensor2::t`].
+pub struct Transposed<'a, T, const R: usize, const C: usize, B>(pub &'a Tensor2<T, R, C, B>)
+where
+    T: Copy + Default,
+    B: HasStorage<T, { R * C }>;
+
+/// A GEMM operand that behaves as a `ROWS x COLS` matrix stored in `LEN`
+/// elements: either `&Tensor2` or a [`Transposed`] view of one.
+pub trait GemmOperand<T, B, const ROWS: usize, const COLS: usize, const LEN: usize>
+where
+    T: Copy + Default,
+    B: HasStorage<T, LEN>,
+{
+    const TRANSPOSED: bool;
+
+    fn storage(&self) -> &<B as HasStorage<T, LEN>>::Storage;
+}
+
+impl<T, const R: usize, const C: usize, B> GemmOperand<T, B, R, C, { R * C }>
+    for &Tensor2<T, R, C, B>
+where
+    T: Copy + Default,
+    B: HasStorage<T, { R * C }>,
+{
+    const TRANSPOSED: bool = false;
+
+    #[inline]
+    fn storage(&self) -> &<B as HasStorage<T, { R * C }>>::Storage {
+        &self.storage
+    }
+}
+
+impl<T, const R: usize, const C: usize, B> GemmOperand<T, B, C, R, { R * C }>
+    for Transposed<'_, T, R, C, B>
+where
+    T: Copy + Default,
+    B: HasStorage<T, { R * C }>,
+{
+    const TRANSPOSED: bool = true;
+
+    #[inline]
+    fn storage(&self) -> &<B as HasStorage<T, { R * C }>>::Storage {
+        &self.0.storage
+    }
+}
+
+impl<T, const R: usize, const C: usize, B> Tensor2<T, R, C, B>
+where
+    T: Copy + Default,
+    B: HasStorage<T, { R * C }>,
+{
+    /// Lazily transposed view of `self`, for use as a GEMM operand.
+    #[inline]
+    pub fn t(&self) -> Transposed<'_, T, R, C, B> {
+        Transposed(self)
+    }
+}
+
+impl<T, const M: usize, const N: usize, B> Tensor2<T, M, N, B>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    B: Gemm<T> + HasStorage<T, { M * N }>,
+{
+    /// Computes `self = alpha * a * b + beta * self` in place.
+    ///
+    /// `a` and `b` are `&Tensor2`s or [`Transposed`] views, e.g. the weight
+    /// gradient of a linear layer is `dw.gemm_(1.0, x.t(), &dy, 0.0)`.
+    #[inline]
+    pub fn gemm_<OA, OB, const K: usize, const LA: usize, const LB: usize>(
+        &mut self,
+        alpha: T,
+        a: OA,
+        b: OB,
+        beta: T,
+    ) where
+        OA: GemmOperand<T, B, M, K, LA>,
+        OB: GemmOperand<T, B, K, N, LB>,
+        B: HasStorage<T, LA> + HasStorage<T, LB>,
+    {
+        let params = GemmParams {
+            m: M,
+            n: N,
+            k: K,
+            trans_a: OA::TRANSPOSED,
+            trans_b: OB::TRANSPOSED,
+            alpha,
+            beta,
+        };
+        B::gemm::<LA, LB, { M * N }>(params, a.storage(), b.storage(), &mut self.storage);
+    }
+
+    /// Returns `alpha * a * b + beta * self`.
+    #[inline]
+    pub fn gemm<OA, OB, const K: usize, const LA: usize, const LB: usize>(
+        mut self,
+        alpha: T,
+        a: OA,
+        b: OB,
+        beta: T,
+    ) -> Self
+    where
+        OA: GemmOperand<T, B, M, K, LA>,
+        OB: GemmOperand<T, B, K, N, LB>,
+        B: HasStorage<T, LA> + HasStorage<T, LB>,
+    {
+        self.gemm_(alpha, a, b, beta);
+        self
+    }
+}
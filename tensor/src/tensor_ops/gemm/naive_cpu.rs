@@ -0,0 +1,86 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::tensor_ops::gemm::{Gemm, GemmParams};
+use core::ops::{Add, Mul};
+use num_traits::Zero;
+
+impl<T> Gemm<T> for NaiveCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Zero,
+{
+    fn gemm<const LA: usize, const LB: usize, const LC: usize>(
+        params: GemmParams<T>,
+        a: &<Self as HasStorage<T, LA>>::Storage,
+        b: &<Self as HasStorage<T, LB>>::Storage,
+        c: &mut <Self as HasStorage<T, LC>>::Storage,
+    ) where
+        Self: HasStorage<T, LA> + HasStorage<T, LB> + HasStorage<T, LC>,
+    {
+        let a = <Self as HasStorage<T, LA>>::as_slice(a);
+        let b = <Self as HasStorage<T, LB>>::as_slice(b);
+        let c = <Self as HasStorage<T, LC>>::as_mut_slice(c);
+        let GemmParams { m, n, k, trans_a, trans_b, alpha, beta } = params;
+
+        // Element (i, j) of op(X) for a stored row-major X.
+        let a_at = |i: usize, p: usize| if trans_a { a[p * m + i] } else { a[i * k + p] };
+        let b_at = |p: usize, j: usize| if trans_b { b[j * k + p] } else { b[p * n + j] };
+
+        for i in 0..m {
+            for j in 0..n {
+                let mut acc = T::default();
+                for p in 0..k {
+                    acc = acc + a_at(i, p) * b_at(p, j);
+                }
+                // With beta == 0, C is write-only, as in BLAS: NaN or
+                // uninitialized values in it must not leak into the result.
+                c[i * n + j] = if beta.is_zero() {
+                    alpha * acc
+                } else {
+                    alpha * acc + beta * c[i * n + j]
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::tensor::Tensor2;
+
+    #[test]
+    fn test_gemm_matches_matmul_for_every_transpose() {
+        let a = Tensor2::<f32, 2, 3, NaiveCpu>::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let b = Tensor2::<f32, 3, 2, NaiveCpu>::new([7.0, 8.0, 9.0, 10.0, 11.0, 12.0]);
+        let at = Tensor2::<f32, 3, 2, NaiveCpu>::new([1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+        let bt = Tensor2::<f32, 2, 3, NaiveCpu>::new([7.0, 9.0, 11.0, 8.0, 10.0, 12.0]);
+        let want = a * b;
+
+        let zero = Tensor2::<f32, 2, 2, NaiveCpu>::zeroes();
+        assert_eq!(zero.gemm(1.0, &a, &b, 0.0).as_slice(), want.as_slice());
+        assert_eq!(zero.gemm(1.0, at.t(), &b, 0.0).as_slice(), want.as_slice());
+        assert_eq!(zero.gemm(1.0, &a, bt.t(), 0.0).as_slice(), want.as_slice());
+        assert_eq!(zero.gemm(1.0, at.t(), bt.t(), 0.0).as_slice(), want.as_slice());
+    }
+
+    #[test]
+    fn test_gemm_alpha_beta_accumulate() {
+        let x = Tensor2::<f64, 3, 2, NaiveCpu>::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let dy = Tensor2::<f64, 3, 1, NaiveCpu>::new([1.0, -1.0, 2.0]);
+
+        // Weight gradient of a linear layer, accumulated into an existing one.
+        let mut dw = Tensor2::<f64, 2, 1, NaiveCpu>::new([100.0, 200.0]);
+        dw.gemm_(2.0, x.t(), &dy, 0.5);
+        // x^T dy = [1 - 3 + 10, 2 - 4 + 12] = [8, 10]
+        assert_eq!(dw.as_slice(), &[2.0 * 8.0 + 50.0, 2.0 * 10.0 + 100.0]);
+    }
+
+    #[test]
+    fn test_gemm_ignores_c_when_beta_is_zero() {
+        let a = Tensor2::<f32, 2, 2, NaiveCpu>::new([1.0, 2.0, 3.0, 4.0]);
+        let b = Tensor2::<f32, 2, 2, NaiveCpu>::new([1.0, 0.0, 0.0, 1.0]);
+        let mut c = Tensor2::<f32, 2, 2, NaiveCpu>::new([f32::NAN, f32::INFINITY, f32::NAN, 1.0]);
+        c.gemm_(2.0, &a, &b, 0.0);
+        assert_eq!(c.as_slice(), &[2.0, 4.0, 6.0, 8.0]);
+    }
+}
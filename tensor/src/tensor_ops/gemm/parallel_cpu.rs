@@ -0,0 +1,28 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::gemm::{Gemm, GemmParams};
+use core::ops::{Add, Mul};
+use num_traits::Zero;
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> Gemm<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Zero,
+{
+    fn gemm<const LA: usize, const LB: usize, const LC: usize>(
+        params: GemmParams<T>,
+        a: &<Self as HasStorage<T, LA>>::Storage,
+        b: &<Self as HasStorage<T, LB>>::Storage,
+        c: &mut <Self as HasStorage<T, LC>>::Storage,
+    ) where
+        Self: HasStorage<T, LA> + HasStorage<T, LB> + HasStorage<T, LC>,
+    {
+        <NaiveCpu as Gemm<T>>::gemm::<LA, LB, LC>(
+            params,
+            naive::<T, LA>(a),
+            naive::<T, LB>(b),
+            naive_mut::<T, LC>(c),
+        )
+    }
+}
@@ -0,0 +1,498 @@
+//! Dense linear algebra on [`Tensor2`] matrices.
+//!
+//! Factorizations return a [`LinalgError`] instead of producing NaNs when the
+//! input is numerically singular. Backend implementers should implement
+//! [`LuDecomp`], [`QrDecomp`], [`Cholesky`], [`SvdDecomp`] and
+//! [`EighDecomp`]; the last two take a leading `batch` count so the same entry
+//! point serves [`Tensor2`] and the batched [`Tensor3`] forms.
+
+pub mod naive_cpu;
+pub mod parallel_cpu;
+
+use crate::storage::HasStorage;
+use crate::tensor::{Tensor1, Tensor2, Tensor3};
+use core::fmt;
+use num_traits::Float;
+
+/// Why a linear-algebra routine could not produce a result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinalgError {
+    /// The matrix is singular to working precision: after partial pivoting
+    /// the largest candidate for pivot `pivot` was negligible.
+    Singular { pivot: usize },
+    /// A Cholesky factorization met a non-positive pivot, so the matrix is
+    /// not symmetric positive-definite.
+    NotPositiveDefinite { pivot: usize },
+    /// A least-squares problem has linearly dependent columns: `R[column,
+    /// column]` of its QR decomposition is negligible.
+    RankDeficient { column: usize },
+}
+
+impl fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinalgError::Singular { pivot } => {
+                write!(f, "matrix is singular (zero pivot in column {pivot})")
+            }
+            LinalgError::NotPositiveDefinite { pivot } => {
+                write!(f, "matrix is not positive-definite (pivot {pivot} is not positive)")
+            }
+            LinalgError::RankDeficient { column } => {
+                write!(f, "matrix is rank-deficient (column {column} is linearly dependent)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LinalgError {}
+
+/// Threshold below which a pivot counts as zero: `n * eps * max|a|`.
+pub(crate) fn pivot_tolerance<T: Float>(a: &[T], n: usize) -> T {
+    let scale = a.iter().fold(T::zero(), |m, &v| m.max(v.abs()));
+    T::from(n).unwrap() * T::epsilon() * scale
+}
+
+/// Backend trait for LU decomposition with partial pivoting, `P·A = L·U`.
+pub trait LuDecomp<T: Float + Default>: Sized {
+    /// Factors `a` into `lu`, which holds `L` strictly below the diagonal
+    /// (its unit diagonal is implied) and `U` on and above it. `perm[i]` is
+    /// the row of `a` that ended up in row `i`.
+    ///
+    /// Returns the sign of the permutation, `1` or `-1`.
+    fn lu_factor<const N: usize>(
+        a: &<Self as HasStorage<T, { N * N }>>::Storage,
+        lu: &mut <Self as HasStorage<T, { N * N }>>::Storage,
+        perm: &mut <Self as HasStorage<usize, N>>::Storage,
+    ) -> Result<T, LinalgError>
+    where
+        Self: HasStorage<T, { N * N }> + HasStorage<usize, N>;
+
+    /// Determinant of `a` by the same elimination, but without the
+    /// singularity tolerance of [`LuDecomp::lu_factor`]: it is `0` only when
+    /// a pivot column is exactly zero.
+    fn lu_det<const N: usize>(a: &<Self as HasStorage<T, { N * N }>>::Storage) -> T
+    where
+        Self: HasStorage<T, { N * N }> + HasStorage<usize, N>;
+
+    /// Solves `A·X = B` for an `N x K` right-hand side, given the output of
+    /// [`LuDecomp::lu_factor`].
+    fn lu_solve<const N: usize, const K: usize>(
+        lu: &<Self as HasStorage<T, { N * N }>>::Storage,
+        perm: &<Self as HasStorage<usize, N>>::Storage,
+        b: &<Self as HasStorage<T, { N * K }>>::Storage,
+        x: &mut <Self as HasStorage<T, { N * K }>>::Storage,
+    ) where
+        Self: HasStorage<T, { N * N }> + HasStorage<usize, N> + HasStorage<T, { N * K }>;
+}
+
+/// Evaluates to `0` when `rows >= cols`.
+///
+/// Used as a `[(); at_least_as_tall(M, N)]:` bound so QR of a wide matrix
+/// fails compilation.
+pub const fn at_least_as_tall(rows: usize, cols: usize) -> usize {
+    assert!(rows >= cols, "QR needs at least as many rows as columns");
+    0
+}
+
+/// Backend trait for thin QR decomposition by Householder reflections.
+pub trait QrDecomp<T: Float + Default>: Sized {
+    /// Factors the `M x N` matrix `a` (`M >= N`) as `Q·R`, where `q` is
+    /// `M x N` with orthonormal columns and `r` is `N x N` upper-triangular
+    /// with a non-negative diagonal.
+    fn qr_factor<const M: usize, const N: usize>(
+        a: &<Self as HasStorage<T, { M * N }>>::Storage,
+        q: &mut <Self as HasStorage<T, { M * N }>>::Storage,
+        r: &mut <Self as HasStorage<T, { N * N }>>::Storage,
+    ) where
+        Self: HasStorage<T, { M * N }> + HasStorage<T, { N * N }>;
+
+    /// Solves `R·X = Qᵀ·B` for an `M x K` right-hand side, the least-squares
+    /// solution of `A·X = B`.
+    fn qr_solve<const M: usize, const N: usize, const K: usize>(
+        q: &<Self as HasStorage<T, { M * N }>>::Storage,
+        r: &<Self as HasStorage<T, { N * N }>>::Storage,
+        b: &<Self as HasStorage<T, { M * K }>>::Storage,
+        x: &mut <Self as HasStorage<T, { N * K }>>::Storage,
+    ) -> Result<(), LinalgError>
+    where
+        Self: HasStorage<T, { M * N }>
+            + HasStorage<T, { N * N }>
+            + HasStorage<T, { M * K }>
+            + HasStorage<T, { N * K }>;
+}
+
+/// Backend trait for Cholesky decomposition `A = L·Lᵀ`.
+pub trait Cholesky<T: Float + Default>: Sized {
+    /// Writes the lower-triangular `l` (zeros above the diagonal). Only the
+    /// lower triangle of `a` is read; it is assumed symmetric.
+    fn cholesky_factor<const N: usize>(
+        a: &<Self as HasStorage<T, { N * N }>>::Storage,
+        l: &mut <Self as HasStorage<T, { N * N }>>::Storage,
+    ) -> Result<(), LinalgError>
+    where
+        Self: HasStorage<T, { N * N }>;
+}
+
+/// `min(rows, cols)`, the inner dimension of a thin SVD.
+pub const fn min_dim(rows: usize, cols: usize) -> usize {
+    if rows < cols { rows } else { cols }
+}
+
+/// Backend trait for the thin singular value decomposition `A = U·Σ·Vᵀ`,
+/// over `batch` matrices laid out back to back. `NA`, `NU`, `NS` and `NV` are
+/// storage lengths.
+pub trait SvdDecomp<T: Float + Default>: Sized {
+    /// With `k = min(rows, cols)`, writes `u` (`rows x k`, orthonormal
+    /// columns), the singular values `s` (`k`, descending) and `vt` (`k x
+    /// cols`, orthonormal rows) of each matrix.
+    #[allow(clippy::too_many_arguments)]
+    fn svd<const NA: usize, const NU: usize, const NS: usize, const NV: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        u: &mut <Self as HasStorage<T, NU>>::Storage,
+        s: &mut <Self as HasStorage<T, NS>>::Storage,
+        vt: &mut <Self as HasStorage<T, NV>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NU> + HasStorage<T, NS> + HasStorage<T, NV>;
+}
+
+/// Backend trait for the eigendecomposition `A = V·diag(w)·Vᵀ` of symmetric
+/// `n x n` matrices, over `batch` matrices laid out back to back.
+pub trait EighDecomp<T: Float + Default>: Sized {
+    /// Writes the eigenvalues `w` in ascending order and the matching unit
+    /// eigenvectors as the columns of `v`. `a` is assumed symmetric.
+    fn eigh<const NA: usize, const NW: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        n: usize,
+        w: &mut <Self as HasStorage<T, NW>>::Storage,
+        v: &mut <Self as HasStorage<T, NA>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NW>;
+}
+
+/// An LU decomposition `P·A = L·U`, from [`Tensor2::lu`].
+pub struct Lu<T, const N: usize, B>
+where
+    T: Float + Default,
+    B: HasStorage<T, { N * N }> + HasStorage<usize, N>,
+{
+    lu: Tensor2<T, N, N, B>,
+    perm: Tensor1<usize, N, B>,
+    sign: T,
+}
+
+impl<T, const N: usize, B> Lu<T, N, B>
+where
+    T: Float + Default,
+    B: LuDecomp<T> + HasStorage<T, { N * N }> + HasStorage<usize, N>,
+{
+    /// The unit lower-triangular factor `L`.
+    pub fn l(&self) -> Tensor2<T, N, N, B> {
+        self.unpack(|i, j, v| match i.cmp(&j) {
+            core::cmp::Ordering::Greater => v,
+            core::cmp::Ordering::Equal => T::one(),
+            core::cmp::Ordering::Less => T::zero(),
+        })
+    }
+
+    /// The upper-triangular factor `U`.
+    pub fn u(&self) -> Tensor2<T, N, N, B> {
+        self.unpack(|i, j, v| if i <= j { v } else { T::zero() })
+    }
+
+    /// Row `i` of `P·A` is row `permutation()[i]` of `A`.
+    pub fn permutation(&self) -> &Tensor1<usize, N, B> {
+        &self.perm
+    }
+
+    /// Determinant of `A`.
+    pub fn det(&self) -> T {
+        let lu = self.lu.as_slice();
+        (0..N).fold(self.sign, |d, i| d * lu[i * N + i])
+    }
+
+    /// Solves `A·X = B`.
+    pub fn solve<const K: usize>(&self, b: &Tensor2<T, N, K, B>) -> Tensor2<T, N, K, B>
+    where
+        B: HasStorage<T, { N * K }>,
+    {
+        let mut x = <B as HasStorage<T, { N * K }>>::storage_uninit();
+        B::lu_solve::<N, K>(&self.lu.storage, &self.perm.storage, &b.storage, &mut x);
+        Tensor2 {
+            storage: x,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// `A⁻¹`.
+    pub fn inverse(&self) -> Tensor2<T, N, N, B> {
+        let mut eye = [T::zero(); N * N];
+        for i in 0..N {
+            eye[i * N + i] = T::one();
+        }
+        self.solve(&Tensor2::new_from_slice(&eye))
+    }
+
+    fn unpack(&self, f: impl Fn(usize, usize, T) -> T) -> Tensor2<T, N, N, B> {
+        let lu = self.lu.as_slice();
+        let mut out = [T::zero(); N * N];
+        for i in 0..N {
+            for j in 0..N {
+                out[i * N + j] = f(i, j, lu[i * N + j]);
+            }
+        }
+        Tensor2::new_from_slice(&out)
+    }
+}
+
+impl<T, const N: usize, B> Tensor2<T, N, N, B>
+where
+    T: Float + Default,
+    B: LuDecomp<T> + HasStorage<T, { N * N }> + HasStorage<usize, N>,
+{
+    /// LU decomposition with partial pivoting.
+    pub fn lu(&self) -> Result<Lu<T, N, B>, LinalgError> {
+        let mut lu = <B as HasStorage<T, { N * N }>>::storage_uninit();
+        let mut perm = <B as HasStorage<usize, N>>::storage_uninit();
+        let sign = B::lu_factor::<N>(&self.storage, &mut lu, &mut perm)?;
+        Ok(Lu {
+            lu: Tensor2 {
+                storage: lu,
+                _p: core::marker::PhantomData,
+            },
+            perm: Tensor1 {
+                storage: perm,
+                _p: core::marker::PhantomData,
+            },
+            sign,
+        })
+    }
+
+    /// Solves `self · X = b`.
+    pub fn solve<const K: usize>(
+        &self,
+        b: &Tensor2<T, N, K, B>,
+    ) -> Result<Tensor2<T, N, K, B>, LinalgError>
+    where
+        B: HasStorage<T, { N * K }>,
+    {
+        Ok(self.lu()?.solve(b))
+    }
+
+    /// `self⁻¹`.
+    pub fn inverse(&self) -> Result<Self, LinalgError> {
+        Ok(self.lu()?.inverse())
+    }
+
+    /// Determinant. Unlike [`lu`](Self::lu) it applies no singularity
+    /// tolerance, so a tiny but nonzero determinant is returned as is.
+    pub fn det(&self) -> T {
+        B::lu_det::<N>(&self.storage)
+    }
+}
+
+impl<T, const M: usize, const N: usize, B> Tensor2<T, M, N, B>
+where
+    T: Float + Default,
+    B: QrDecomp<T> + HasStorage<T, { M * N }> + HasStorage<T, { N * N }>,
+    [(); at_least_as_tall(M, N)]:,
+{
+    /// Thin QR decomposition `(Q, R)`: `Q` is `M x N` with orthonormal
+    /// columns and `R` is `N x N` upper-triangular with a non-negative
+    /// diagonal, which makes the pair unique for a full-rank `self`.
+    pub fn qr(&self) -> (Tensor2<T, M, N, B>, Tensor2<T, N, N, B>) {
+        let mut q = <B as HasStorage<T, { M * N }>>::storage_uninit();
+        let mut r = <B as HasStorage<T, { N * N }>>::storage_uninit();
+        B::qr_factor::<M, N>(&self.storage, &mut q, &mut r);
+        (
+            Tensor2 {
+                storage: q,
+                _p: core::marker::PhantomData,
+            },
+            Tensor2 {
+                storage: r,
+                _p: core::marker::PhantomData,
+            },
+        )
+    }
+
+    /// Least-squares solution `X` minimizing `‖self·X - b‖`, via QR.
+    pub fn lstsq<const K: usize>(
+        &self,
+        b: &Tensor2<T, M, K, B>,
+    ) -> Result<Tensor2<T, N, K, B>, LinalgError>
+    where
+        B: HasStorage<T, { M * K }> + HasStorage<T, { N * K }>,
+    {
+        let (q, r) = self.qr();
+        let mut x = <B as HasStorage<T, { N * K }>>::storage_uninit();
+        B::qr_solve::<M, N, K>(&q.storage, &r.storage, &b.storage, &mut x)?;
+        Ok(Tensor2 {
+            storage: x,
+            _p: core::marker::PhantomData,
+        })
+    }
+}
+
+impl<T, const N: usize, B> Tensor2<T, N, N, B>
+where
+    T: Float + Default,
+    B: Cholesky<T> + HasStorage<T, { N * N }>,
+{
+    /// Lower-triangular `L` with `self = L·Lᵀ`, for a symmetric
+    /// positive-definite `self`.
+    pub fn cholesky(&self) -> Result<Self, LinalgError> {
+        let mut l = <B as HasStorage<T, { N * N }>>::storage_uninit();
+        B::cholesky_factor::<N>(&self.storage, &mut l)?;
+        Ok(Tensor2 {
+            storage: l,
+            _p: core::marker::PhantomData,
+        })
+    }
+}
+
+impl<T, const M: usize, const N: usize, B> Tensor2<T, M, N, B>
+where
+    T: Float + Default,
+    B: SvdDecomp<T>
+        + HasStorage<T, { M * N }>
+        + HasStorage<T, { M * min_dim(M, N) }>
+        + HasStorage<T, { min_dim(M, N) }>
+        + HasStorage<T, { min_dim(M, N) * N }>,
+{
+    /// Thin SVD `(U, Σ, Vᵀ)` with `self = U·diag(Σ)·Vᵀ` and `Σ` descending.
+    ///
+    /// Each singular pair's sign is fixed so the largest-magnitude entry of
+    /// its column of `U` is positive.
+    #[allow(clippy::type_complexity)]
+    pub fn svd(
+        &self,
+    ) -> (
+        Tensor2<T, M, { min_dim(M, N) }, B>,
+        Tensor1<T, { min_dim(M, N) }, B>,
+        Tensor2<T, { min_dim(M, N) }, N, B>,
+    ) {
+        let mut u = <B as HasStorage<T, { M * min_dim(M, N) }>>::storage_uninit();
+        let mut s = <B as HasStorage<T, { min_dim(M, N) }>>::storage_uninit();
+        let mut vt = <B as HasStorage<T, { min_dim(M, N) * N }>>::storage_uninit();
+        B::svd::<{ M * N }, { M * min_dim(M, N) }, { min_dim(M, N) }, { min_dim(M, N) * N }>(
+            &self.storage,
+            1,
+            M,
+            N,
+            &mut u,
+            &mut s,
+            &mut vt,
+        );
+        (
+            Tensor2 {
+                storage: u,
+                _p: core::marker::PhantomData,
+            },
+            Tensor1 {
+                storage: s,
+                _p: core::marker::PhantomData,
+            },
+            Tensor2 {
+                storage: vt,
+                _p: core::marker::PhantomData,
+            },
+        )
+    }
+}
+
+impl<T, const N: usize, B> Tensor2<T, N, N, B>
+where
+    T: Float + Default,
+    B: EighDecomp<T> + HasStorage<T, { N * N }> + HasStorage<T, N>,
+{
+    /// Eigenvalues (ascending) and unit eigenvectors (columns) of a
+    /// symmetric `self`. Each eigenvector's largest-magnitude entry is
+    /// positive.
+    pub fn eigh(&self) -> (Tensor1<T, N, B>, Self) {
+        let mut w = <B as HasStorage<T, N>>::storage_uninit();
+        let mut v = <B as HasStorage<T, { N * N }>>::storage_uninit();
+        B::eigh::<{ N * N }, N>(&self.storage, 1, N, &mut w, &mut v);
+        (
+            Tensor1 {
+                storage: w,
+                _p: core::marker::PhantomData,
+            },
+            Tensor2 {
+                storage: v,
+                _p: core::marker::PhantomData,
+            },
+        )
+    }
+}
+
+impl<T, const D: usize, const M: usize, const N: usize, B> Tensor3<T, D, M, N, B>
+where
+    T: Float + Default,
+    B: SvdDecomp<T>
+        + HasStorage<T, { D * (M * N) }>
+        + HasStorage<T, { D * (M * min_dim(M, N)) }>
+        + HasStorage<T, { D * min_dim(M, N) }>
+        + HasStorage<T, { D * (min_dim(M, N) * N) }>,
+{
+    /// Thin SVD of each of the `D` matrices; see [`Tensor2::svd`].
+    #[allow(clippy::type_complexity)]
+    pub fn svd(
+        &self,
+    ) -> (
+        Tensor3<T, D, M, { min_dim(M, N) }, B>,
+        Tensor2<T, D, { min_dim(M, N) }, B>,
+        Tensor3<T, D, { min_dim(M, N) }, N, B>,
+    ) {
+        let mut u = <B as HasStorage<T, { D * (M * min_dim(M, N)) }>>::storage_uninit();
+        let mut s = <B as HasStorage<T, { D * min_dim(M, N) }>>::storage_uninit();
+        let mut vt = <B as HasStorage<T, { D * (min_dim(M, N) * N) }>>::storage_uninit();
+        B::svd::<
+            { D * (M * N) },
+            { D * (M * min_dim(M, N)) },
+            { D * min_dim(M, N) },
+            { D * (min_dim(M, N) * N) },
+        >(&self.storage, D, M, N, &mut u, &mut s, &mut vt);
+        (
+            Tensor3 {
+                storage: u,
+                _p: core::marker::PhantomData,
+            },
+            Tensor2 {
+                storage: s,
+                _p: core::marker::PhantomData,
+            },
+            Tensor3 {
+                storage: vt,
+                _p: core::marker::PhantomData,
+            },
+        )
+    }
+}
+
+impl<T, const D: usize, const N: usize, B> Tensor3<T, D, N, N, B>
+where
+    T: Float + Default,
+    B: EighDecomp<T> + HasStorage<T, { D * (N * N) }> + HasStorage<T, { D * N }>,
+{
+    /// Eigendecomposition of each of the `D` symmetric matrices; see
+    /// [`Tensor2::eigh`].
+    pub fn eigh(&self) -> (Tensor2<T, D, N, B>, Self) {
+        let mut w = <B as HasStorage<T, { D * N }>>::storage_uninit();
+        let mut v = <B as HasStorage<T, { D * (N * N) }>>::storage_uninit();
+        B::eigh::<{ D * (N * N) }, { D * N }>(&self.storage, D, N, &mut w, &mut v);
+        (
+            Tensor2 {
+                storage: w,
+                _p: core::marker::PhantomData,
+            },
+            Tensor3 {
+                storage: v,
+                _p: core::marker::PhantomData,
+            },
+        )
+    }
+}
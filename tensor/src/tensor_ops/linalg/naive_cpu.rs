@@ -0,0 +1,757 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::tensor_ops::linalg::{
+    Cholesky, EighDecomp, LinalgError, LuDecomp, QrDecomp, SvdDecomp, min_dim, pivot_tolerance,
+};
+use crate::tensor_ops::{directed_cmp, total_cmp};
+use num_traits::Float;
+
+/// In-place LU elimination with partial pivoting of the `n x n` matrix in
+/// `lu`, failing on the first pivot whose magnitude is at most `tol`.
+/// Returns the sign of the permutation written to `perm`.
+fn eliminate<T: Float>(
+    lu: &mut [T],
+    perm: &mut [usize],
+    n: usize,
+    tol: T,
+) -> Result<T, LinalgError> {
+    for (i, p) in perm.iter_mut().enumerate() {
+        *p = i;
+    }
+
+    let mut sign = T::one();
+    for k in 0..n {
+        // Partial pivoting: bring the largest remaining entry of column k up.
+        let p = (k + 1..n).fold(k, |best, i| {
+            if lu[i * n + k].abs() > lu[best * n + k].abs() { i } else { best }
+        });
+        let pivot_abs = lu[p * n + k].abs();
+        if pivot_abs <= tol || pivot_abs.is_nan() {
+            return Err(LinalgError::Singular { pivot: k });
+        }
+        if p != k {
+            for j in 0..n {
+                lu.swap(k * n + j, p * n + j);
+            }
+            perm.swap(k, p);
+            sign = -sign;
+        }
+
+        let pivot = lu[k * n + k];
+        for i in k + 1..n {
+            let l = lu[i * n + k] / pivot;
+            lu[i * n + k] = l;
+            for j in k + 1..n {
+                lu[i * n + j] = lu[i * n + j] - l * lu[k * n + j];
+            }
+        }
+    }
+    Ok(sign)
+}
+
+impl<T> LuDecomp<T> for NaiveCpu
+where
+    T: Float + Default,
+{
+    fn lu_factor<const N: usize>(
+        a: &<Self as HasStorage<T, { N * N }>>::Storage,
+        lu: &mut <Self as HasStorage<T, { N * N }>>::Storage,
+        perm: &mut <Self as HasStorage<usize, N>>::Storage,
+    ) -> Result<T, LinalgError>
+    where
+        Self: HasStorage<T, { N * N }> + HasStorage<usize, N>,
+    {
+        let a = <Self as HasStorage<T, { N * N }>>::as_slice(a);
+        let lu = <Self as HasStorage<T, { N * N }>>::as_mut_slice(lu);
+        let perm = <Self as HasStorage<usize, N>>::as_mut_slice(perm);
+        lu.copy_from_slice(a);
+        eliminate(lu, perm, N, pivot_tolerance(a, N))
+    }
+
+    fn lu_det<const N: usize>(a: &<Self as HasStorage<T, { N * N }>>::Storage) -> T
+    where
+        Self: HasStorage<T, { N * N }> + HasStorage<usize, N>,
+    {
+        let mut lu = <Self as HasStorage<T, { N * N }>>::storage_uninit();
+        let mut perm = <Self as HasStorage<usize, N>>::storage_uninit();
+        let lu = <Self as HasStorage<T, { N * N }>>::as_mut_slice(&mut lu);
+        let perm = <Self as HasStorage<usize, N>>::as_mut_slice(&mut perm);
+        lu.copy_from_slice(<Self as HasStorage<T, { N * N }>>::as_slice(a));
+        match eliminate(lu, perm, N, T::zero()) {
+            Ok(sign) => (0..N).fold(sign, |d, i| d * lu[i * N + i]),
+            Err(_) => T::zero(),
+        }
+    }
+
+    fn lu_solve<const N: usize, const K: usize>(
+        lu: &<Self as HasStorage<T, { N * N }>>::Storage,
+        perm: &<Self as HasStorage<usize, N>>::Storage,
+        b: &<Self as HasStorage<T, { N * K }>>::Storage,
+        x: &mut <Self as HasStorage<T, { N * K }>>::Storage,
+    ) where
+        Self: HasStorage<T, { N * N }> + HasStorage<usize, N> + HasStorage<T, { N * K }>,
+    {
+        let lu = <Self as HasStorage<T, { N * N }>>::as_slice(lu);
+        let perm = <Self as HasStorage<usize, N>>::as_slice(perm);
+        let b = <Self as HasStorage<T, { N * K }>>::as_slice(b);
+        let x = <Self as HasStorage<T, { N * K }>>::as_mut_slice(x);
+
+        // x = P·b, then L·y = x (unit diagonal), then U·x = y, all in place.
+        for (i, &p) in perm.iter().enumerate() {
+            x[i * K..(i + 1) * K].copy_from_slice(&b[p * K..(p + 1) * K]);
+        }
+        for i in 0..N {
+            for j in 0..i {
+                let l = lu[i * N + j];
+                for c in 0..K {
+                    x[i * K + c] = x[i * K + c] - l * x[j * K + c];
+                }
+            }
+        }
+        for i in (0..N).rev() {
+            for j in i + 1..N {
+                let u = lu[i * N + j];
+                for c in 0..K {
+                    x[i * K + c] = x[i * K + c] - u * x[j * K + c];
+                }
+            }
+            let d = lu[i * N + i];
+            for c in 0..K {
+                x[i * K + c] = x[i * K + c] / d;
+            }
+        }
+    }
+}
+
+impl<T> QrDecomp<T> for NaiveCpu
+where
+    T: Float + Default,
+{
+    fn qr_factor<const M: usize, const N: usize>(
+        a: &<Self as HasStorage<T, { M * N }>>::Storage,
+        q: &mut <Self as HasStorage<T, { M * N }>>::Storage,
+        r: &mut <Self as HasStorage<T, { N * N }>>::Storage,
+    ) where
+        Self: HasStorage<T, { M * N }> + HasStorage<T, { N * N }>,
+    {
+        let a = <Self as HasStorage<T, { M * N }>>::as_slice(a);
+        let q = <Self as HasStorage<T, { M * N }>>::as_mut_slice(q);
+        let r = <Self as HasStorage<T, { N * N }>>::as_mut_slice(r);
+
+        // Reduce a copy of `a` to upper-triangular form, keeping the unit
+        // Householder vector `v_k` (acting on rows k..M) of each step.
+        let mut work = a.to_vec();
+        let mut reflectors: Vec<Vec<T>> = Vec::with_capacity(N);
+        for k in 0..N {
+            let mut v: Vec<T> = (k..M).map(|i| work[i * N + k]).collect();
+            let norm = v.iter().fold(T::zero(), |s, &x| s + x * x).sqrt();
+            // Reflect onto -sign(x0)·‖x‖·e0 to avoid cancellation.
+            let alpha = if v[0] > T::zero() { -norm } else { norm };
+            v[0] = v[0] - alpha;
+            let v_norm = v.iter().fold(T::zero(), |s, &x| s + x * x).sqrt();
+            if v_norm > T::zero() {
+                v.iter_mut().for_each(|x| *x = *x / v_norm);
+                reflect_rows(&mut work, N, k, &v, k);
+            }
+            reflectors.push(v);
+        }
+
+        // Q = H_0·H_1·…·H_{N-1} applied to the first N columns of the identity.
+        q.fill(T::zero());
+        for i in 0..N {
+            q[i * N + i] = T::one();
+        }
+        for (k, v) in reflectors.iter().enumerate().rev() {
+            reflect_rows(q, N, k, v, 0);
+        }
+
+        for i in 0..N {
+            for j in 0..N {
+                r[i * N + j] = if j >= i { work[i * N + j] } else { T::zero() };
+            }
+            // Make the diagonal non-negative so the factorization is unique.
+            if r[i * N + i] < T::zero() {
+                (i..N).for_each(|j| r[i * N + j] = -r[i * N + j]);
+                (0..M).for_each(|row| q[row * N + i] = -q[row * N + i]);
+            }
+        }
+    }
+
+    fn qr_solve<const M: usize, const N: usize, const K: usize>(
+        q: &<Self as HasStorage<T, { M * N }>>::Storage,
+        r: &<Self as HasStorage<T, { N * N }>>::Storage,
+        b: &<Self as HasStorage<T, { M * K }>>::Storage,
+        x: &mut <Self as HasStorage<T, { N * K }>>::Storage,
+    ) -> Result<(), LinalgError>
+    where
+        Self: HasStorage<T, { M * N }>
+            + HasStorage<T, { N * N }>
+            + HasStorage<T, { M * K }>
+            + HasStorage<T, { N * K }>,
+    {
+        let q = <Self as HasStorage<T, { M * N }>>::as_slice(q);
+        let r = <Self as HasStorage<T, { N * N }>>::as_slice(r);
+        let b = <Self as HasStorage<T, { M * K }>>::as_slice(b);
+        let x = <Self as HasStorage<T, { N * K }>>::as_mut_slice(x);
+
+        let tol = pivot_tolerance(r, M);
+        if let Some(column) = (0..N).find(|&i| r[i * N + i] <= tol) {
+            return Err(LinalgError::RankDeficient { column });
+        }
+
+        // x = Qᵀ·b, then back-substitute through R.
+        for i in 0..N {
+            for c in 0..K {
+                x[i * K + c] = (0..M).fold(T::zero(), |s, row| s + q[row * N + i] * b[row * K + c]);
+            }
+        }
+        for i in (0..N).rev() {
+            for j in i + 1..N {
+                let u = r[i * N + j];
+                for c in 0..K {
+                    x[i * K + c] = x[i * K + c] - u * x[j * K + c];
+                }
+            }
+            for c in 0..K {
+                x[i * K + c] = x[i * K + c] / r[i * N + i];
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Applies `I - 2·v·vᵀ` to rows `k..` of the row-major `m`, which has `cols`
+/// columns, touching only columns `from..`.
+fn reflect_rows<T: Float>(m: &mut [T], cols: usize, k: usize, v: &[T], from: usize) {
+    let two = T::one() + T::one();
+    for j in from..cols {
+        let dot = v
+            .iter()
+            .enumerate()
+            .fold(T::zero(), |s, (i, &vi)| s + vi * m[(k + i) * cols + j]);
+        for (i, &vi) in v.iter().enumerate() {
+            let idx = (k + i) * cols + j;
+            m[idx] = m[idx] - two * vi * dot;
+        }
+    }
+}
+
+impl<T> Cholesky<T> for NaiveCpu
+where
+    T: Float + Default,
+{
+    fn cholesky_factor<const N: usize>(
+        a: &<Self as HasStorage<T, { N * N }>>::Storage,
+        l: &mut <Self as HasStorage<T, { N * N }>>::Storage,
+    ) -> Result<(), LinalgError>
+    where
+        Self: HasStorage<T, { N * N }>,
+    {
+        let a = <Self as HasStorage<T, { N * N }>>::as_slice(a);
+        let l = <Self as HasStorage<T, { N * N }>>::as_mut_slice(l);
+        l.fill(T::zero());
+
+        for j in 0..N {
+            let row_j =
+                |l: &[T], i: usize| (0..j).fold(T::zero(), |s, k| s + l[i * N + k] * l[j * N + k]);
+            let d = a[j * N + j] - row_j(l, j);
+            if d <= T::zero() || d.is_nan() {
+                return Err(LinalgError::NotPositiveDefinite { pivot: j });
+            }
+            let d = d.sqrt();
+            l[j * N + j] = d;
+            for i in j + 1..N {
+                l[i * N + j] = (a[i * N + j] - row_j(l, i)) / d;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Upper bound on Jacobi sweeps; both iterations below converge
+/// quadratically and normally stop after well under a dozen.
+const MAX_SWEEPS: usize = 64;
+
+impl<T> SvdDecomp<T> for NaiveCpu
+where
+    T: Float + Default,
+{
+    fn svd<const NA: usize, const NU: usize, const NS: usize, const NV: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        u: &mut <Self as HasStorage<T, NU>>::Storage,
+        s: &mut <Self as HasStorage<T, NS>>::Storage,
+        vt: &mut <Self as HasStorage<T, NV>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NU> + HasStorage<T, NS> + HasStorage<T, NV>,
+    {
+        let a = <Self as HasStorage<T, NA>>::as_slice(a);
+        let u = <Self as HasStorage<T, NU>>::as_mut_slice(u);
+        let s = <Self as HasStorage<T, NS>>::as_mut_slice(s);
+        let vt = <Self as HasStorage<T, NV>>::as_mut_slice(vt);
+        let k = min_dim(rows, cols);
+
+        for b in 0..batch {
+            let a = &a[b * rows * cols..(b + 1) * rows * cols];
+            let u = &mut u[b * rows * k..(b + 1) * rows * k];
+            let s = &mut s[b * k..(b + 1) * k];
+            let vt = &mut vt[b * k * cols..(b + 1) * k * cols];
+
+            // Work on the tall orientation: A = (Aᵀ)ᵀ = (U'·Σ·V'ᵀ)ᵀ = V'·Σ·U'ᵀ.
+            let (left, sigma, right) = if rows >= cols {
+                jacobi_svd(a, rows, cols)
+            } else {
+                let at: Vec<T> =
+                    (0..cols * rows).map(|i| a[(i % rows) * cols + i / rows]).collect();
+                let (left, sigma, right) = jacobi_svd(&at, cols, rows);
+                (right, sigma, left)
+            };
+            s.copy_from_slice(&sigma);
+            for j in 0..k {
+                let flip = column_sign(&left, rows, k, j);
+                (0..rows).for_each(|r| u[r * k + j] = left[r * k + j] * flip);
+                (0..cols).for_each(|c| vt[j * cols + c] = right[c * k + j] * flip);
+            }
+        }
+    }
+}
+
+/// One-sided (Hestenes) Jacobi SVD of a row-major `m x n` matrix with
+/// `m >= n`. Returns `U` (`m x n`), the singular values and `V` (`n x n`),
+/// with singular vectors as columns ordered by descending singular value.
+fn jacobi_svd<T: Float>(a: &[T], m: usize, n: usize) -> (Vec<T>, Vec<T>, Vec<T>) {
+    let mut w = a.to_vec();
+    let mut v = identity::<T>(n);
+
+    // Rotate column pairs of W = A·V until they are mutually orthogonal.
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let (mut alpha, mut beta, mut gamma) = (T::zero(), T::zero(), T::zero());
+                for i in 0..m {
+                    let (wp, wq) = (w[i * n + p], w[i * n + q]);
+                    alpha = alpha + wp * wp;
+                    beta = beta + wq * wq;
+                    gamma = gamma + wp * wq;
+                }
+                if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let (c, sn) = jacobi_rotation(alpha, beta, gamma);
+                rotate_columns(&mut w, n, p, q, c, sn);
+                rotate_columns(&mut v, n, p, q, c, sn);
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+
+    // The columns of W are now U·Σ.
+    let norms: Vec<T> = (0..n)
+        .map(|j| (0..m).fold(T::zero(), |acc, i| acc + w[i * n + j] * w[i * n + j]).sqrt())
+        .collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&x, &y| directed_cmp(&norms[x], &norms[y], true));
+
+    let tol = T::from(m).unwrap() * T::epsilon() * norms[order[0]];
+    let mut u = vec![T::zero(); m * n];
+    let mut vs = vec![T::zero(); n * n];
+    for (dst, &src) in order.iter().enumerate() {
+        let norm = norms[src];
+        if norm > tol {
+            (0..m).for_each(|i| u[i * n + dst] = w[i * n + src] / norm);
+        } else {
+            complete_orthonormal_column(&mut u, m, n, dst);
+        }
+        (0..n).for_each(|i| vs[i * n + dst] = v[i * n + src]);
+    }
+    (u, order.iter().map(|&j| norms[j]).collect(), vs)
+}
+
+/// Fills column `col` of the row-major `m x n` matrix `u` with a unit vector
+/// orthogonal to columns `..col`, by Gram-Schmidt on the standard basis.
+fn complete_orthonormal_column<T: Float>(u: &mut [T], m: usize, n: usize, col: usize) {
+    let project_out = |x: &mut [T]| {
+        for j in 0..col {
+            let d = (0..m).fold(T::zero(), |acc, i| acc + u[i * n + j] * x[i]);
+            (0..m).for_each(|i| x[i] = x[i] - d * u[i * n + j]);
+        }
+    };
+    // The squared projected norms of the `m` basis vectors sum to `m - col`,
+    // so the longest one keeps at least `1/sqrt(m)` of its length.
+    let mut best = vec![T::zero(); m];
+    let mut best_norm = T::zero();
+    for e in 0..m {
+        let mut x: Vec<T> = (0..m).map(|i| if i == e { T::one() } else { T::zero() }).collect();
+        project_out(&mut x);
+        let norm = x.iter().fold(T::zero(), |acc, &v| acc + v * v).sqrt();
+        if norm > best_norm {
+            (best, best_norm) = (x, norm);
+        }
+    }
+    // A second pass removes what rounding left of the earlier columns.
+    project_out(&mut best);
+    let norm = best.iter().fold(T::zero(), |acc, &v| acc + v * v).sqrt();
+    (0..m).for_each(|i| u[i * n + col] = best[i] / norm);
+}
+
+impl<T> EighDecomp<T> for NaiveCpu
+where
+    T: Float + Default,
+{
+    fn eigh<const NA: usize, const NW: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        n: usize,
+        w: &mut <Self as HasStorage<T, NW>>::Storage,
+        v: &mut <Self as HasStorage<T, NA>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NW>,
+    {
+        let a = <Self as HasStorage<T, NA>>::as_slice(a);
+        let w = <Self as HasStorage<T, NW>>::as_mut_slice(w);
+        let v = <Self as HasStorage<T, NA>>::as_mut_slice(v);
+
+        for b in 0..batch {
+            let a = &a[b * n * n..(b + 1) * n * n];
+            let (values, vectors) = jacobi_eigh(a, n);
+            w[b * n..(b + 1) * n].copy_from_slice(&values);
+            v[b * n * n..(b + 1) * n * n].copy_from_slice(&vectors);
+        }
+    }
+}
+
+/// Cyclic Jacobi eigenvalue iteration on a symmetric row-major `n x n`
+/// matrix. Returns ascending eigenvalues and eigenvectors as columns.
+fn jacobi_eigh<T: Float>(a: &[T], n: usize) -> (Vec<T>, Vec<T>) {
+    let mut m = a.to_vec();
+    let mut v = identity::<T>(n);
+    let scale = m.iter().fold(T::zero(), |acc, &x| acc + x * x);
+
+    for _ in 0..MAX_SWEEPS {
+        let off = (0..n)
+            .flat_map(|p| (p + 1..n).map(move |q| (p, q)))
+            .fold(T::zero(), |acc, (p, q)| acc + m[p * n + q] * m[p * n + q]);
+        if off <= T::epsilon() * T::epsilon() * scale {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = m[p * n + q];
+                if apq == T::zero() {
+                    continue;
+                }
+                // Zero m[p, q] with Jᵀ·M·J; `jacobi_rotation` solves the same
+                // 2x2 problem with alpha = m[p, p], beta = m[q, q].
+                let (c, sn) = jacobi_rotation(m[p * n + p], m[q * n + q], apq);
+                rotate_columns(&mut m, n, p, q, c, sn);
+                for k in 0..n {
+                    let (mp, mq) = (m[p * n + k], m[q * n + k]);
+                    m[p * n + k] = c * mp - sn * mq;
+                    m[q * n + k] = sn * mp + c * mq;
+                }
+                rotate_columns(&mut v, n, p, q, c, sn);
+                // Symmetrize away rounding in the zeroed pair.
+                m[p * n + q] = T::zero();
+                m[q * n + p] = T::zero();
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&x, &y| total_cmp(&m[x * n + x], &m[y * n + y]));
+    let values = order.iter().map(|&j| m[j * n + j]).collect();
+    let mut vectors = vec![T::zero(); n * n];
+    for (dst, &src) in order.iter().enumerate() {
+        (0..n).for_each(|i| vectors[i * n + dst] = v[i * n + src]);
+        let flip = column_sign(&vectors, n, n, dst);
+        (0..n).for_each(|i| vectors[i * n + dst] = vectors[i * n + dst] * flip);
+    }
+    (values, vectors)
+}
+
+/// `(c, s)` of the rotation that diagonalizes `[[alpha, gamma], [gamma,
+/// beta]]`, taking the smaller of the two possible angles.
+fn jacobi_rotation<T: Float>(alpha: T, beta: T, gamma: T) -> (T, T) {
+    let zeta = (beta - alpha) / (gamma + gamma);
+    let sign = if zeta >= T::zero() { T::one() } else { -T::one() };
+    let t = sign / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+    let c = T::one() / (T::one() + t * t).sqrt();
+    (c, c * t)
+}
+
+/// Replaces columns `p` and `q` of the row-major `cols`-wide matrix `m` with
+/// `c·m_p - s·m_q` and `s·m_p + c·m_q`.
+fn rotate_columns<T: Float>(m: &mut [T], cols: usize, p: usize, q: usize, c: T, s: T) {
+    for row in m.chunks_exact_mut(cols) {
+        let (mp, mq) = (row[p], row[q]);
+        row[p] = c * mp - s * mq;
+        row[q] = s * mp + c * mq;
+    }
+}
+
+/// `1` or `-1`, whichever makes the largest-magnitude entry of column `col`
+/// positive.
+fn column_sign<T: Float>(m: &[T], rows: usize, cols: usize, col: usize) -> T {
+    let largest = (0..rows)
+        .map(|i| m[i * cols + col])
+        .fold(T::zero(), |best, x| if x.abs() > best.abs() { x } else { best });
+    if largest < T::zero() { -T::one() } else { T::one() }
+}
+
+fn identity<T: Float>(n: usize) -> Vec<T> {
+    (0..n * n).map(|i| if i % (n + 1) == 0 { T::one() } else { T::zero() }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Tensor2, Tensor3};
+
+    fn assert_close(got: &[f64], want: &[f64]) {
+        for (g, w) in got.iter().zip(want) {
+            assert!((g - w).abs() < 1e-12, "got {got:?}, want {want:?}");
+        }
+    }
+
+    #[test]
+    fn test_lu_reconstructs_permuted_matrix() {
+        let a =
+            Tensor2::<f64, 3, 3, NaiveCpu>::new([2.0, 1.0, 1.0, 4.0, -6.0, 0.0, -2.0, 7.0, 2.0]);
+        let lu = a.lu().unwrap();
+        assert_eq!(lu.permutation().as_slice(), &[1, 0, 2]);
+
+        let rows = lu.permutation().as_slice().iter();
+        let pa: Vec<f64> = rows.flat_map(|&r| a.as_slice()[r * 3..][..3].to_vec()).collect();
+        assert_close((lu.l() * lu.u()).as_slice(), &pa);
+        assert_close(&[lu.det()], &[-16.0]);
+        assert_close(&[a.det()], &[-16.0]);
+    }
+
+    #[test]
+    fn test_solve_and_inverse() {
+        let a =
+            Tensor2::<f64, 3, 3, NaiveCpu>::new([4.0, -2.0, 1.0, -2.0, 4.0, -2.0, 1.0, -2.0, 4.0]);
+        let x = Tensor2::<f64, 3, 2, NaiveCpu>::new([1.0, 0.5, -2.0, 0.0, 3.0, -1.0]);
+        let b = a * x;
+        assert_close(a.solve(&b).unwrap().as_slice(), x.as_slice());
+
+        let inv = a.inverse().unwrap();
+        assert_close((a * inv).as_slice(), &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_det_has_no_singularity_tolerance() {
+        let a = Tensor2::<f64, 2, 2, NaiveCpu>::new([1.0, 0.0, 0.0, 1e-20]);
+        assert!(a.lu().is_err());
+        assert_eq!(a.det(), 1e-20);
+
+        let a = Tensor2::<f64, 3, 3, NaiveCpu>::new([1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 0.0, 1.0, 5.0]);
+        assert_eq!(a.det(), 0.0);
+    }
+
+    #[test]
+    fn test_singular_matrix_is_an_error() {
+        let a =
+            Tensor2::<f64, 3, 3, NaiveCpu>::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        assert_eq!(a.lu().err(), Some(LinalgError::Singular { pivot: 2 }));
+        assert!(a.inverse().is_err());
+        assert!(a.det().abs() < 1e-12);
+
+        let zero = Tensor2::<f32, 2, 2, NaiveCpu>::zeroes();
+        assert_eq!(zero.lu().err(), Some(LinalgError::Singular { pivot: 0 }));
+    }
+
+    #[test]
+    fn test_qr_matches_known_decomposition() {
+        // A = Q·R with Q = [[3/5, -4/5], [4/5, 3/5], [0, 0]] and R = [[5, 10], [0, 5]].
+        let a = Tensor2::<f64, 3, 2, NaiveCpu>::new([3.0, 2.0, 4.0, 11.0, 0.0, 0.0]);
+        let (q, r) = a.qr();
+        assert_close(q.as_slice(), &[0.6, -0.8, 0.8, 0.6, 0.0, 0.0]);
+        assert_close(r.as_slice(), &[5.0, 10.0, 0.0, 5.0]);
+
+        // Columns of Q stay orthonormal and Q·R reconstructs A.
+        let m = Tensor2::<f64, 4, 3, NaiveCpu>::new([
+            2.0, -1.0, 0.5, 1.0, 3.0, -2.0, 0.0, 1.0, 4.0, -3.0, 2.0, 1.0,
+        ]);
+        let (q, r) = m.qr();
+        let qtq = Tensor2::<f64, 3, 3, NaiveCpu>::zeroes().gemm(1.0, q.t(), &q, 0.0);
+        assert_close(qtq.as_slice(), &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+        assert_close((q * r).as_slice(), m.as_slice());
+        let below_diagonal = [r.as_slice()[3], r.as_slice()[6], r.as_slice()[7]];
+        assert_eq!(below_diagonal, [0.0; 3]);
+    }
+
+    #[test]
+    fn test_cholesky() {
+        // [[4, 2, -2], [2, 10, 2], [-2, 2, 6]] = L·Lᵀ, L = [[2, 0, 0], [1, 3, 0], [-1, 1, 2]].
+        let a =
+            Tensor2::<f64, 3, 3, NaiveCpu>::new([4.0, 2.0, -2.0, 2.0, 10.0, 2.0, -2.0, 2.0, 6.0]);
+        let l = a.cholesky().unwrap();
+        assert_close(l.as_slice(), &[2.0, 0.0, 0.0, 1.0, 3.0, 0.0, -1.0, 1.0, 2.0]);
+
+        let indefinite = Tensor2::<f64, 2, 2, NaiveCpu>::new([1.0, 2.0, 2.0, 1.0]);
+        let err = indefinite.cholesky().err();
+        assert_eq!(err, Some(LinalgError::NotPositiveDefinite { pivot: 1 }));
+    }
+
+    #[test]
+    fn test_lstsq() {
+        // Fit y = c0 + c1·x through (0, 1), (1, 2), (2, 2), (3, 4): c = [0.9, 0.9].
+        let a = Tensor2::<f64, 4, 2, NaiveCpu>::new([1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+        let b = Tensor2::<f64, 4, 1, NaiveCpu>::new([1.0, 2.0, 2.0, 4.0]);
+        assert_close(a.lstsq(&b).unwrap().as_slice(), &[0.9, 0.9]);
+
+        // A square, full-rank system is solved exactly.
+        let s = Tensor2::<f64, 2, 2, NaiveCpu>::new([2.0, 1.0, 1.0, 3.0]);
+        let rhs = Tensor2::<f64, 2, 2, NaiveCpu>::new([3.0, 1.0, 5.0, 2.0]);
+        let x = s.lstsq(&rhs).unwrap();
+        assert_close((s * x).as_slice(), rhs.as_slice());
+
+        let dependent = Tensor2::<f64, 3, 2, NaiveCpu>::new([1.0, 2.0, 2.0, 4.0, 3.0, 6.0]);
+        let b = Tensor2::<f64, 3, 1, NaiveCpu>::new([1.0, 2.0, 3.0]);
+        let err = dependent.lstsq(&b).err();
+        assert_eq!(err, Some(LinalgError::RankDeficient { column: 1 }));
+    }
+
+    /// `U·diag(s)·Vᵀ` for row-major `u` (`m x k`) and `vt` (`k x n`).
+    fn reconstruct(u: &[f64], s: &[f64], vt: &[f64], m: usize, n: usize) -> Vec<f64> {
+        let k = s.len();
+        (0..m * n)
+            .map(|i| (0..k).map(|j| u[i / n * k + j] * s[j] * vt[j * n + i % n]).sum())
+            .collect()
+    }
+
+    /// `QᵀQ = I` for the row-major `m x n` matrix `q`.
+    fn assert_orthonormal_columns(q: &[f64], m: usize, n: usize) {
+        let qtq: Vec<f64> = (0..n * n)
+            .map(|i| (0..m).map(|r| q[r * n + i / n] * q[r * n + i % n]).sum())
+            .collect();
+        assert_close(&qtq, &identity(n));
+    }
+
+    #[test]
+    fn test_svd_matches_known_decomposition() {
+        let h = 0.5f64.sqrt();
+        let a = Tensor2::<f64, 2, 3, NaiveCpu>::new([3.0, 2.0, 2.0, 2.0, 3.0, -2.0]);
+        let (u, s, vt) = a.svd();
+        assert_close(u.as_slice(), &[h, h, h, -h]);
+        assert_close(s.as_slice(), &[5.0, 3.0]);
+        let v2 = h / 3.0;
+        assert_close(vt.as_slice(), &[h, h, 0.0, v2, -v2, 4.0 * v2]);
+
+        // The transpose has the same singular values with U and V swapped.
+        let at = Tensor2::<f64, 3, 2, NaiveCpu>::new([3.0, 2.0, 2.0, 3.0, 2.0, -2.0]);
+        let (u_t, s_t, vt_t) = at.svd();
+        assert_close(s_t.as_slice(), &[5.0, 3.0]);
+        assert_close(u_t.as_slice(), &[h, v2, h, -v2, 0.0, 4.0 * v2]);
+        assert_close(vt_t.as_slice(), &[h, h, h, -h]);
+    }
+
+    #[test]
+    fn test_svd_rank_deficient_and_batched() {
+        // Rank 2: the third column is the sum of the first two.
+        let data = [1.0, 2.0, 3.0, 4.0, 0.0, 4.0, -1.0, 1.0, 0.0, 2.0, 5.0, 7.0];
+        let a = Tensor2::<f64, 4, 3, NaiveCpu>::new(data);
+        let (u, s, vt) = a.svd();
+        assert!(s.as_slice()[0] >= s.as_slice()[1] && s.as_slice()[2].abs() < 1e-12);
+        assert_close(&reconstruct(u.as_slice(), s.as_slice(), vt.as_slice(), 4, 3), &data);
+        // U keeps orthonormal columns even for the zero singular value.
+        let utu = Tensor2::<f64, 3, 3, NaiveCpu>::zeroes().gemm(1.0, u.t(), &u, 0.0);
+        assert_close(utu.as_slice(), &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+
+        let other = [2.0, 0.0, 1.0, 0.0, 3.0, 0.0, 1.0, 0.0, 2.0, 0.0, 0.0, 1.0];
+        let mut stacked = [0.0; 24];
+        stacked[..12].copy_from_slice(&data);
+        stacked[12..].copy_from_slice(&other);
+        let (bu, bs, bvt) = Tensor3::<f64, 2, 4, 3, NaiveCpu>::new(stacked).svd();
+        let (ou, os, ovt) = Tensor2::<f64, 4, 3, NaiveCpu>::new(other).svd();
+        assert_close(bu.as_slice(), &[u.as_slice(), ou.as_slice()].concat());
+        assert_close(bs.as_slice(), &[s.as_slice(), os.as_slice()].concat());
+        assert_close(bvt.as_slice(), &[vt.as_slice(), ovt.as_slice()].concat());
+    }
+
+    /// Rank 4: the rows sum to zero, so the null left singular vector has the
+    /// same weight, `1/sqrt(5)`, on every row.
+    const ROWS_SUM_TO_ZERO: [f64; 25] = [
+        1.0, 2.0, 0.0, -1.0, 3.0, //
+        0.0, 1.0, 4.0, 2.0, -1.0, //
+        2.0, -1.0, 1.0, 0.0, 1.0, //
+        -1.0, 0.0, 2.0, 3.0, 1.0, //
+        -2.0, -2.0, -7.0, -4.0, -4.0,
+    ];
+
+    #[test]
+    fn test_svd_completes_a_null_vector_spread_over_every_row() {
+        let (u, s, vt) = Tensor2::<f64, 5, 5, NaiveCpu>::new(ROWS_SUM_TO_ZERO).svd();
+        assert!(s.as_slice()[3] > 1e-6 && s.as_slice()[4].abs() < 1e-12);
+        assert_orthonormal_columns(u.as_slice(), 5, 5);
+        let r = reconstruct(u.as_slice(), s.as_slice(), vt.as_slice(), 5, 5);
+        assert_close(&r, &ROWS_SUM_TO_ZERO);
+    }
+
+    #[test]
+    fn test_batched_rank_deficient_decompositions_stay_orthonormal() {
+        // Rank 3: the third row is the sum of the first two, the fifth the
+        // negated fourth.
+        let row = |r: usize| &ROWS_SUM_TO_ZERO[r * 5..][..5];
+        let mut data = ROWS_SUM_TO_ZERO.to_vec();
+        data.extend_from_slice(row(0));
+        data.extend_from_slice(row(1));
+        data.extend((0..5).map(|c| row(0)[c] + row(1)[c]));
+        data.extend_from_slice(row(3));
+        data.extend(row(3).iter().map(|x| -x));
+        let a = Tensor3::<f64, 2, 5, 5, NaiveCpu>::new_from_slice(&data);
+        let (u, s, vt) = a.svd();
+        for b in 0..2 {
+            let (ub, sb) = (&u.as_slice()[b * 25..][..25], &s.as_slice()[b * 5..][..5]);
+            assert_orthonormal_columns(ub, 5, 5);
+            let r = reconstruct(ub, sb, &vt.as_slice()[b * 25..][..25], 5, 5);
+            assert_close(&r, &data[b * 25..][..25]);
+        }
+        assert!(s.as_slice()[8].abs() < 1e-12 && s.as_slice()[9].abs() < 1e-12);
+
+        // Rank 1 and rank 3, with repeated zero eigenvalues.
+        let mut sym = [1.0; 50];
+        sym[25..].copy_from_slice(&[
+            1.0, 1.0, 0.0, 0.0, 0.0, //
+            1.0, 1.0, 0.0, 0.0, 0.0, //
+            0.0, 0.0, 2.0, 0.0, 0.0, //
+            0.0, 0.0, 0.0, 0.0, 0.0, //
+            0.0, 0.0, 0.0, 0.0, 3.0,
+        ]);
+        let (w, v) = Tensor3::<f64, 2, 5, 5, NaiveCpu>::new(sym).eigh();
+        assert_close(w.as_slice(), &[0.0, 0.0, 0.0, 0.0, 5.0, 0.0, 0.0, 2.0, 2.0, 3.0]);
+        for b in 0..2 {
+            assert_orthonormal_columns(&v.as_slice()[b * 25..][..25], 5, 5);
+        }
+    }
+
+    #[test]
+    fn test_eigh() {
+        let h = 0.5f64.sqrt();
+        let a = Tensor2::<f64, 2, 2, NaiveCpu>::new([2.0, 1.0, 1.0, 2.0]);
+        let (w, v) = a.eigh();
+        assert_close(w.as_slice(), &[1.0, 3.0]);
+        assert_close(v.as_slice(), &[h, h, -h, h]);
+
+        let data = [4.0, 1.0, -2.0, 1.0, 3.0, 0.5, -2.0, 0.5, 1.0];
+        let (w3, v3) = Tensor2::<f64, 3, 3, NaiveCpu>::new(data).eigh();
+        assert!(w3.as_slice().windows(2).all(|p| p[0] <= p[1]));
+        let vt3: Vec<f64> = (0..9).map(|i| v3.as_slice()[i % 3 * 3 + i / 3]).collect();
+        assert_close(&reconstruct(v3.as_slice(), w3.as_slice(), &vt3, 3, 3), &data);
+
+        let mut stacked = [0.0; 18];
+        stacked[..9].copy_from_slice(&data);
+        stacked[9..].copy_from_slice(&[1.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 2.0]);
+        let (bw, bv) = Tensor3::<f64, 2, 3, 3, NaiveCpu>::new(stacked).eigh();
+        assert_close(bw.as_slice(), &[w3.as_slice(), &[-1.0, 1.0, 2.0]].concat());
+        let permuted = [0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+        assert_close(bv.as_slice(), &[v3.as_slice(), &permuted].concat());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::linalg::{Cholesky, EighDecomp, LinalgError, LuDecomp, QrDecomp, SvdDecomp};
+use num_traits::Float;
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> LuDecomp<T> for ParallelCpu
+where
+    T: Float + Default,
+{
+    fn lu_factor<const N: usize>(
+        a: &<Self as HasStorage<T, { N * N }>>::Storage,
+        lu: &mut <Self as HasStorage<T, { N * N }>>::Storage,
+        perm: &mut <Self as HasStorage<usize, N>>::Storage,
+    ) -> Result<T, LinalgError>
+    where
+        Self: HasStorage<T, { N * N }> + HasStorage<usize, N>,
+    {
+        <NaiveCpu as LuDecomp<T>>::lu_factor::<N>(
+            naive::<T, { N * N }>(a),
+            naive_mut::<T, { N * N }>(lu),
+            naive_mut::<usize, N>(perm),
+        )
+    }
+
+    fn lu_det<const N: usize>(a: &<Self as HasStorage<T, { N * N }>>::Storage) -> T
+    where
+        Self: HasStorage<T, { N * N }> + HasStorage<usize, N>,
+    {
+        <NaiveCpu as LuDecomp<T>>::lu_det::<N>(naive::<T, { N * N }>(a))
+    }
+
+    fn lu_solve<const N: usize, const K: usize>(
+        lu: &<Self as HasStorage<T, { N * N }>>::Storage,
+        perm: &<Self as HasStorage<usize, N>>::Storage,
+        b: &<Self as HasStorage<T, { N * K }>>::Storage,
+        x: &mut <Self as HasStorage<T, { N * K }>>::Storage,
+    ) where
+        Self: HasStorage<T, { N * N }> + HasStorage<usize, N> + HasStorage<T, { N * K }>,
+    {
+        <NaiveCpu as LuDecomp<T>>::lu_solve::<N, K>(
+            naive::<T, { N * N }>(lu),
+            naive::<usize, N>(perm),
+            naive::<T, { N * K }>(b),
+            naive_mut::<T, { N * K }>(x),
+        )
+    }
+}
+
+impl<T> QrDecomp<T> for ParallelCpu
+where
+    T: Float + Default,
+{
+    fn qr_factor<const M: usize, const N: usize>(
+        a: &<Self as HasStorage<T, { M * N }>>::Storage,
+        q: &mut <Self as HasStorage<T, { M * N }>>::Storage,
+        r: &mut <Self as HasStorage<T, { N * N }>>::Storage,
+    ) where
+        Self: HasStorage<T, { M * N }> + HasStorage<T, { N * N }>,
+    {
+        <NaiveCpu as QrDecomp<T>>::qr_factor::<M, N>(
+            naive::<T, { M * N }>(a),
+            naive_mut::<T, { M * N }>(q),
+            naive_mut::<T, { N * N }>(r),
+        )
+    }
+
+    fn qr_solve<const M: usize, const N: usize, const K: usize>(
+        q: &<Self as HasStorage<T, { M * N }>>::Storage,
+        r: &<Self as HasStorage<T, { N * N }>>::Storage,
+        b: &<Self as HasStorage<T, { M * K }>>::Storage,
+        x: &mut <Self as HasStorage<T, { N * K }>>::Storage,
+    ) -> Result<(), LinalgError>
+    where
+        Self: HasStorage<T, { M * N }>
+            + HasStorage<T, { N * N }>
+            + HasStorage<T, { M * K }>
+            + HasStorage<T, { N * K }>,
+    {
+        <NaiveCpu as QrDecomp<T>>::qr_solve::<M, N, K>(
+            naive::<T, { M * N }>(q),
+            naive::<T, { N * N }>(r),
+            naive::<T, { M * K }>(b),
+            naive_mut::<T, { N * K }>(x),
+        )
+    }
+}
+
+impl<T> Cholesky<T> for ParallelCpu
+where
+    T: Float + Default,
+{
+    fn cholesky_factor<const N: usize>(
+        a: &<Self as HasStorage<T, { N * N }>>::Storage,
+        l: &mut <Self as HasStorage<T, { N * N }>>::Storage,
+    ) -> Result<(), LinalgError>
+    where
+        Self: HasStorage<T, { N * N }>,
+    {
+        <NaiveCpu as Cholesky<T>>::cholesky_factor::<N>(
+            naive::<T, { N * N }>(a),
+            naive_mut::<T, { N * N }>(l),
+        )
+    }
+}
+
+impl<T> SvdDecomp<T> for ParallelCpu
+where
+    T: Float + Default,
+{
+    fn svd<const NA: usize, const NU: usize, const NS: usize, const NV: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        u: &mut <Self as HasStorage<T, NU>>::Storage,
+        s: &mut <Self as HasStorage<T, NS>>::Storage,
+        vt: &mut <Self as HasStorage<T, NV>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NU> + HasStorage<T, NS> + HasStorage<T, NV>,
+    {
+        <NaiveCpu as SvdDecomp<T>>::svd::<NA, NU, NS, NV>(
+            naive::<T, NA>(a),
+            batch,
+            rows,
+            cols,
+            naive_mut::<T, NU>(u),
+            naive_mut::<T, NS>(s),
+            naive_mut::<T, NV>(vt),
+        )
+    }
+}
+
+impl<T> EighDecomp<T> for ParallelCpu
+where
+    T: Float + Default,
+{
+    fn eigh<const NA: usize, const NW: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        n: usize,
+        w: &mut <Self as HasStorage<T, NW>>::Storage,
+        v: &mut <Self as HasStorage<T, NA>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NW>,
+    {
+        <NaiveCpu as EighDecomp<T>>::eigh::<NA, NW>(
+            naive::<T, NA>(a),
+            batch,
+            n,
+            naive_mut::<T, NW>(w),
+            naive_mut::<T, NA>(v),
+        )
+    }
+}
@@ -1,4 +1,5 @@
 pub mod naive_cpu;
+pub mod parallel_cpu;
 
 use crate::storage::HasStorage;
 use crate::tensor::{Tensor2, Tensor3, Tensor4};
@@ -12,6 +13,11 @@ pub trait Log<T: Copy + Default>: Sized {
     ) where
         T: Float,
         Self: HasStorage<T, N>;
+
+    fn log_inplace<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage)
+    where
+        T: Float,
+        Self: HasStorage<T, N>;
 }
 
 impl<T, const R: usize, const C: usize, B> Tensor2<T, R, C, B>
@@ -28,6 +34,12 @@ where
             _p: core::marker::PhantomData,
         }
     }
+
+    /// In-place variant of [`Self::log`].
+    #[inline]
+    pub fn log_(&mut self) {
+        B::log_inplace::<{ R * C }>(&mut self.storage);
+    }
 }
 
 impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
@@ -45,6 +57,12 @@ where
             _p: core::marker::PhantomData,
         }
     }
+
+    /// In-place variant of [`Self::log`].
+    #[inline]
+    pub fn log_(&mut self) {
+        B::log_inplace::<{ D0 * (D1 * D2) }>(&mut self.storage);
+    }
 }
 
 impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
@@ -63,4 +81,10 @@ where
             _p: core::marker::PhantomData,
         }
     }
+
+    /// In-place variant of [`Self::log`].
+    #[inline]
+    pub fn log_(&mut self) {
+        B::log_inplace::<{ D0 * (D1 * (D3 * D4)) }>(&mut self.storage);
+    }
 }
@@ -19,6 +19,15 @@ where
             dst[i] = src[i].ln();
         }
     }
+
+    fn log_inplace<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage)
+    where
+        Self: HasStorage<T, N>,
+    {
+        for v in <Self as HasStorage<T, N>>::as_mut_slice(a).iter_mut() {
+            *v = v.ln();
+        }
+    }
 }
 
 #[cfg(test)]
@@ -36,4 +45,14 @@ mod tests {
         assert!((s[2] - 2.0).abs() < 1e-6);
         assert!((s[3] - 3.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_log_exp_inplace_roundtrip() {
+        let mut t = Tensor2::<f64, 1, 3, NaiveCpu>::new([0.5, 1.0, 4.0]);
+        t.log_();
+        t.exp_();
+        for (got, want) in t.as_slice().iter().zip([0.5, 1.0, 4.0]) {
+            assert!((got - want).abs() < 1e-12);
+        }
+    }
 }
@@ -0,0 +1,27 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::log::Log;
+use num_traits::Float;
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> Log<T> for ParallelCpu
+where
+    T: Copy + Default + Float,
+{
+    fn log<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Log<T>>::log::<N>(naive::<T, N>(a), naive_mut::<T, N>(out))
+    }
+
+    fn log_inplace<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage)
+    where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Log<T>>::log_inplace::<N>(naive_mut::<T, N>(a))
+    }
+}
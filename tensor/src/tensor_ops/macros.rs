@@ -0,0 +1,134 @@
+//! Helpers for stamping out operator impls over owned and borrowed operands.
+//!
+//! Each arm takes the generic parameter list in brackets, the operator trait
+//! and method, the operand types and a body written against borrowed operands.
+//! The body only ever reads `storage` through the references it is handed, so
+//! the same code serves every owned/borrowed combination.
+
+/// Binary operator: implements `lhs op rhs`, `&lhs op &rhs`, `&lhs op rhs` and
+/// `lhs op &rhs`.
+macro_rules! impl_binop {
+    (
+        impl<[$($gen:tt)*]> $tr:ident::$method:ident($lhs:ty, $rhs:ty) -> $out:ty
+        where { $($bounds:tt)* }
+        |$a:ident, $b:ident| $body:block
+    ) => {
+        impl<$($gen)*> $tr<$rhs> for $lhs
+        where
+            $($bounds)*
+        {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                let ($a, $b) = (&self, &rhs);
+                $body
+            }
+        }
+
+        impl<'a, 'b, $($gen)*> $tr<&'b $rhs> for &'a $lhs
+        where
+            $($bounds)*
+        {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, rhs: &'b $rhs) -> Self::Output {
+                let ($a, $b) = (self, rhs);
+                $body
+            }
+        }
+
+        impl<'a, $($gen)*> $tr<$rhs> for &'a $lhs
+        where
+            $($bounds)*
+        {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                let ($a, $b) = (self, &rhs);
+                $body
+            }
+        }
+
+        impl<'b, $($gen)*> $tr<&'b $rhs> for $lhs
+        where
+            $($bounds)*
+        {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, rhs: &'b $rhs) -> Self::Output {
+                let ($a, $b) = (&self, rhs);
+                $body
+            }
+        }
+    };
+}
+
+/// Tensor-scalar operator: implements `lhs op k` and `&lhs op k`.
+macro_rules! impl_scalar_op {
+    (
+        impl<[$($gen:tt)*]> $tr:ident::$method:ident($lhs:ty, $rhs:ty) -> $out:ty
+        where { $($bounds:tt)* }
+        |$a:ident, $k:ident| $body:block
+    ) => {
+        impl<$($gen)*> $tr<$rhs> for $lhs
+        where
+            $($bounds)*
+        {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                let ($a, $k) = (&self, rhs);
+                $body
+            }
+        }
+
+        impl<'a, $($gen)*> $tr<$rhs> for &'a $lhs
+        where
+            $($bounds)*
+        {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                let ($a, $k) = (self, rhs);
+                $body
+            }
+        }
+    };
+}
+
+/// Compound assignment operator: implements `lhs op= rhs` and `lhs op= &rhs`.
+macro_rules! impl_assign_op {
+    (
+        impl<[$($gen:tt)*]> $tr:ident::$method:ident($lhs:ty, $rhs:ty)
+        where { $($bounds:tt)* }
+        |$a:ident, $b:ident| $body:block
+    ) => {
+        impl<$($gen)*> $tr<$rhs> for $lhs
+        where
+            $($bounds)*
+        {
+            #[inline]
+            fn $method(&mut self, rhs: $rhs) {
+                let ($a, $b) = (self, &rhs);
+                $body
+            }
+        }
+
+        impl<'b, $($gen)*> $tr<&'b $rhs> for $lhs
+        where
+            $($bounds)*
+        {
+            #[inline]
+            fn $method(&mut self, rhs: &'b $rhs) {
+                let ($a, $b) = (self, rhs);
+                $body
+            }
+        }
+    };
+}
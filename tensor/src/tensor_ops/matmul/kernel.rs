@@ -0,0 +1,131 @@
+//! Cache-blocked matmul kernel shared by the CPU backends.
+//!
+//! `f32` and `f64` go through a packed, `std::simd` micro-kernel; every other
+//! element type uses a scalar loop ordered for contiguous access to `b`.
+
+use core::any::TypeId;
+use core::ops::{Add, AddAssign, Mul};
+use std::simd::{LaneCount, Simd, SimdElement, SupportedLaneCount};
+
+/// Rows of `a` handled per micro-kernel call.
+const MR: usize = 4;
+/// Depth of a packed block of `b`, sized so a block stays in L2.
+const KC: usize = 256;
+/// Columns of a packed block of `b`.
+const NC: usize = 256;
+
+/// `out = a * b` for row-major `a: m x k`, `b: k x n` and `out: m x n`.
+pub(crate) fn matmul_into<T>(a: &[T], b: &[T], out: &mut [T], m: usize, k: usize, n: usize)
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + 'static,
+{
+    if let (Some(a), Some(b), Some(out)) = (cast::<T, f32>(a), cast::<T, f32>(b), cast_mut(out)) {
+        return simd_matmul::<f32, 8>(a, b, out, m, k, n);
+    }
+    if let (Some(a), Some(b), Some(out)) = (cast::<T, f64>(a), cast::<T, f64>(b), cast_mut(out)) {
+        return simd_matmul::<f64, 4>(a, b, out, m, k, n);
+    }
+
+    out[..m * n].fill(T::default());
+    for i in 0..m {
+        let row = &mut out[i * n..(i + 1) * n];
+        for p in 0..k {
+            let a_ip = a[i * k + p];
+            for (o, &b_pj) in row.iter_mut().zip(&b[p * n..(p + 1) * n]) {
+                *o = *o + a_ip * b_pj;
+            }
+        }
+    }
+}
+
+/// Reinterprets `s` as `&[U]` when `T` and `U` are the same type.
+fn cast<T: 'static, U: 'static>(s: &[T]) -> Option<&[U]> {
+    if TypeId::of::<T>() == TypeId::of::<U>() {
+        // SAFETY: `T` and `U` are the same type.
+        Some(unsafe { core::slice::from_raw_parts(s.as_ptr().cast(), s.len()) })
+    } else {
+        None
+    }
+}
+
+fn cast_mut<T: 'static, U: 'static>(s: &mut [T]) -> Option<&mut [U]> {
+    if TypeId::of::<T>() == TypeId::of::<U>() {
+        // SAFETY: `T` and `U` are the same type.
+        Some(unsafe { core::slice::from_raw_parts_mut(s.as_mut_ptr().cast(), s.len()) })
+    } else {
+        None
+    }
+}
+
+fn simd_matmul<T, const L: usize>(a: &[T], b: &[T], out: &mut [T], m: usize, k: usize, n: usize)
+where
+    T: SimdElement + Default + Add<Output = T> + Mul<Output = T>,
+    LaneCount<L>: SupportedLaneCount,
+    Simd<T, L>: AddAssign + Mul<Output = Simd<T, L>>,
+{
+    out[..m * n].fill(T::default());
+    let mut packed = vec![T::default(); KC.min(k) * NC.min(n)];
+
+    for jc in (0..n).step_by(NC) {
+        let nc = NC.min(n - jc);
+        for pc in (0..k).step_by(KC) {
+            let kc = KC.min(k - pc);
+            // Pack the `kc x nc` block of `b` contiguously.
+            for p in 0..kc {
+                let src = (pc + p) * n + jc;
+                packed[p * nc..(p + 1) * nc].copy_from_slice(&b[src..src + nc]);
+            }
+            for i0 in (0..m).step_by(MR) {
+                let mr = MR.min(m - i0);
+                micro_kernel::<T, L>(a, &packed, out, (i0, mr), (pc, kc), (jc, nc), k, n);
+            }
+        }
+    }
+}
+
+/// Accumulates `a[i0..i0 + mr, pc..pc + kc] * packed` into the matching
+/// `mr x nc` block of `out`, `L` columns at a time.
+#[allow(clippy::too_many_arguments)]
+#[inline(always)]
+fn micro_kernel<T, const L: usize>(
+    a: &[T],
+    packed: &[T],
+    out: &mut [T],
+    (i0, mr): (usize, usize),
+    (pc, kc): (usize, usize),
+    (jc, nc): (usize, usize),
+    k: usize,
+    n: usize,
+) where
+    T: SimdElement + Default + Add<Output = T> + Mul<Output = T>,
+    LaneCount<L>: SupportedLaneCount,
+    Simd<T, L>: AddAssign + Mul<Output = Simd<T, L>>,
+{
+    let full = nc - nc % L;
+    for j in (0..full).step_by(L) {
+        let mut acc = [Simd::<T, L>::splat(T::default()); MR];
+        for p in 0..kc {
+            let bv = Simd::from_slice(&packed[p * nc + j..]);
+            for (r, acc) in acc.iter_mut().enumerate().take(mr) {
+                *acc += Simd::splat(a[(i0 + r) * k + pc + p]) * bv;
+            }
+        }
+        for (r, acc) in acc.iter().enumerate().take(mr) {
+            let dst = &mut out[(i0 + r) * n + jc + j..][..L];
+            let mut sum = Simd::from_slice(dst);
+            sum += *acc;
+            sum.copy_to_slice(dst);
+        }
+    }
+    // Columns left over after the last full vector.
+    for r in 0..mr {
+        for j in full..nc {
+            let mut acc = T::default();
+            for p in 0..kc {
+                acc = acc + a[(i0 + r) * k + pc + p] * packed[p * nc + j];
+            }
+            let dst = &mut out[(i0 + r) * n + jc + j];
+            *dst = *dst + acc;
+        }
+    }
+}
@@ -89,7 +89,7 @@ mod bench {
         let b_mat = Tensor2::<f32, N, N, MetalGpu>::new([0.0; N * N]);
 
         b.iter(|| {
-            test::black_box(a.clone() * b_mat.clone());
+            test::black_box(&a * &b_mat);
         });
     }
 }
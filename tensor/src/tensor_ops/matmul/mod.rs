@@ -1,6 +1,8 @@
+pub(crate) mod kernel;
 #[cfg(target_os = "macos")]
 mod metal_gpu;
 pub mod naive_cpu;
+pub mod parallel_cpu;
 
 use crate::storage::HasStorage;
 use crate::tensor::Tensor2;
@@ -16,24 +18,25 @@ pub trait MatMul<T: Copy + Default>: Sized {
         Self: HasStorage<T, { R * C }> + HasStorage<T, { C * K }> + HasStorage<T, { R * K }>;
 }
 
-impl<T, const R: usize, const C: usize, const K: usize, B> Mul<Tensor2<T, C, K, B>>
-    for Tensor2<T, R, C, B>
-where
-    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
-    B: MatMul<T> + HasStorage<T, { R * C }> + HasStorage<T, { C * K }> + HasStorage<T, { R * K }>,
-{
-    type Output = Tensor2<T, R, K, B>;
-
-    #[inline]
-    fn mul(self, rhs: Tensor2<T, C, K, B>) -> Self::Output {
+impl_binop! {
+    impl<[T, const R: usize, const C: usize, const K: usize, B]>
+    Mul::mul(Tensor2<T, R, C, B>, Tensor2<T, C, K, B>) -> Tensor2<T, R, K, B>
+    where {
+        T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+        B: MatMul<T>
+            + HasStorage<T, { R * C }>
+            + HasStorage<T, { C * K }>
+            + HasStorage<T, { R * K }>,
+    }
+    |a, b| {
         // Allocate the output buffer using the backend
         let mut out: <B as HasStorage<T, { R * K }>>::Storage =
             <B as HasStorage<T, { R * K }>>::storage_uninit();
 
         // Delegate to the backend’s implementation
-        B::matmul::<R, C, K>(&self.storage, &rhs.storage, &mut out);
+        B::matmul::<R, C, K>(&a.storage, &b.storage, &mut out);
 
-        Self::Output {
+        Tensor2 {
             storage: out,
             _p: core::marker::PhantomData,
         }
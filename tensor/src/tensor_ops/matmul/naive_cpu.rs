@@ -1,14 +1,17 @@
-//! NaiveCPU backend – reference “school‑book” implementation.
-//! Works for any `T` that supports `Default + Add + Mul`.
+//! NaiveCPU backend, built on the cache-blocked kernel in
+//! [`kernel`](crate::tensor_ops::matmul::kernel).
+//! Works for any `T` that supports `Default + Add + Mul`; `f32` and `f64` are
+//! SIMD-vectorized.
 
 use crate::storage::HasStorage;
 use crate::storage::naive_cpu::NaiveCpu;
 use crate::tensor_ops::matmul::MatMul;
+use crate::tensor_ops::matmul::kernel::matmul_into;
 use core::ops::{Add, Mul};
 
 impl<T> MatMul<T> for NaiveCpu
 where
-    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + 'static,
 {
     fn matmul<const R: usize, const C: usize, const K: usize>(
         a: &<Self as HasStorage<T, { R * C }>>::Storage,
@@ -22,15 +25,61 @@ where
         let b = <Self as HasStorage<T, { C * K }>>::as_slice(b);
         let out = <Self as HasStorage<T, { R * K }>>::as_mut_slice(out);
 
-        for r in 0..R {
-            for k in 0..K {
-                let mut acc = T::default();
-                for c in 0..C {
-                    acc = acc + a[r * C + c] * b[c * K + k];
-                }
-                out[r * K + k] = acc;
+        matmul_into(a, b, out, R, C, K);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::tensor::{Tensor2, Tensor3};
+
+    /// Shapes chosen so the blocked kernel hits a partial depth block, a
+    /// partial row group and a scalar column tail.
+    const M: usize = 6;
+    const K: usize = 259;
+    const N: usize = 19;
+
+    fn reference(a: &[f64], b: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; M * N];
+        for i in 0..M {
+            for j in 0..N {
+                out[i * N + j] = (0..K).map(|p| a[i * K + p] * b[p * N + j]).sum();
             }
         }
+        out
+    }
+
+    #[test]
+    fn test_blocked_kernel_matches_reference() {
+        let a: Vec<f64> = (0..M * K).map(|i| ((i * 7) % 13) as f64 - 6.0).collect();
+        let b: Vec<f64> = (0..K * N).map(|i| ((i * 5) % 11) as f64 * 0.5).collect();
+        let want = reference(&a, &b);
+
+        let got = Tensor2::<f64, M, K, NaiveCpu>::new_from_slice(&a)
+            * Tensor2::<f64, K, N, NaiveCpu>::new_from_slice(&b);
+        assert_eq!(got.as_slice(), &want[..]);
+
+        let a32: Vec<f32> = a.iter().map(|&v| v as f32).collect();
+        let b32: Vec<f32> = b.iter().map(|&v| v as f32).collect();
+        let got = Tensor2::<f32, M, K, NaiveCpu>::new_from_slice(&a32)
+            * Tensor2::<f32, K, N, NaiveCpu>::new_from_slice(&b32);
+        for (g, w) in got.as_slice().iter().zip(&want) {
+            assert!((*g as f64 - w).abs() < 1e-3, "{g} vs {w}");
+        }
+
+        // Non-float element types take the scalar path.
+        let ai: Vec<i64> = a.iter().map(|&v| v as i64).collect();
+        let bi: Vec<i64> = b.iter().map(|&v| (v * 2.0) as i64).collect();
+        let got = Tensor2::<i64, M, K, NaiveCpu>::new_from_slice(&ai)
+            * Tensor2::<i64, K, N, NaiveCpu>::new_from_slice(&bi);
+        let want_i: Vec<i64> = want.iter().map(|&v| (v * 2.0) as i64).collect();
+        assert_eq!(got.as_slice(), &want_i[..]);
+
+        // Broadcast matmul stacks batches into the same kernel.
+        let batched = Tensor3::<f64, 2, 3, K, NaiveCpu>::new_from_slice(&a)
+            * Tensor2::<f64, K, N, NaiveCpu>::new_from_slice(&b);
+        assert_eq!(batched.as_slice(), &want[..]);
     }
 }
 
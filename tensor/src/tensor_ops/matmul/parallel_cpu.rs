@@ -0,0 +1,83 @@
+use crate::storage::HasStorage;
+use crate::storage::parallel_cpu::{ParallelCpu, for_each_chunk};
+use crate::tensor_ops::matmul::MatMul;
+use crate::tensor_ops::matmul::kernel::matmul_into;
+use core::ops::{Add, Mul};
+
+/// Fewest output rows worth giving a thread of their own.
+pub(crate) const MIN_ROWS_PER_THREAD: usize = 8;
+
+/// `out = a * b`, with bands of output rows computed on separate threads.
+pub(crate) fn par_matmul_into<T>(a: &[T], b: &[T], out: &mut [T], m: usize, k: usize, n: usize)
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync + 'static,
+{
+    for_each_chunk(&mut out[..m * n], n, MIN_ROWS_PER_THREAD, |row, band| {
+        let rows = band.len() / n.max(1);
+        matmul_into(&a[row * k..(row + rows) * k], b, band, rows, k, n);
+    });
+}
+
+impl<T> MatMul<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Send + Sync + 'static,
+{
+    fn matmul<const R: usize, const C: usize, const K: usize>(
+        a: &<Self as HasStorage<T, { R * C }>>::Storage,
+        b: &<Self as HasStorage<T, { C * K }>>::Storage,
+        out: &mut <Self as HasStorage<T, { R * K }>>::Storage,
+    ) where
+        Self: HasStorage<T, { R * C }> + HasStorage<T, { C * K }> + HasStorage<T, { R * K }>,
+    {
+        let a = <Self as HasStorage<T, { R * C }>>::as_slice(a);
+        let b = <Self as HasStorage<T, { C * K }>>::as_slice(b);
+        let out = <Self as HasStorage<T, { R * K }>>::as_mut_slice(out);
+        par_matmul_into(a, b, out, R, C, K);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::storage::parallel_cpu::ParallelCpu;
+    use crate::tensor::{Tensor2, Tensor4};
+
+    #[test]
+    fn test_matches_naive_cpu() {
+        ParallelCpu::with_num_threads(3, || {
+            let a: Vec<f32> = (0..37 * 20).map(|i| (i % 17) as f32 * 0.25 - 2.0).collect();
+            let b: Vec<f32> = (0..20 * 9).map(|i| (i % 5) as f32 - 1.5).collect();
+
+            let want = Tensor2::<f32, 37, 20, NaiveCpu>::new_from_slice(&a)
+                * Tensor2::<f32, 20, 9, NaiveCpu>::new_from_slice(&b);
+            let got = Tensor2::<f32, 37, 20, ParallelCpu>::new_from_slice(&a)
+                * Tensor2::<f32, 20, 9, ParallelCpu>::new_from_slice(&b);
+            assert_eq!(got.as_slice(), want.as_slice());
+
+            let want = Tensor4::<f32, 37, 1, 1, 20, NaiveCpu>::new_from_slice(&a)
+                * Tensor2::<f32, 20, 9, NaiveCpu>::new_from_slice(&b);
+            let got = Tensor4::<f32, 37, 1, 1, 20, ParallelCpu>::new_from_slice(&a)
+                * Tensor2::<f32, 20, 9, ParallelCpu>::new_from_slice(&b);
+            assert_eq!(got.as_slice(), want.as_slice());
+        });
+    }
+}
+
+#[cfg(test)]
+mod bench {
+    use crate::storage::parallel_cpu::ParallelCpu;
+    use crate::tensor::Tensor2;
+    use test::Bencher;
+
+    #[bench]
+    fn matmul_128x128(b: &mut Bencher) {
+        const N: usize = 256;
+
+        let a = Tensor2::<f32, N, N, ParallelCpu>::new([0.0; N * N]);
+        let b_mat = Tensor2::<f32, N, N, ParallelCpu>::new([0.0; N * N]);
+
+        b.iter(|| {
+            test::black_box(a * b_mat);
+        });
+    }
+}
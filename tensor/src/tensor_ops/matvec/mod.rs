@@ -0,0 +1,204 @@
+//! Matrix-vector, vector-matrix, dot and outer products.
+//!
+//! These avoid reshaping vectors into `[N, 1]` matrices and going through
+//! [`MatMul`](crate::tensor_ops::matmul::MatMul). Every kernel takes a leading
+//! `batch` count so the same backend entry point serves the plain
+//! [`Tensor1`]/[`Tensor2`] forms and the batched ones. The batched forms are
+//! the `batch_*` functions on [`Tensor3`], whose leading axis is the batch.
+//! Backend implementers should implement [`MatVec`].
+
+pub mod naive_cpu;
+pub mod parallel_cpu;
+
+use crate::storage::HasStorage;
+use crate::tensor::{Tensor1, Tensor2, Tensor3};
+use core::ops::{Add, Mul};
+
+/// Backend trait for vector products, over `batch` independent problems laid
+/// out back to back. `NA`, `NX`, `NY` and `NO` are storage lengths.
+pub trait MatVec<T: Copy + Default + Add<Output = T> + Mul<Output = T>>: Sized {
+    /// `out[b, r] = sum_c a[b, r, c] * x[b, c]`.
+    fn matvec<const NA: usize, const NX: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NX> + HasStorage<T, NO>;
+
+    /// `out[b, c] = sum_r x[b, r] * a[b, r, c]`.
+    fn vecmat<const NX: usize, const NA: usize, const NO: usize>(
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NX> + HasStorage<T, NA> + HasStorage<T, NO>;
+
+    /// `out[b] = sum_i x[b, i] * y[b, i]`.
+    fn dot<const NX: usize, const NO: usize>(
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        y: &<Self as HasStorage<T, NX>>::Storage,
+        batch: usize,
+        len: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NX> + HasStorage<T, NO>;
+
+    /// `out[b, r, c] = x[b, r] * y[b, c]`.
+    fn outer<const NX: usize, const NY: usize, const NO: usize>(
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        y: &<Self as HasStorage<T, NY>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NX> + HasStorage<T, NY> + HasStorage<T, NO>;
+}
+
+impl<T, const R: usize, const C: usize, B> Tensor2<T, R, C, B>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    B: MatVec<T> + HasStorage<T, { R * C }> + HasStorage<T, C> + HasStorage<T, R>,
+{
+    /// `self · x` for a column vector `x`.
+    #[inline]
+    pub fn matvec(&self, x: &Tensor1<T, C, B>) -> Tensor1<T, R, B> {
+        let mut out = <B as HasStorage<T, R>>::storage_uninit();
+        B::matvec::<{ R * C }, C, R>(&self.storage, &x.storage, 1, R, C, &mut out);
+        Tensor1 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, const N: usize, B> Tensor1<T, N, B>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    B: MatVec<T> + HasStorage<T, N>,
+{
+    /// `self · a` for a row vector `self`.
+    #[inline]
+    pub fn vecmat<const C: usize>(&self, a: &Tensor2<T, N, C, B>) -> Tensor1<T, C, B>
+    where
+        B: HasStorage<T, { N * C }> + HasStorage<T, C>,
+    {
+        let mut out = <B as HasStorage<T, C>>::storage_uninit();
+        B::vecmat::<N, { N * C }, C>(&self.storage, &a.storage, 1, N, C, &mut out);
+        Tensor1 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Inner product of two vectors.
+    #[inline]
+    pub fn dot(&self, other: &Self) -> T
+    where
+        B: HasStorage<T, 1>,
+    {
+        let mut out = <B as HasStorage<T, 1>>::storage_uninit();
+        B::dot::<N, 1>(&self.storage, &other.storage, 1, N, &mut out);
+        <B as HasStorage<T, 1>>::as_slice(&out)[0]
+    }
+
+    /// Outer product `self ⊗ other`, an `N x C` matrix.
+    #[inline]
+    pub fn outer<const C: usize>(&self, other: &Tensor1<T, C, B>) -> Tensor2<T, N, C, B>
+    where
+        B: HasStorage<T, C> + HasStorage<T, { N * C }>,
+    {
+        let mut out = <B as HasStorage<T, { N * C }>>::storage_uninit();
+        B::outer::<N, C, { N * C }>(&self.storage, &other.storage, 1, N, C, &mut out);
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, const BATCH: usize, const R: usize, const C: usize, B> Tensor3<T, BATCH, R, C, B>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    B: MatVec<T>
+        + HasStorage<T, { BATCH * (R * C) }>
+        + HasStorage<T, { BATCH * C }>
+        + HasStorage<T, { BATCH * R }>,
+{
+    /// Batched [`Tensor2::matvec`]: `out[b] = self[b] · x[b]`.
+    #[inline]
+    pub fn batch_matvec(&self, x: &Tensor2<T, BATCH, C, B>) -> Tensor2<T, BATCH, R, B> {
+        let mut out = <B as HasStorage<T, { BATCH * R }>>::storage_uninit();
+        B::matvec::<{ BATCH * (R * C) }, { BATCH * C }, { BATCH * R }>(
+            &self.storage,
+            &x.storage,
+            BATCH,
+            R,
+            C,
+            &mut out,
+        );
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Batched [`Tensor1::vecmat`]: `out[b] = x[b] · self[b]`.
+    #[inline]
+    pub fn batch_vecmat(&self, x: &Tensor2<T, BATCH, R, B>) -> Tensor2<T, BATCH, C, B> {
+        let mut out = <B as HasStorage<T, { BATCH * C }>>::storage_uninit();
+        B::vecmat::<{ BATCH * R }, { BATCH * (R * C) }, { BATCH * C }>(
+            &x.storage,
+            &self.storage,
+            BATCH,
+            R,
+            C,
+            &mut out,
+        );
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Batched [`Tensor1::dot`] over every row: `out[b, r] = self[b, r] · other[b, r]`.
+    #[inline]
+    pub fn batch_dot(&self, other: &Self) -> Tensor2<T, BATCH, R, B> {
+        let mut out = <B as HasStorage<T, { BATCH * R }>>::storage_uninit();
+        B::dot::<{ BATCH * (R * C) }, { BATCH * R }>(
+            &self.storage,
+            &other.storage,
+            BATCH * R,
+            C,
+            &mut out,
+        );
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Batched [`Tensor1::outer`]: `out[b] = x[b] ⊗ y[b]`.
+    #[inline]
+    pub fn batch_outer(x: &Tensor2<T, BATCH, R, B>, y: &Tensor2<T, BATCH, C, B>) -> Self {
+        let mut out = <B as HasStorage<T, { BATCH * (R * C) }>>::storage_uninit();
+        B::outer::<{ BATCH * R }, { BATCH * C }, { BATCH * (R * C) }>(
+            &x.storage,
+            &y.storage,
+            BATCH,
+            R,
+            C,
+            &mut out,
+        );
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
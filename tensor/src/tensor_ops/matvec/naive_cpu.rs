@@ -0,0 +1,137 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::tensor_ops::matvec::MatVec;
+use core::ops::{Add, Mul};
+
+impl<T> MatVec<T> for NaiveCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    fn matvec<const NA: usize, const NX: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NX> + HasStorage<T, NO>,
+    {
+        let a = <Self as HasStorage<T, NA>>::as_slice(a);
+        let x = <Self as HasStorage<T, NX>>::as_slice(x);
+        let out = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+        for b in 0..batch {
+            let x = &x[b * cols..(b + 1) * cols];
+            for r in 0..rows {
+                let row = &a[(b * rows + r) * cols..][..cols];
+                out[b * rows + r] =
+                    row.iter().zip(x).fold(T::default(), |acc, (&v, &w)| acc + v * w);
+            }
+        }
+    }
+
+    fn vecmat<const NX: usize, const NA: usize, const NO: usize>(
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NX> + HasStorage<T, NA> + HasStorage<T, NO>,
+    {
+        let x = <Self as HasStorage<T, NX>>::as_slice(x);
+        let a = <Self as HasStorage<T, NA>>::as_slice(a);
+        let out = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+        for b in 0..batch {
+            let acc = &mut out[b * cols..(b + 1) * cols];
+            acc.fill(T::default());
+            // Walk `a` row by row so every access is contiguous.
+            for r in 0..rows {
+                let xr = x[b * rows + r];
+                let row = &a[(b * rows + r) * cols..][..cols];
+                for (o, &v) in acc.iter_mut().zip(row) {
+                    *o = *o + xr * v;
+                }
+            }
+        }
+    }
+
+    fn dot<const NX: usize, const NO: usize>(
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        y: &<Self as HasStorage<T, NX>>::Storage,
+        batch: usize,
+        len: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NX> + HasStorage<T, NO>,
+    {
+        let x = <Self as HasStorage<T, NX>>::as_slice(x);
+        let y = <Self as HasStorage<T, NX>>::as_slice(y);
+        let out = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+        for (b, o) in out.iter_mut().enumerate().take(batch) {
+            let (x, y) = (&x[b * len..][..len], &y[b * len..][..len]);
+            *o = x.iter().zip(y).fold(T::default(), |acc, (&v, &w)| acc + v * w);
+        }
+    }
+
+    fn outer<const NX: usize, const NY: usize, const NO: usize>(
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        y: &<Self as HasStorage<T, NY>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NX> + HasStorage<T, NY> + HasStorage<T, NO>,
+    {
+        let x = <Self as HasStorage<T, NX>>::as_slice(x);
+        let y = <Self as HasStorage<T, NY>>::as_slice(y);
+        let out = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+        for b in 0..batch {
+            let y = &y[b * cols..(b + 1) * cols];
+            for r in 0..rows {
+                let xr = x[b * rows + r];
+                let row = &mut out[(b * rows + r) * cols..][..cols];
+                for (o, &v) in row.iter_mut().zip(y) {
+                    *o = xr * v;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Tensor1, Tensor2, Tensor3};
+
+    #[test]
+    fn test_vector_products() {
+        let a = Tensor2::<i32, 2, 3, NaiveCpu>::new([1, 2, 3, 4, 5, 6]);
+        let x = Tensor1::<i32, 3, NaiveCpu>::new([1, 0, -1]);
+        let y = Tensor1::<i32, 2, NaiveCpu>::new([2, -1]);
+
+        assert_eq!(a.matvec(&x).as_slice(), &[-2, -2]);
+        assert_eq!(y.vecmat(&a).as_slice(), &[-2, -1, 0]);
+        assert_eq!(x.dot(&x), 2);
+        assert_eq!(y.outer(&x).as_slice(), &[2, 0, -2, -1, 0, 1]);
+
+        // Must agree with the equivalent matmul.
+        let col = Tensor2::<i32, 3, 1, NaiveCpu>::new([1, 0, -1]);
+        assert_eq!((a * col).as_slice(), a.matvec(&x).as_slice());
+    }
+
+    #[test]
+    fn test_batched_vector_products() {
+        let a = Tensor3::<i32, 2, 2, 2, NaiveCpu>::new([1, 2, 3, 4, 0, 1, 1, 0]);
+        let x = Tensor2::<i32, 2, 2, NaiveCpu>::new([1, 1, 5, 7]);
+
+        assert_eq!(a.batch_matvec(&x).as_slice(), &[3, 7, 7, 5]);
+        assert_eq!(a.batch_vecmat(&x).as_slice(), &[4, 6, 7, 5]);
+        assert_eq!(a.batch_dot(&a).as_slice(), &[5, 25, 1, 1]);
+        let y = Tensor2::<i32, 2, 3, NaiveCpu>::new([1, 2, 3, 0, 1, -1]);
+        let outer = Tensor3::batch_outer(&x, &y);
+        assert_eq!(outer.as_slice(), &[1, 2, 3, 1, 2, 3, 0, 5, -5, 0, 7, -7]);
+    }
+}
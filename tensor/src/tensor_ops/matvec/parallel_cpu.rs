@@ -0,0 +1,89 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::matvec::MatVec;
+use core::ops::{Add, Mul};
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> MatVec<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    fn matvec<const NA: usize, const NX: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NX> + HasStorage<T, NO>,
+    {
+        <NaiveCpu as MatVec<T>>::matvec::<NA, NX, NO>(
+            naive::<T, NA>(a),
+            naive::<T, NX>(x),
+            batch,
+            rows,
+            cols,
+            naive_mut::<T, NO>(out),
+        )
+    }
+
+    fn vecmat<const NX: usize, const NA: usize, const NO: usize>(
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NX> + HasStorage<T, NA> + HasStorage<T, NO>,
+    {
+        <NaiveCpu as MatVec<T>>::vecmat::<NX, NA, NO>(
+            naive::<T, NX>(x),
+            naive::<T, NA>(a),
+            batch,
+            rows,
+            cols,
+            naive_mut::<T, NO>(out),
+        )
+    }
+
+    fn dot<const NX: usize, const NO: usize>(
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        y: &<Self as HasStorage<T, NX>>::Storage,
+        batch: usize,
+        len: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NX> + HasStorage<T, NO>,
+    {
+        <NaiveCpu as MatVec<T>>::dot::<NX, NO>(
+            naive::<T, NX>(x),
+            naive::<T, NX>(y),
+            batch,
+            len,
+            naive_mut::<T, NO>(out),
+        )
+    }
+
+    fn outer<const NX: usize, const NY: usize, const NO: usize>(
+        x: &<Self as HasStorage<T, NX>>::Storage,
+        y: &<Self as HasStorage<T, NY>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NX> + HasStorage<T, NY> + HasStorage<T, NO>,
+    {
+        <NaiveCpu as MatVec<T>>::outer::<NX, NY, NO>(
+            naive::<T, NX>(x),
+            naive::<T, NY>(y),
+            batch,
+            rows,
+            cols,
+            naive_mut::<T, NO>(out),
+        )
+    }
+}
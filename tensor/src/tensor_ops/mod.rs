@@ -1,12 +1,88 @@
+#[macro_use]
+mod macros;
+
+pub mod batched_matmul;
 pub mod broadcast_conv;
 pub mod broadcast_matmul;
 pub mod broadcast_const_ops;
 pub mod const_ops;
 pub mod conv;
+pub mod conv2d;
+pub mod einsum;
 pub mod elemwise;
 pub mod exp;
+pub mod gemm;
+pub mod linalg;
 pub mod log;
 pub mod matmul;
+pub mod matvec;
 pub mod reduce;
 pub mod relu;
 pub mod reshape;
+pub mod scan;
+pub mod softmax;
+pub mod sort;
+pub mod structure;
+pub mod unary;
+
+use core::cmp::Ordering;
+
+/// Total order used wherever values are sorted: the usual order, with NaN-like
+/// values (those not comparable to themselves) after everything else.
+pub(crate) fn total_cmp<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or_else(|| is_nan(a).cmp(&is_nan(b)))
+}
+
+/// [`total_cmp`] in either direction. `descending` reverses the order of
+/// comparable values only, so NaN-like values come last both ways.
+pub(crate) fn directed_cmp<T: PartialOrd>(a: &T, b: &T, descending: bool) -> Ordering {
+    is_nan(a).cmp(&is_nan(b)).then_with(|| {
+        if descending {
+            total_cmp(b, a)
+        } else {
+            total_cmp(a, b)
+        }
+    })
+}
+
+/// The larger of `a` and `b`, or whichever is NaN-like if either is, as in
+/// numpy's `maximum`. A plain comparison would return the other operand.
+pub(crate) fn nan_max<T: PartialOrd>(a: T, b: T) -> T {
+    match a.partial_cmp(&b) {
+        Some(Ordering::Less) => b,
+        Some(_) => a,
+        None if is_nan(&a) => a,
+        None => b,
+    }
+}
+
+/// The smaller of `a` and `b`, propagating NaN-like values like [`nan_max`].
+pub(crate) fn nan_min<T: PartialOrd>(a: T, b: T) -> T {
+    match a.partial_cmp(&b) {
+        Some(Ordering::Greater) => b,
+        Some(_) => a,
+        None if is_nan(&a) => a,
+        None => b,
+    }
+}
+
+fn is_nan<T: PartialOrd>(x: &T) -> bool {
+    x.partial_cmp(x).is_none()
+}
+
+/// Splits a row-major `shape` around `axis` into `(outer, len, inner)`, so that
+/// element `p` of line `(o, i)` along `axis` lives at `(o * len + p) * inner + i`.
+pub(crate) fn axis_lines(shape: &[usize], axis: usize) -> (usize, usize, usize) {
+    let outer = shape[..axis].iter().product();
+    let inner = shape[axis + 1..].iter().product();
+    (outer, shape[axis], inner)
+}
+
+/// Evaluates to `0` when `axis < rank`.
+///
+/// Used as a `[(); axis_in_range(AXIS, RANK)]:` bound so an out-of-range axis
+/// fails compilation.
+pub const fn axis_in_range(axis: usize, rank: usize) -> usize {
+    assert!(axis < rank, "axis out of range");
+    0
+}
@@ -1,342 +1,493 @@
+//! Reductions along one or more axes, or over a whole tensor.
+//!
+//! Every rank has `*_axis::<AXIS>()` (the axis is removed) and
+//! `*_keepdim::<AXES>()` (the axes are kept with size 1) forms, plus
+//! `sum_all`/`mean_all`/`max_all`. `Tensor3` and `Tensor4` also have
+//! `*_axes::<AXES>()`, which removes two axes, and `Tensor4` has
+//! `*_axes3::<AXES>()`, which removes three. Together these reduce any axis set
+//! of any rank with the reduced axes dropped. Axes are checked at compile time.
+//!
+//! The named shorthands `*_axis0`/`*_axis1`/`*_axis2` (on `Tensor3`) and
+//! `*_axis23` (on `Tensor4`) are fixed-axis forms of the same kernels.
+//!
+//! Backend implementers should implement the `*_axes` kernel traits ([`Sum`],
+//! [`Mean`], [`Max`], [`Argmax`], [`Min`], [`Argmin`], [`Prod`], [`Var`],
+//! [`LogSumExp`], [`Norm`]).
+
 pub mod naive_cpu;
+pub mod parallel_cpu;
 
 use crate::storage::HasStorage;
-use crate::tensor::{Tensor2, Tensor3, Tensor4};
-use core::ops::Add;
-use num_traits::Float;
-
-pub trait Sum3<T: Copy + Default>: Sized {
-    fn sum_axis0<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D1 * D2 }>>::Storage,
-    ) where
-        T: Add<Output = T>,
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>;
-
-    fn sum_axis1<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D2 }>>::Storage,
-    ) where
-        T: Add<Output = T>,
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D2 }>;
-
-    fn sum_axis2<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D1 }>>::Storage,
+use crate::tensor::{Tensor1, Tensor2, Tensor3, Tensor4};
+use core::ops::{Add, Mul};
+use num_traits::{Float, One};
+
+/// Backend trait for the sum along a set of axes.
+///
+/// Like the other `*_axes` kernels below, it works on any rank: `shape` is the
+/// logical shape of `a` and `axes` the (ascending) axes that are reduced away;
+/// `out` holds the remaining axes in their original order.
+pub trait Sum<T: Copy + Default>: Sized {
+    fn sum_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
         T: Add<Output = T>,
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D1 }>;
+        Self: HasStorage<T, N> + HasStorage<T, M>;
 }
 
-pub trait Mean3<T: Float + Default>: Sized {
-    fn mean_axis0<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D1 * D2 }>>::Storage,
-    ) where
-        T: Float,
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>;
-
-    fn mean_axis1<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D2 }>>::Storage,
+/// Backend trait for the mean along a set of axes.
+pub trait Mean<T: Float + Default>: Sized {
+    fn mean_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        T: Float,
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D2 }>;
+        Self: HasStorage<T, N> + HasStorage<T, M>;
+}
 
-    fn mean_axis2<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D1 }>>::Storage,
+/// Backend trait for maximum along a set of axes.
+pub trait Max<T: Copy + Default + PartialOrd>: Sized {
+    fn max_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        T: Float,
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D1 }>;
+        Self: HasStorage<T, N> + HasStorage<T, M>;
 }
 
-pub trait Max3<T: Copy + Default + PartialOrd>: Sized {
-    fn max_axis0<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D1 * D2 }>>::Storage,
+/// Backend trait for the position of the maximum along a set of axes.
+///
+/// Indices are flat positions within the reduced axes; ties resolve to the
+/// first occurrence.
+pub trait Argmax<T: Copy + Default + PartialOrd>: Sized {
+    fn argmax_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<usize, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>;
+        Self: HasStorage<T, N> + HasStorage<usize, M>;
+}
 
-    fn max_axis1<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D2 }>>::Storage,
+/// Backend trait for minimum along a set of axes.
+pub trait Min<T: Copy + Default + PartialOrd>: Sized {
+    fn min_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D2 }>;
+        Self: HasStorage<T, N> + HasStorage<T, M>;
+}
 
-    fn max_axis2<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D1 }>>::Storage,
+/// Backend trait for the position of the minimum along a set of axes.
+///
+/// Indices are flat positions within the reduced axes; ties resolve to the
+/// first occurrence.
+pub trait Argmin<T: Copy + Default + PartialOrd>: Sized {
+    fn argmin_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<usize, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D1 }>;
+        Self: HasStorage<T, N> + HasStorage<usize, M>;
 }
 
-pub trait Argmax3<T: Copy + Default + PartialOrd>: Sized {
-    fn argmax_axis0<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<usize, { D1 * D2 }>>::Storage,
+/// Backend trait for the product along a set of axes.
+pub trait Prod<T: Copy + Default>: Sized {
+    fn prod_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<usize, { D1 * D2 }>;
+        T: Mul<Output = T> + One,
+        Self: HasStorage<T, N> + HasStorage<T, M>;
+}
 
-    fn argmax_axis1<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<usize, { D0 * D2 }>>::Storage,
+/// Backend trait for variance and standard deviation along a set of axes.
+///
+/// With `unbiased` the sum of squared deviations is divided by `n - 1`
+/// (Bessel's correction) instead of `n`. An unbiased estimate over a single
+/// element is NaN, as in numpy.
+pub trait Var<T: Float + Default>: Sized {
+    fn var_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        unbiased: bool,
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<usize, { D0 * D2 }>;
-
-    fn argmax_axis2<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<usize, { D0 * D1 }>>::Storage,
+        Self: HasStorage<T, N> + HasStorage<T, M>;
+
+    fn std_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        unbiased: bool,
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<usize, { D0 * D1 }>;
+        Self: HasStorage<T, N> + HasStorage<T, M>;
 }
 
-pub trait Mean4<T: Float + Default>: Sized {
-    fn mean_axis23<const D0: usize, const D1: usize, const D2: usize, const D3: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D1 }>>::Storage,
+/// Backend trait for `ln(sum(exp(x)))` along a set of axes, computed without
+/// overflowing for large inputs.
+pub trait LogSumExp<T: Float + Default>: Sized {
+    fn logsumexp_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        T: Float,
-        Self: HasStorage<T, { D0 * (D1 * (D2 * D3)) }> + HasStorage<T, { D0 * D1 }>;
+        Self: HasStorage<T, N> + HasStorage<T, M>;
 }
 
-pub trait Max4<T: Copy + Default + PartialOrd>: Sized {
-    fn max_axis23<const D0: usize, const D1: usize, const D2: usize, const D3: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D1 }>>::Storage,
+/// Backend trait for L1 and L2 norms along a set of axes.
+pub trait Norm<T: Float + Default>: Sized {
+    fn l1_norm_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D2 * D3)) }> + HasStorage<T, { D0 * D1 }>;
-}
+        Self: HasStorage<T, N> + HasStorage<T, M>;
 
-pub trait Argmax4<T: Copy + Default + PartialOrd>: Sized {
-    fn argmax_axis23<const D0: usize, const D1: usize, const D2: usize, const D3: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::Storage,
-        out: &mut <Self as HasStorage<usize, { D0 * D1 }>>::Storage,
+    fn l2_norm_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D2 * D3)) }> + HasStorage<usize, { D0 * D1 }>;
+        Self: HasStorage<T, N> + HasStorage<T, M>;
 }
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
-where
-    T: Copy + Default + Add<Output = T>,
-    B: Sum3<T>
-        + HasStorage<T, { D0 * (D1 * D2) }>
-        + HasStorage<T, { D1 * D2 }>
-        + HasStorage<T, { D0 * D2 }>
-        + HasStorage<T, { D0 * D1 }>,
-    [(); D0 * (D1 * D2)]:,
-{
-    #[inline]
-    pub fn sum_axis0(self) -> Tensor2<T, D1, D2, B> {
-        let mut out = <B as HasStorage<T, { D1 * D2 }>>::storage_uninit();
-        B::sum_axis0::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
-    }
-
-    #[inline]
-    pub fn sum_axis1(self) -> Tensor2<T, D0, D2, B> {
-        let mut out = <B as HasStorage<T, { D0 * D2 }>>::storage_uninit();
-        B::sum_axis1::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
-    }
-
-    #[inline]
-    pub fn sum_axis2(self) -> Tensor2<T, D0, D1, B> {
-        let mut out = <B as HasStorage<T, { D0 * D1 }>>::storage_uninit();
-        B::sum_axis2::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
-    }
+/// Size of output dimension `j` when `axis` is removed from a rank-`rank`
+/// shape; `dj` and `dnext` are input dimensions `j` and `j + 1`.
+///
+/// Panics (and therefore fails compilation when used in a type) if `axis` is
+/// out of range.
+pub const fn skip_axis(axis: usize, rank: usize, j: usize, dj: usize, dnext: usize) -> usize {
+    assert!(axis < rank, "reduction axis out of range");
+    if j < axis { dj } else { dnext }
 }
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, const D3: usize, B>
-    Tensor4<T, D0, D1, D2, D3, B>
-where
-    T: Float + Default,
-    B: Mean4<T> + HasStorage<T, { D0 * (D1 * (D2 * D3)) }> + HasStorage<T, { D0 * D1 }>,
-    [(); D0 * (D1 * (D2 * D3))]:,
-{
-    #[inline]
-    pub fn mean_axis23(self) -> Tensor2<T, D0, D1, B> {
-        let mut out = <B as HasStorage<T, { D0 * D1 }>>::storage_uninit();
-        B::mean_axis23::<D0, D1, D2, D3>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
-    }
+/// Size of dimension `i` (of input size `d`) after the axes selected by `mask`
+/// are reduced to size 1.
+///
+/// Panics (and therefore fails compilation when used in a type) if `mask`
+/// selects an axis outside a rank-`rank` shape.
+pub const fn keep_dim(mask: usize, rank: usize, i: usize, d: usize) -> usize {
+    assert!(mask >> rank == 0, "reduction axis out of range");
+    if mask & (1 << i) != 0 { 1 } else { d }
 }
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, const D3: usize, B>
-    Tensor4<T, D0, D1, D2, D3, B>
-where
-    T: Copy + Default + PartialOrd,
-    B: Max4<T> + HasStorage<T, { D0 * (D1 * (D2 * D3)) }> + HasStorage<T, { D0 * D1 }>,
-    [(); D0 * (D1 * (D2 * D3))]:,
-{
-    #[inline]
-    pub fn max_axis23(self) -> Tensor2<T, D0, D1, B> {
-        let mut out = <B as HasStorage<T, { D0 * D1 }>>::storage_uninit();
-        B::max_axis23::<D0, D1, D2, D3>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
+/// Input axis that becomes output axis `j` when the `removed` axes selected
+/// by `mask` are dropped from a rank-`rank` shape.
+///
+/// Panics (and therefore fails compilation when used in a type) if `mask`
+/// selects an axis outside the shape or does not select exactly `removed` axes.
+pub const fn kept_axis(mask: usize, rank: usize, removed: u32, j: usize) -> usize {
+    assert!(mask >> rank == 0, "reduction axis out of range");
+    assert!(mask.count_ones() == removed, "wrong number of reduction axes");
+    let mut kept = 0;
+    let mut d = 0;
+    while d < rank {
+        if mask & (1 << d) == 0 {
+            if kept == j {
+                return d;
+            }
+            kept += 1;
         }
+        d += 1;
     }
+    unreachable!()
 }
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, const D3: usize, B>
-    Tensor4<T, D0, D1, D2, D3, B>
-where
-    T: Copy + Default + PartialOrd,
-    B: Argmax4<T>
-        + HasStorage<T, { D0 * (D1 * (D2 * D3)) }>
-        + HasStorage<usize, { D0 * D1 }>,
-    [(); D0 * (D1 * (D2 * D3))]:,
-{
-    #[inline]
-    pub fn argmax_axis23(self) -> Tensor2<usize, D0, D1, B> {
-        let mut out = <B as HasStorage<usize, { D0 * D1 }>>::storage_uninit();
-        B::argmax_axis23::<D0, D1, D2, D3>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
+/// Size of `axis` in the shape `[d0, d1, d2, d3]`.
+pub const fn dim_at(axis: usize, d0: usize, d1: usize, d2: usize, d3: usize) -> usize {
+    match axis {
+        0 => d0,
+        1 => d1,
+        2 => d2,
+        _ => d3,
     }
 }
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
-where
-    T: Float + Default,
-    B: Mean3<T>
-        + HasStorage<T, { D0 * (D1 * D2) }>
-        + HasStorage<T, { D1 * D2 }>
-        + HasStorage<T, { D0 * D2 }>
-        + HasStorage<T, { D0 * D1 }>,
-    [(); D0 * (D1 * D2)]:,
-{
-    #[inline]
-    pub fn mean_axis0(self) -> Tensor2<T, D1, D2, B> {
-        let mut out = <B as HasStorage<T, { D1 * D2 }>>::storage_uninit();
-        B::mean_axis0::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
-    }
-
-    #[inline]
-    pub fn mean_axis1(self) -> Tensor2<T, D0, D2, B> {
-        let mut out = <B as HasStorage<T, { D0 * D2 }>>::storage_uninit();
-        B::mean_axis1::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
-    }
-
-    #[inline]
-    pub fn mean_axis2(self) -> Tensor2<T, D0, D1, B> {
-        let mut out = <B as HasStorage<T, { D0 * D1 }>>::storage_uninit();
-        B::mean_axis2::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
+/// Bitmask selecting `axes`, for the `*_keepdim` and `*_axes` reductions.
+///
+/// ```ignore
+/// let pooled = x.mean_keepdim::<{ axis_mask(&[2, 3]) }>(); // [N, C, 1, 1]
+/// ```
+pub const fn axis_mask(axes: &[usize]) -> usize {
+    let mut mask = 0;
+    let mut i = 0;
+    while i < axes.len() {
+        mask |= 1 << axes[i];
+        i += 1;
     }
+    mask
 }
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
-where
-    T: Copy + Default + PartialOrd,
-    B: Max3<T>
-        + HasStorage<T, { D0 * (D1 * D2) }>
-        + HasStorage<T, { D1 * D2 }>
-        + HasStorage<T, { D0 * D2 }>
-        + HasStorage<T, { D0 * D1 }>,
-    [(); D0 * (D1 * D2)]:,
-{
-    #[inline]
-    pub fn max_axis0(self) -> Tensor2<T, D1, D2, B> {
-        let mut out = <B as HasStorage<T, { D1 * D2 }>>::storage_uninit();
-        B::max_axis0::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
-    }
-
-    #[inline]
-    pub fn max_axis1(self) -> Tensor2<T, D0, D2, B> {
-        let mut out = <B as HasStorage<T, { D0 * D2 }>>::storage_uninit();
-        B::max_axis1::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
-    }
-
-    #[inline]
-    pub fn max_axis2(self) -> Tensor2<T, D0, D1, B> {
-        let mut out = <B as HasStorage<T, { D0 * D1 }>>::storage_uninit();
-        B::max_axis2::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
+/// Expands an axis bitmask into an ascending axis list (`.0[..n]`).
+fn mask_axes(mask: usize) -> ([usize; 4], usize) {
+    let mut axes = [0usize; 4];
+    let mut n = 0;
+    for d in 0..4 {
+        if mask & (1 << d) != 0 {
+            axes[n] = d;
+            n += 1;
         }
     }
+    (axes, n)
 }
 
-impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
-where
-    T: Copy + Default + PartialOrd,
-    B: Argmax3<T>
-        + HasStorage<T, { D0 * (D1 * D2) }>
-        + HasStorage<usize, { D1 * D2 }>
-        + HasStorage<usize, { D0 * D2 }>
-        + HasStorage<usize, { D0 * D1 }>,
-    [(); D0 * (D1 * D2)]:,
-{
-    #[inline]
-    pub fn argmax_axis0(self) -> Tensor2<usize, D1, D2, B> {
-        let mut out = <B as HasStorage<usize, { D1 * D2 }>>::storage_uninit();
-        B::argmax_axis0::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
+/// Const-generic single-axis (`*_axis::<AXIS>`), two- and three-axis
+/// (`*_axes::<AXES>`, `*_axes3::<AXES>`) and keepdim (`*_keepdim::<AXES>`)
+/// forms of every reduction, for one tensor rank, plus the named fixed-axis
+/// shorthands (`*_axis0`, `*_axis1`, `*_axis2` and `*_axis23`) that the rank
+/// selects with `named [...]`. The tensor-level `*_axes` method shares its name
+/// with the kernel it calls.
+macro_rules! impl_axis_reductions {
+    ($name:ident $($rank:tt)*) => {
+        impl_axis_reductions!(@op sum_axis, sum_keepdim, Sum, sum_axes, T, [Add<Output = T>], (),
+            "Sum", [sum_axis0, sum_axis1, sum_axis2, sum_axis23], sum_axes3; $name $($rank)*);
+        impl_axis_reductions!(@op mean_axis, mean_keepdim, Mean, mean_axes, T, [Float], (),
+            "Mean", [mean_axis0, mean_axis1, mean_axis2, mean_axis23], mean_axes3; $name $($rank)*);
+        impl_axis_reductions!(@op max_axis, max_keepdim, Max, max_axes, T, [PartialOrd], (),
+            "Maximum", [max_axis0, max_axis1, max_axis2, max_axis23], max_axes3; $name $($rank)*);
+        impl_axis_reductions!(@op min_axis, min_keepdim, Min, min_axes, T, [PartialOrd], (),
+            "Minimum", [min_axis0, min_axis1, min_axis2, min_axis23], min_axes3; $name $($rank)*);
+        impl_axis_reductions!(@op argmax_axis, argmax_keepdim, Argmax, argmax_axes, usize,
+            [PartialOrd], (), "Position of the maximum",
+            [argmax_axis0, argmax_axis1, argmax_axis2, argmax_axis23], argmax_axes3;
+            $name $($rank)*);
+        impl_axis_reductions!(@op argmin_axis, argmin_keepdim, Argmin, argmin_axes, usize,
+            [PartialOrd], (), "Position of the minimum",
+            [argmin_axis0, argmin_axis1, argmin_axis2, argmin_axis23], argmin_axes3;
+            $name $($rank)*);
+        impl_axis_reductions!(@op prod_axis, prod_keepdim, Prod, prod_axes, T,
+            [Mul<Output = T> + One], (), "Product",
+            [prod_axis0, prod_axis1, prod_axis2, prod_axis23], prod_axes3; $name $($rank)*);
+        impl_axis_reductions!(@op var_axis, var_keepdim, Var, var_axes, T, [Float],
+            (unbiased: bool), "Variance",
+            [var_axis0, var_axis1, var_axis2, var_axis23], var_axes3; $name $($rank)*);
+        impl_axis_reductions!(@op std_axis, std_keepdim, Var, std_axes, T, [Float],
+            (unbiased: bool), "Standard deviation",
+            [std_axis0, std_axis1, std_axis2, std_axis23], std_axes3; $name $($rank)*);
+        impl_axis_reductions!(@op logsumexp_axis, logsumexp_keepdim, LogSumExp, logsumexp_axes, T,
+            [Float], (), "`ln(sum(exp(x)))`",
+            [logsumexp_axis0, logsumexp_axis1, logsumexp_axis2, logsumexp_axis23], logsumexp_axes3;
+            $name $($rank)*);
+        impl_axis_reductions!(@op l1_norm_axis, l1_norm_keepdim, Norm, l1_norm_axes, T, [Float],
+            (), "L1 norm",
+            [l1_norm_axis0, l1_norm_axis1, l1_norm_axis2, l1_norm_axis23], l1_norm_axes3;
+            $name $($rank)*);
+        impl_axis_reductions!(@op l2_norm_axis, l2_norm_keepdim, Norm, l2_norm_axes, T, [Float],
+            (), "L2 norm",
+            [l2_norm_axis0, l2_norm_axis1, l2_norm_axis2, l2_norm_axis23], l2_norm_axes3;
+            $name $($rank)*);
+    };
+
+    (@op $axis_fn:ident, $keep_fn:ident, $tr:ident, $kernel:ident, $et:ty, [$($tb:tt)+],
+        ($($arg:ident: $argty:ty),*), $doc:literal, [$($short:ident),+], $axes3_fn:ident;
+        $name:ident [$($d:ident),+] => $drop:ident [$($o:expr),+]; keep [$($k:expr),+];
+        axes $axes:tt; axes3 $axes3:tt; named [$($named:tt),+]) => {
+        impl<T, $(const $d: usize,)+ B> $name<T, $($d,)+ B>
+        where
+            T: Copy + Default + $($tb)+,
+            B: $tr<T> + HasStorage<T, { impl_axis_reductions!(@prod $($d),+) }>,
+            [(); impl_axis_reductions!(@prod $($d),+)]:,
+        {
+            #[doc = concat!($doc, " over axis `AXIS`, which is removed from the shape.")]
+            #[inline]
+            pub fn $axis_fn<const AXIS: usize>(self $(, $arg: $argty)*) -> $drop<$et, $({ $o },)+ B>
+            where
+                B: HasStorage<$et, { impl_axis_reductions!(@prod $($o),+) }>,
+            {
+                let mut out = <B as HasStorage<
+                    $et,
+                    { impl_axis_reductions!(@prod $($o),+) },
+                >>::storage_uninit();
+                B::$kernel::<
+                    { impl_axis_reductions!(@prod $($d),+) },
+                    { impl_axis_reductions!(@prod $($o),+) },
+                >(&self.storage, &[$($d),+], &[AXIS], $($arg,)* &mut out);
+                $drop {
+                    storage: out,
+                    _p: core::marker::PhantomData,
+                }
+            }
+
+            #[doc = concat!($doc, " over the axes selected by the bitmask `AXES`.")]
+            ///
+            /// The reduced axes are kept with size 1 so the result broadcasts
+            /// against `self`; build `AXES` with [`axis_mask`].
+            #[inline]
+            pub fn $keep_fn<const AXES: usize>(self $(, $arg: $argty)*) -> $name<$et, $({ $k },)+ B>
+            where
+                B: HasStorage<$et, { impl_axis_reductions!(@prod $($k),+) }>,
+            {
+                let (axes, n) = mask_axes(AXES);
+                let mut out = <B as HasStorage<
+                    $et,
+                    { impl_axis_reductions!(@prod $($k),+) },
+                >>::storage_uninit();
+                B::$kernel::<
+                    { impl_axis_reductions!(@prod $($d),+) },
+                    { impl_axis_reductions!(@prod $($k),+) },
+                >(&self.storage, &[$($d),+], &axes[..n], $($arg,)* &mut out);
+                $name {
+                    storage: out,
+                    _p: core::marker::PhantomData,
+                }
+            }
         }
-    }
 
-    #[inline]
-    pub fn argmax_axis1(self) -> Tensor2<usize, D0, D2, B> {
-        let mut out = <B as HasStorage<usize, { D0 * D2 }>>::storage_uninit();
-        B::argmax_axis1::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
+        impl_axis_reductions!(@axes $kernel, "two", $tr, $kernel, $et, [$($tb)+],
+            ($($arg: $argty),*), $doc; $name [$($d),+]; $axes);
+        impl_axis_reductions!(@axes $axes3_fn, "three", $tr, $kernel, $et, [$($tb)+],
+            ($($arg: $argty),*), $doc; $name [$($d),+]; $axes3);
+        impl_axis_reductions!(@named $tr, $kernel, $et, [$($tb)+], ($($arg: $argty),*), $doc;
+            $name [$($d),+]; [$($short),+]; [$($named),+]);
+    };
+
+    // Reduction that removes `$count` axes (two for `axes`, three for
+    // `axes3`), for ranks that have one (`_` otherwise).
+    (@axes $method:ident, $count:literal, $tr:ident, $kernel:ident, $et:ty, [$($tb:tt)+],
+        ($($arg:ident: $argty:ty),*), $doc:literal; $name:ident [$($d:ident),+]; _) => {};
+    (@axes $method:ident, $count:literal, $tr:ident, $kernel:ident, $et:ty, [$($tb:tt)+],
+        ($($arg:ident: $argty:ty),*), $doc:literal; $name:ident [$($d:ident),+];
+        { $out:ident [$($a:expr),+] }) => {
+        impl<T, $(const $d: usize,)+ B> $name<T, $($d,)+ B>
+        where
+            T: Copy + Default + $($tb)+,
+            B: $tr<T> + HasStorage<T, { impl_axis_reductions!(@prod $($d),+) }>,
+            [(); impl_axis_reductions!(@prod $($d),+)]:,
+        {
+            #[doc = concat!($doc, " over the ", $count, " axes selected by the bitmask `AXES`.")]
+            ///
+            /// The axes are removed from the shape; build `AXES` with
+            #[doc = concat!("[`axis_mask`]. A mask that does not select exactly ", $count, " axes")]
+            /// fails to compile.
+            #[inline]
+            pub fn $method<const AXES: usize>(self $(, $arg: $argty)*) -> $out<$et, $({ $a },)+ B>
+            where
+                B: HasStorage<$et, { impl_axis_reductions!(@prod $($a),+) }>,
+            {
+                let (axes, n) = mask_axes(AXES);
+                let mut out = <B as HasStorage<
+                    $et,
+                    { impl_axis_reductions!(@prod $($a),+) },
+                >>::storage_uninit();
+                B::$kernel::<
+                    { impl_axis_reductions!(@prod $($d),+) },
+                    { impl_axis_reductions!(@prod $($a),+) },
+                >(&self.storage, &[$($d),+], &axes[..n], $($arg,)* &mut out);
+                $out {
+                    storage: out,
+                    _p: core::marker::PhantomData,
+                }
+            }
         }
-    }
-
-    #[inline]
-    pub fn argmax_axis2(self) -> Tensor2<usize, D0, D1, B> {
-        let mut out = <B as HasStorage<usize, { D0 * D1 }>>::storage_uninit();
-        B::argmax_axis2::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
+    };
+
+    // Walks the shorthand names and the rank's `named` shapes in step; `_`
+    // skips a shorthand this rank does not have.
+    (@named $tr:ident, $kernel:ident, $et:ty, [$($tb:tt)+], ($($arg:ident: $argty:ty),*),
+        $doc:literal; $name:ident [$($d:ident),+]; []; []) => {};
+    (@named $tr:ident, $kernel:ident, $et:ty, [$($tb:tt)+], ($($arg:ident: $argty:ty),*),
+        $doc:literal; $name:ident [$($d:ident),+];
+        [$short:ident $(, $shorts:ident)*]; [_ $(, $rest:tt)*]) => {
+        impl_axis_reductions!(@named $tr, $kernel, $et, [$($tb)+], ($($arg: $argty),*), $doc;
+            $name [$($d),+]; [$($shorts),*]; [$($rest),*]);
+    };
+    (@named $tr:ident, $kernel:ident, $et:ty, [$($tb:tt)+], ($($arg:ident: $argty:ty),*),
+        $doc:literal; $name:ident [$($d:ident),+];
+        [$short:ident $(, $shorts:ident)*];
+        [{ $what:literal: [$($ax:literal),+] => $out:ident [$($od:ident),+] } $(, $rest:tt)*]) => {
+        impl<T, $(const $d: usize,)+ B> $name<T, $($d,)+ B>
+        where
+            T: Copy + Default + $($tb)+,
+            B: $tr<T>
+                + HasStorage<T, { impl_axis_reductions!(@prod $($d),+) }>
+                + HasStorage<$et, { impl_axis_reductions!(@prod $($od),+) }>,
+            [(); impl_axis_reductions!(@prod $($d),+)]:,
+        {
+            #[doc = concat!($doc, " over ", $what, ".")]
+            #[inline]
+            pub fn $short(self $(, $arg: $argty)*) -> $out<$et, $($od,)+ B> {
+                let mut out = <B as HasStorage<
+                    $et,
+                    { impl_axis_reductions!(@prod $($od),+) },
+                >>::storage_uninit();
+                B::$kernel::<
+                    { impl_axis_reductions!(@prod $($d),+) },
+                    { impl_axis_reductions!(@prod $($od),+) },
+                >(&self.storage, &[$($d),+], &[$($ax),+], $($arg,)* &mut out);
+                $out {
+                    storage: out,
+                    _p: core::marker::PhantomData,
+                }
+            }
         }
-    }
 
+        impl_axis_reductions!(@named $tr, $kernel, $et, [$($tb)+], ($($arg: $argty),*), $doc;
+            $name [$($d),+]; [$($shorts),*]; [$($rest),*]);
+    };
+
+    (@prod $first:expr $(,$rest:expr)+) => { $first * impl_axis_reductions!(@prod $($rest),+) };
+    (@prod $only:expr) => { $only };
 }
 
+impl_axis_reductions!(
+    Tensor2 [R, C] => Tensor1 [skip_axis(AXIS, 2, 0, R, C)];
+    keep [keep_dim(AXES, 2, 0, R), keep_dim(AXES, 2, 1, C)];
+    axes _;
+    axes3 _;
+    named [_, _, _, _]
+);
+impl_axis_reductions!(
+    Tensor3 [D0, D1, D2]
+        => Tensor2 [skip_axis(AXIS, 3, 0, D0, D1), skip_axis(AXIS, 3, 1, D1, D2)];
+    keep [keep_dim(AXES, 3, 0, D0), keep_dim(AXES, 3, 1, D1), keep_dim(AXES, 3, 2, D2)];
+    axes { Tensor1 [dim_at(kept_axis(AXES, 3, 2, 0), D0, D1, D2, 1)] };
+    axes3 _;
+    named [
+        { "axis 0": [0] => Tensor2 [D1, D2] },
+        { "axis 1": [1] => Tensor2 [D0, D2] },
+        { "axis 2": [2] => Tensor2 [D0, D1] },
+        _
+    ]
+);
+impl_axis_reductions!(
+    Tensor4 [D0, D1, D2, D3]
+        => Tensor3 [
+            skip_axis(AXIS, 4, 0, D0, D1),
+            skip_axis(AXIS, 4, 1, D1, D2),
+            skip_axis(AXIS, 4, 2, D2, D3)
+        ];
+    keep [
+        keep_dim(AXES, 4, 0, D0),
+        keep_dim(AXES, 4, 1, D1),
+        keep_dim(AXES, 4, 2, D2),
+        keep_dim(AXES, 4, 3, D3)
+    ];
+    axes {
+        Tensor2 [
+            dim_at(kept_axis(AXES, 4, 2, 0), D0, D1, D2, D3),
+            dim_at(kept_axis(AXES, 4, 2, 1), D0, D1, D2, D3)
+        ]
+    };
+    axes3 { Tensor1 [dim_at(kept_axis(AXES, 4, 3, 0), D0, D1, D2, D3)] };
+    named [_, _, _, { "axes 2 and 3 together": [2, 3] => Tensor2 [D0, D1] }]
+);
+
 impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
 where
     T: Copy + Default,
@@ -348,41 +499,94 @@ where
     pub fn mean_batches(self) -> Tensor2<T, D0, D1, B>
     where
         T: Float,
-        B: Mean3<T>,
+        B: Mean<T>,
     {
-        let mut out = <B as HasStorage<T, { D0 * D1 }>>::storage_uninit();
-        B::mean_axis2::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
+        self.mean_axis2()
     }
 
     #[inline]
     pub fn max_batches(self) -> Tensor2<T, D0, D1, B>
     where
         T: PartialOrd,
-        B: Max3<T>,
+        B: Max<T>,
     {
-        let mut out = <B as HasStorage<T, { D0 * D1 }>>::storage_uninit();
-        B::max_axis2::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
+        self.max_axis2()
     }
 
     #[inline]
     pub fn argmax_batches(self) -> Tensor2<usize, D0, D1, B>
     where
         T: PartialOrd,
-        B: Argmax3<T> + HasStorage<usize, { D0 * D1 }>,
+        B: Argmax<T> + HasStorage<usize, { D0 * D1 }>,
     {
-        let mut out = <B as HasStorage<usize, { D0 * D1 }>>::storage_uninit();
-        B::argmax_axis2::<D0, D1, D2>(&self.storage, &mut out);
-        Tensor2 {
-            storage: out,
-            _p: core::marker::PhantomData,
-        }
+        self.argmax_axis2()
     }
 }
+
+/// Whole-tensor reductions to a single scalar, for one tensor rank.
+macro_rules! impl_full_reductions {
+    ($name:ident [$($d:ident),+], axes [$($ax:literal),+]) => {
+        impl<T, $(const $d: usize,)+ B> $name<T, $($d,)+ B>
+        where
+            T: Copy + Default,
+            B: HasStorage<T, { impl_axis_reductions!(@prod $($d),+) }> + HasStorage<T, 1>,
+            [(); impl_axis_reductions!(@prod $($d),+)]:,
+        {
+            /// Sum of every element.
+            #[inline]
+            pub fn sum_all(self) -> T
+            where
+                T: Add<Output = T>,
+                B: Sum<T>,
+            {
+                let mut out = <B as HasStorage<T, 1>>::storage_uninit();
+                B::sum_axes::<{ impl_axis_reductions!(@prod $($d),+) }, 1>(
+                    &self.storage,
+                    &[$($d),+],
+                    &[$($ax),+],
+                    &mut out,
+                );
+                <B as HasStorage<T, 1>>::as_slice(&out)[0]
+            }
+
+            /// Mean of every element.
+            #[inline]
+            pub fn mean_all(self) -> T
+            where
+                T: Float,
+                B: Mean<T>,
+            {
+                let mut out = <B as HasStorage<T, 1>>::storage_uninit();
+                B::mean_axes::<{ impl_axis_reductions!(@prod $($d),+) }, 1>(
+                    &self.storage,
+                    &[$($d),+],
+                    &[$($ax),+],
+                    &mut out,
+                );
+                <B as HasStorage<T, 1>>::as_slice(&out)[0]
+            }
+
+            /// Largest element.
+            #[inline]
+            pub fn max_all(self) -> T
+            where
+                T: PartialOrd,
+                B: Max<T>,
+            {
+                let mut out = <B as HasStorage<T, 1>>::storage_uninit();
+                B::max_axes::<{ impl_axis_reductions!(@prod $($d),+) }, 1>(
+                    &self.storage,
+                    &[$($d),+],
+                    &[$($ax),+],
+                    &mut out,
+                );
+                <B as HasStorage<T, 1>>::as_slice(&out)[0]
+            }
+        }
+    };
+}
+
+impl_full_reductions!(Tensor1 [N], axes [0]);
+impl_full_reductions!(Tensor2 [R, C], axes [0, 1]);
+impl_full_reductions!(Tensor3 [D0, D1, D2], axes [0, 1, 2]);
+impl_full_reductions!(Tensor4 [D0, D1, D2, D3], axes [0, 1, 2, 3]);
@@ -1,368 +1,339 @@
 use crate::storage::HasStorage;
 use crate::storage::naive_cpu::NaiveCpu;
-use crate::tensor_ops::reduce::{Argmax3, Argmax4, Max3, Max4, Mean3, Mean4, Sum3};
-use core::ops::Add;
-use num_traits::Float;
+use crate::tensor_ops::reduce::{Argmax, Argmin, LogSumExp, Max, Mean, Min, Norm, Prod, Sum, Var};
+use core::ops::{Add, Mul};
+use num_traits::{Float, One};
 
-impl<T> Sum3<T> for NaiveCpu
-where
-    T: Copy + Default + Add<Output = T>,
-{
-    fn sum_axis0<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D1 * D2 }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>,
-    {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<T, { D1 * D2 }>>::as_mut_slice(out);
-        for i1 in 0..D1 {
-            for i2 in 0..D2 {
-                let mut acc = T::default();
-                for i0 in 0..D0 {
-                    acc = acc + src[i0 * D1 * D2 + i1 * D2 + i2];
-                }
-                dst[i1 * D2 + i2] = acc;
-            }
+/// Calls `f(i, o, r)` for every element of a row-major tensor of `shape`,
+/// where `i` is its flat index, `o` the flat index of the output element it is
+/// reduced into when `axes` are removed, and `r` its flat position within the
+/// reduced axes. Elements are visited in ascending `i`, so `r == 0` is always
+/// the first element seen for each `o`.
+fn for_each_reduced(shape: &[usize], axes: &[usize], mut f: impl FnMut(usize, usize, usize)) {
+    let rank = shape.len();
+    let mut out_strides = [0usize; 4];
+    let mut red_strides = [0usize; 4];
+    let (mut out_acc, mut red_acc) = (1, 1);
+    for d in (0..rank).rev() {
+        if axes.contains(&d) {
+            red_strides[d] = red_acc;
+            red_acc *= shape[d];
+        } else {
+            out_strides[d] = out_acc;
+            out_acc *= shape[d];
         }
     }
 
-    fn sum_axis1<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D2 }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D2 }>,
-    {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<T, { D0 * D2 }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i2 in 0..D2 {
-                let mut acc = T::default();
-                for i1 in 0..D1 {
-                    acc = acc + src[i0 * D1 * D2 + i1 * D2 + i2];
-                }
-                dst[i0 * D2 + i2] = acc;
+    let total: usize = shape.iter().product();
+    let mut idx = [0usize; 4];
+    let (mut o, mut r) = (0, 0);
+    for i in 0..total {
+        f(i, o, r);
+        // Advance the multi-index, keeping `o` and `r` in sync.
+        for d in (0..rank).rev() {
+            idx[d] += 1;
+            o += out_strides[d];
+            r += red_strides[d];
+            if idx[d] < shape[d] {
+                break;
             }
+            o -= out_strides[d] * shape[d];
+            r -= red_strides[d] * shape[d];
+            idx[d] = 0;
         }
     }
+}
 
-    fn sum_axis2<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D1 }>>::Storage,
-    ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D1 }>,
-    {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<T, { D0 * D1 }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i1 in 0..D1 {
-                let mut acc = T::default();
-                for i2 in 0..D2 {
-                    acc = acc + src[i0 * D1 * D2 + i1 * D2 + i2];
-                }
-                dst[i0 * D1 + i1] = acc;
-            }
-        }
-    }
+/// Number of elements folded into each output element.
+fn reduced_len(shape: &[usize], axes: &[usize]) -> usize {
+    axes.iter().map(|&d| shape[d]).product()
 }
 
-impl<T> Mean3<T> for NaiveCpu
+impl<T> Sum<T> for NaiveCpu
 where
-    T: Float + Default,
+    T: Copy + Default + Add<Output = T>,
 {
-    fn mean_axis0<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D1 * D2 }>>::Storage,
+    fn sum_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>,
+        Self: HasStorage<T, N> + HasStorage<T, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<T, { D1 * D2 }>>::as_mut_slice(out);
-        let denom = T::from(D0 as u32).unwrap();
-        for i1 in 0..D1 {
-            for i2 in 0..D2 {
-                let mut acc = T::zero();
-                for i0 in 0..D0 {
-                    acc = acc + src[i0 * D1 * D2 + i1 * D2 + i2];
-                }
-                dst[i1 * D2 + i2] = acc / denom;
-            }
-        }
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, M>>::as_mut_slice(out);
+        dst.fill(T::default());
+        for_each_reduced(shape, axes, |i, o, _| dst[o] = dst[o] + src[i]);
     }
+}
 
-    fn mean_axis1<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D2 }>>::Storage,
+impl<T> Mean<T> for NaiveCpu
+where
+    T: Float + Default,
+{
+    fn mean_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D2 }>,
+        Self: HasStorage<T, N> + HasStorage<T, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<T, { D0 * D2 }>>::as_mut_slice(out);
-        let denom = T::from(D1 as u32).unwrap();
-        for i0 in 0..D0 {
-            for i2 in 0..D2 {
-                let mut acc = T::zero();
-                for i1 in 0..D1 {
-                    acc = acc + src[i0 * D1 * D2 + i1 * D2 + i2];
-                }
-                dst[i0 * D2 + i2] = acc / denom;
-            }
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, M>>::as_mut_slice(out);
+        let count = T::from(reduced_len(shape, axes)).unwrap();
+        dst.fill(T::zero());
+        for_each_reduced(shape, axes, |i, o, _| dst[o] = dst[o] + src[i]);
+        for v in dst.iter_mut() {
+            *v = *v / count;
         }
     }
+}
 
-    fn mean_axis2<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D1 }>>::Storage,
+impl<T> Max<T> for NaiveCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn max_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D1 }>,
+        Self: HasStorage<T, N> + HasStorage<T, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<T, { D0 * D1 }>>::as_mut_slice(out);
-        let denom = T::from(D2 as u32).unwrap();
-        for i0 in 0..D0 {
-            for i1 in 0..D1 {
-                let mut acc = T::zero();
-                for i2 in 0..D2 {
-                    acc = acc + src[i0 * D1 * D2 + i1 * D2 + i2];
-                }
-                dst[i0 * D1 + i1] = acc / denom;
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, M>>::as_mut_slice(out);
+        for_each_reduced(shape, axes, |i, o, r| {
+            if r == 0 || src[i] > dst[o] {
+                dst[o] = src[i];
             }
-        }
+        });
     }
 }
 
-impl<T> Max3<T> for NaiveCpu
+impl<T> Argmax<T> for NaiveCpu
 where
     T: Copy + Default + PartialOrd,
 {
-    fn max_axis0<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D1 * D2 }>>::Storage,
+    fn argmax_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<usize, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D1 * D2 }>,
+        Self: HasStorage<T, N> + HasStorage<usize, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<T, { D1 * D2 }>>::as_mut_slice(out);
-        for i1 in 0..D1 {
-            for i2 in 0..D2 {
-                let mut best = src[i1 * D2 + i2];
-                for i0 in 1..D0 {
-                    let val = src[i0 * D1 * D2 + i1 * D2 + i2];
-                    if val > best {
-                        best = val;
-                    }
-                }
-                dst[i1 * D2 + i2] = best;
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<usize, M>>::as_mut_slice(out);
+        let mut best = vec![T::default(); M];
+        for_each_reduced(shape, axes, |i, o, r| {
+            if r == 0 || src[i] > best[o] {
+                best[o] = src[i];
+                dst[o] = r;
             }
-        }
+        });
     }
+}
 
-    fn max_axis1<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D2 }>>::Storage,
+impl<T> Min<T> for NaiveCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn min_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D2 }>,
+        Self: HasStorage<T, N> + HasStorage<T, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<T, { D0 * D2 }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i2 in 0..D2 {
-                let mut best = src[i0 * D1 * D2 + i2];
-                for i1 in 1..D1 {
-                    let val = src[i0 * D1 * D2 + i1 * D2 + i2];
-                    if val > best {
-                        best = val;
-                    }
-                }
-                dst[i0 * D2 + i2] = best;
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, M>>::as_mut_slice(out);
+        for_each_reduced(shape, axes, |i, o, r| {
+            if r == 0 || src[i] < dst[o] {
+                dst[o] = src[i];
             }
-        }
+        });
     }
+}
 
-    fn max_axis2<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D1 }>>::Storage,
+impl<T> Argmin<T> for NaiveCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn argmin_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<usize, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { D0 * D1 }>,
+        Self: HasStorage<T, N> + HasStorage<usize, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<T, { D0 * D1 }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i1 in 0..D1 {
-                let mut best = src[i0 * D1 * D2 + i1 * D2];
-                for i2 in 1..D2 {
-                    let val = src[i0 * D1 * D2 + i1 * D2 + i2];
-                    if val > best {
-                        best = val;
-                    }
-                }
-                dst[i0 * D1 + i1] = best;
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<usize, M>>::as_mut_slice(out);
+        let mut best = vec![T::default(); M];
+        for_each_reduced(shape, axes, |i, o, r| {
+            if r == 0 || src[i] < best[o] {
+                best[o] = src[i];
+                dst[o] = r;
             }
-        }
+        });
     }
 }
 
-impl<T> Argmax3<T> for NaiveCpu
+impl<T> Prod<T> for NaiveCpu
 where
-    T: Copy + Default + PartialOrd,
+    T: Copy + Default + Mul<Output = T> + One,
 {
-    fn argmax_axis0<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<usize, { D1 * D2 }>>::Storage,
+    fn prod_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<usize, { D1 * D2 }>,
+        Self: HasStorage<T, N> + HasStorage<T, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<usize, { D1 * D2 }>>::as_mut_slice(out);
-        for i1 in 0..D1 {
-            for i2 in 0..D2 {
-                let mut best = src[i1 * D2 + i2];
-                let mut idx = 0usize;
-                for i0 in 1..D0 {
-                    let val = src[i0 * D1 * D2 + i1 * D2 + i2];
-                    if val > best {
-                        best = val;
-                        idx = i0;
-                    }
-                }
-                dst[i1 * D2 + i2] = idx;
-            }
-        }
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, M>>::as_mut_slice(out);
+        dst.fill(T::one());
+        for_each_reduced(shape, axes, |i, o, _| dst[o] = dst[o] * src[i]);
+    }
+}
+
+/// Two-pass variance: the mean first, then the mean squared deviation.
+fn variance_into<T: Float>(
+    src: &[T],
+    shape: &[usize],
+    axes: &[usize],
+    unbiased: bool,
+    dst: &mut [T],
+) {
+    let n = reduced_len(shape, axes);
+    if unbiased && n < 2 {
+        // No degrees of freedom are left, so the estimate is undefined: NaN,
+        // as numpy gives for `ddof=1`.
+        dst.fill(T::nan());
+        return;
     }
+    let count = T::from(n).unwrap();
+    let mut mean = vec![T::zero(); dst.len()];
+    for_each_reduced(shape, axes, |i, o, _| mean[o] = mean[o] + src[i]);
+    for m in mean.iter_mut() {
+        *m = *m / count;
+    }
+
+    dst.fill(T::zero());
+    for_each_reduced(shape, axes, |i, o, _| {
+        let d = src[i] - mean[o];
+        dst[o] = dst[o] + d * d;
+    });
+    let denom = if unbiased { T::from(n - 1).unwrap() } else { count };
+    for v in dst.iter_mut() {
+        *v = *v / denom;
+    }
+}
 
-    fn argmax_axis1<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<usize, { D0 * D2 }>>::Storage,
+impl<T> Var<T> for NaiveCpu
+where
+    T: Float + Default,
+{
+    fn var_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        unbiased: bool,
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<usize, { D0 * D2 }>,
+        Self: HasStorage<T, N> + HasStorage<T, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<usize, { D0 * D2 }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i2 in 0..D2 {
-                let mut best = src[i0 * D1 * D2 + i2];
-                let mut idx = 0usize;
-                for i1 in 1..D1 {
-                    let val = src[i0 * D1 * D2 + i1 * D2 + i2];
-                    if val > best {
-                        best = val;
-                        idx = i1;
-                    }
-                }
-                dst[i0 * D2 + i2] = idx;
-            }
-        }
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, M>>::as_mut_slice(out);
+        variance_into(src, shape, axes, unbiased, dst);
     }
 
-    fn argmax_axis2<const D0: usize, const D1: usize, const D2: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
-        out: &mut <Self as HasStorage<usize, { D0 * D1 }>>::Storage,
+    fn std_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        unbiased: bool,
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<usize, { D0 * D1 }>,
+        Self: HasStorage<T, N> + HasStorage<T, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * D2) }>>::as_slice(a);
-        let dst = <Self as HasStorage<usize, { D0 * D1 }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i1 in 0..D1 {
-                let mut best = src[i0 * D1 * D2 + i1 * D2];
-                let mut idx = 0usize;
-                for i2 in 1..D2 {
-                    let val = src[i0 * D1 * D2 + i1 * D2 + i2];
-                    if val > best {
-                        best = val;
-                        idx = i2;
-                    }
-                }
-                dst[i0 * D1 + i1] = idx;
-            }
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, M>>::as_mut_slice(out);
+        variance_into(src, shape, axes, unbiased, dst);
+        for v in dst.iter_mut() {
+            *v = v.sqrt();
         }
     }
 }
 
-impl<T> Mean4<T> for NaiveCpu
+impl<T> LogSumExp<T> for NaiveCpu
 where
     T: Float + Default,
 {
-    fn mean_axis23<const D0: usize, const D1: usize, const D2: usize, const D3: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D1 }>>::Storage,
+    fn logsumexp_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D2 * D3)) }> + HasStorage<T, { D0 * D1 }>,
+        Self: HasStorage<T, N> + HasStorage<T, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::as_slice(a);
-        let dst = <Self as HasStorage<T, { D0 * D1 }>>::as_mut_slice(out);
-        let denom = T::from((D2 * D3) as u32).unwrap();
-        for i0 in 0..D0 {
-            for i1 in 0..D1 {
-                let mut acc = T::zero();
-                for i2 in 0..D2 {
-                    for i3 in 0..D3 {
-                        acc = acc + src[((i0 * D1 + i1) * D2 + i2) * D3 + i3];
-                    }
-                }
-                dst[i0 * D1 + i1] = acc / denom;
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, M>>::as_mut_slice(out);
+
+        // Shift by the maximum so the largest exponent is e^0.
+        let mut max = vec![T::zero(); M];
+        for_each_reduced(shape, axes, |i, o, r| {
+            if r == 0 || src[i] > max[o] {
+                max[o] = src[i];
             }
+        });
+        let mut sum = vec![T::zero(); M];
+        for_each_reduced(shape, axes, |i, o, _| sum[o] = sum[o] + (src[i] - max[o]).exp());
+        for ((d, &m), &s) in dst.iter_mut().zip(&max).zip(&sum) {
+            // An infinite maximum would turn the shift into NaN; it is also the answer.
+            *d = if m.is_infinite() { m } else { m + s.ln() };
         }
     }
 }
 
-impl<T> Max4<T> for NaiveCpu
+impl<T> Norm<T> for NaiveCpu
 where
-    T: Copy + Default + PartialOrd,
+    T: Float + Default,
 {
-    fn max_axis23<const D0: usize, const D1: usize, const D2: usize, const D3: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::Storage,
-        out: &mut <Self as HasStorage<T, { D0 * D1 }>>::Storage,
+    fn l1_norm_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D2 * D3)) }> + HasStorage<T, { D0 * D1 }>,
+        Self: HasStorage<T, N> + HasStorage<T, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::as_slice(a);
-        let dst = <Self as HasStorage<T, { D0 * D1 }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i1 in 0..D1 {
-                let mut best = src[((i0 * D1 + i1) * D2) * D3];
-                for i2 in 0..D2 {
-                    for i3 in 0..D3 {
-                        let val = src[((i0 * D1 + i1) * D2 + i2) * D3 + i3];
-                        if val > best {
-                            best = val;
-                        }
-                    }
-                }
-                dst[i0 * D1 + i1] = best;
-            }
-        }
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, M>>::as_mut_slice(out);
+        dst.fill(T::zero());
+        for_each_reduced(shape, axes, |i, o, _| dst[o] = dst[o] + src[i].abs());
     }
-}
 
-impl<T> Argmax4<T> for NaiveCpu
-where
-    T: Copy + Default + PartialOrd,
-{
-    fn argmax_axis23<const D0: usize, const D1: usize, const D2: usize, const D3: usize>(
-        a: &<Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::Storage,
-        out: &mut <Self as HasStorage<usize, { D0 * D1 }>>::Storage,
+    fn l2_norm_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
     ) where
-        Self: HasStorage<T, { D0 * (D1 * (D2 * D3)) }> + HasStorage<usize, { D0 * D1 }>,
+        Self: HasStorage<T, N> + HasStorage<T, M>,
     {
-        let src = <Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::as_slice(a);
-        let dst = <Self as HasStorage<usize, { D0 * D1 }>>::as_mut_slice(out);
-        for i0 in 0..D0 {
-            for i1 in 0..D1 {
-                let mut best = src[((i0 * D1 + i1) * D2) * D3];
-                let mut idx = 0usize;
-                for i2 in 0..D2 {
-                    for i3 in 0..D3 {
-                        let val = src[((i0 * D1 + i1) * D2 + i2) * D3 + i3];
-                        let flat = i2 * D3 + i3;
-                        if val > best {
-                            best = val;
-                            idx = flat;
-                        }
-                    }
-                }
-                dst[i0 * D1 + i1] = idx;
-            }
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, M>>::as_mut_slice(out);
+        dst.fill(T::zero());
+        for_each_reduced(shape, axes, |i, o, _| dst[o] = dst[o] + src[i] * src[i]);
+        for v in dst.iter_mut() {
+            *v = v.sqrt();
         }
     }
 }
@@ -370,7 +341,9 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tensor::{Tensor3, Tensor4};
+    use crate::tensor::{Tensor1, Tensor2, Tensor3, Tensor4};
+    use crate::tensor_ops::broadcast_const_ops::BroadcastSub;
+    use crate::tensor_ops::reduce::axis_mask;
 
     #[test]
     fn test_sum_axes() {
@@ -447,4 +420,127 @@ mod tests {
         let am = t.argmax_axis23();
         assert_eq!(am.as_slice(), &[3, 3, 3, 3]);
     }
+
+    #[test]
+    fn test_min_argmin_prod() {
+        let t = Tensor3::<f32, 2, 2, 2, NaiveCpu>::new([3.0, 2.0, 1.0, 4.0, 5.0, 0.0, 7.0, 0.0]);
+        assert_eq!(t.min_axis0().as_slice(), &[3.0, 0.0, 1.0, 0.0]);
+        assert_eq!(t.min_axis2().as_slice(), &[2.0, 1.0, 0.0, 0.0]);
+        // Ties resolve to the first occurrence.
+        assert_eq!(t.argmin_axis1().as_slice(), &[1, 0, 0, 0]);
+        assert_eq!(t.prod_axis2().as_slice(), &[6.0, 4.0, 0.0, 0.0]);
+
+        let u = Tensor4::<i32, 1, 2, 2, 2, NaiveCpu>::new([4, 2, 3, 1, 1, 2, 3, 4]);
+        assert_eq!(u.min_axis23().as_slice(), &[1, 1]);
+        assert_eq!(u.argmin_axis23().as_slice(), &[3, 0]);
+        assert_eq!(u.prod_axis23().as_slice(), &[24, 24]);
+    }
+
+    #[test]
+    fn test_var_std_and_norms() {
+        let t = Tensor3::<f64, 1, 2, 4, NaiveCpu>::new([1.0, 2.0, 3.0, 4.0, -3.0, 0.0, 0.0, 3.0]);
+        let v = t.var_axis2(false);
+        assert_eq!(v.as_slice(), &[1.25, 4.5]);
+        let v = t.var_axis2(true);
+        assert!((v.as_slice()[0] - 5.0 / 3.0).abs() < 1e-12);
+        let s = t.std_axis2(false);
+        assert!((s.as_slice()[1] - 4.5f64.sqrt()).abs() < 1e-12);
+
+        assert_eq!(t.l1_norm_axis2().as_slice(), &[10.0, 6.0]);
+        assert_eq!(t.l2_norm_axis1().as_slice(), &[10f64.sqrt(), 2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_unbiased_variance_of_one_element_is_nan() {
+        let t = Tensor3::<f64, 2, 1, 3, NaiveCpu>::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert!(t.var_axis1(true).as_slice().iter().all(|v| v.is_nan()));
+        assert!(t.std_axis1(true).as_slice().iter().all(|v| v.is_nan()));
+        assert_eq!(t.var_axis1(false).as_slice(), &[0.0; 6]);
+    }
+
+    #[test]
+    fn test_logsumexp_is_stable() {
+        let t = Tensor4::<f32, 1, 2, 1, 2, NaiveCpu>::new([0.0, 0.0, 1000.0, 1000.0]);
+        let l = t.logsumexp_axis23();
+        let ln2 = 2f32.ln();
+        assert!((l.as_slice()[0] - ln2).abs() < 1e-6);
+        assert!((l.as_slice()[1] - (1000.0 + ln2)).abs() < 1e-3);
+
+        let t = Tensor3::<f32, 1, 1, 2, NaiveCpu>::new([f32::NEG_INFINITY, f32::NEG_INFINITY]);
+        assert_eq!(t.logsumexp_axis2().as_slice(), &[f32::NEG_INFINITY]);
+    }
+
+    #[test]
+    fn test_single_axis_reductions_any_rank() {
+        let m = Tensor2::<f32, 2, 3, NaiveCpu>::new([1.0, 5.0, 3.0, 4.0, 2.0, 6.0]);
+        let cols: Tensor1<f32, 3, NaiveCpu> = m.sum_axis::<0>();
+        assert_eq!(cols.as_slice(), &[5.0, 7.0, 9.0]);
+        assert_eq!(m.argmax_axis::<1>().as_slice(), &[1, 2]);
+
+        let t = Tensor4::<i32, 2, 1, 2, 2, NaiveCpu>::new([1, 2, 3, 4, 5, 6, 7, 8]);
+        let s: Tensor3<i32, 1, 2, 2, NaiveCpu> = t.sum_axis::<0>();
+        assert_eq!(s.as_slice(), &[6, 8, 10, 12]);
+        let mx: Tensor3<i32, 2, 1, 2, NaiveCpu> = t.max_axis::<2>();
+        assert_eq!(mx.as_slice(), &[3, 4, 7, 8]);
+        assert_eq!(t.min_axis::<3>().as_slice(), &[1, 3, 5, 7]);
+        assert_eq!(t.sum_axis23().as_slice(), &[10, 26]);
+    }
+
+    #[test]
+    fn test_keepdim_reductions_broadcast_back() {
+        let x = Tensor4::<f32, 1, 2, 2, 2, NaiveCpu>::new([1.0, 2.0, 3.0, 6.0, 0.0, 4.0, 4.0, 8.0]);
+        let mean = x.mean_keepdim::<{ axis_mask(&[2, 3]) }>();
+        let _: &Tensor4<f32, 1, 2, 1, 1, NaiveCpu> = &mean;
+        assert_eq!(mean.as_slice(), &[3.0, 4.0]);
+
+        // The kept size-1 axes broadcast against the input.
+        let centered = x.broadcast_sub(mean);
+        assert_eq!(centered.as_slice(), &[-2.0, -1.0, 0.0, 3.0, -4.0, 0.0, 0.0, 4.0]);
+
+        let v = x.var_keepdim::<{ axis_mask(&[1, 2, 3]) }>(false);
+        assert_eq!(v.as_slice(), &[6.0]);
+        let am = x.argmax_keepdim::<{ axis_mask(&[1]) }>();
+        assert_eq!(am.as_slice(), &[0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_two_axis_reductions_drop_both_axes() {
+        let x = Tensor4::<f32, 1, 2, 2, 2, NaiveCpu>::new([1.0, 2.0, 3.0, 6.0, 0.0, 4.0, 4.0, 8.0]);
+        let pooled: Tensor2<f32, 1, 2, NaiveCpu> = x.mean_axes::<{ axis_mask(&[2, 3]) }>();
+        assert_eq!(pooled.as_slice(), x.mean_axis23().as_slice());
+        let per_row: Tensor2<f32, 2, 2, NaiveCpu> = x.sum_axes::<{ axis_mask(&[0, 3]) }>();
+        assert_eq!(per_row.as_slice(), &[3.0, 9.0, 4.0, 12.0]);
+
+        let t = Tensor3::<i32, 2, 3, 2, NaiveCpu>::new([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+        let cols: Tensor1<i32, 3, NaiveCpu> = t.max_axes::<{ axis_mask(&[0, 2]) }>();
+        assert_eq!(cols.as_slice(), &[8, 10, 12]);
+        assert_eq!(t.argmin_axes::<{ axis_mask(&[0, 2]) }>().as_slice(), &[0, 0, 0]);
+    }
+
+    #[test]
+    fn test_three_axis_reductions_keep_one_axis() {
+        // Per-channel statistics of an [N, C, H, W] batch.
+        let x = Tensor4::<f64, 2, 3, 1, 2, NaiveCpu>::new(core::array::from_fn(|i| i as f64));
+        let per_channel: Tensor1<f64, 3, NaiveCpu> = x.sum_axes3::<{ axis_mask(&[0, 2, 3]) }>();
+        assert_eq!(per_channel.as_slice(), &[14.0, 22.0, 30.0]);
+        let var = x.var_axes3::<{ axis_mask(&[0, 2, 3]) }>(false);
+        assert_eq!(var.as_slice(), &[9.25; 3]);
+
+        let per_batch = x.max_axes3::<{ axis_mask(&[1, 2, 3]) }>();
+        assert_eq!(per_batch.as_slice(), &[5.0, 11.0]);
+        let last = x.argmax_axes3::<{ axis_mask(&[0, 1, 2]) }>();
+        assert_eq!(last.as_slice(), &[5, 5]);
+    }
+
+    #[test]
+    fn test_full_reductions() {
+        let v = Tensor1::<f32, 4, NaiveCpu>::new([1.0, -2.0, 8.0, 1.0]);
+        assert_eq!(v.sum_all(), 8.0);
+        assert_eq!(v.max_all(), 8.0);
+
+        let t = Tensor3::<f64, 2, 2, 2, NaiveCpu>::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+        assert_eq!(t.sum_all(), 36.0);
+        assert_eq!(t.mean_all(), 4.5);
+        assert_eq!(t.max_all(), 8.0);
+    }
 }
@@ -0,0 +1,270 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::reduce::{Argmax, Argmin, LogSumExp, Max, Mean, Min, Norm, Prod, Sum, Var};
+use core::ops::{Add, Mul};
+use num_traits::{Float, One};
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> Sum<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T>,
+{
+    fn sum_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M>,
+    {
+        <NaiveCpu as Sum<T>>::sum_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            naive_mut::<T, M>(out),
+        )
+    }
+}
+
+impl<T> Mean<T> for ParallelCpu
+where
+    T: Float + Default,
+{
+    fn mean_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M>,
+    {
+        <NaiveCpu as Mean<T>>::mean_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            naive_mut::<T, M>(out),
+        )
+    }
+}
+
+impl<T> Max<T> for ParallelCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn max_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M>,
+    {
+        <NaiveCpu as Max<T>>::max_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            naive_mut::<T, M>(out),
+        )
+    }
+}
+
+impl<T> Argmax<T> for ParallelCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn argmax_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<usize, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<usize, M>,
+    {
+        <NaiveCpu as Argmax<T>>::argmax_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            naive_mut::<usize, M>(out),
+        )
+    }
+}
+
+impl<T> Min<T> for ParallelCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn min_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M>,
+    {
+        <NaiveCpu as Min<T>>::min_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            naive_mut::<T, M>(out),
+        )
+    }
+}
+
+impl<T> Argmin<T> for ParallelCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn argmin_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<usize, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<usize, M>,
+    {
+        <NaiveCpu as Argmin<T>>::argmin_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            naive_mut::<usize, M>(out),
+        )
+    }
+}
+
+impl<T> Prod<T> for ParallelCpu
+where
+    T: Copy + Default + Mul<Output = T> + One,
+{
+    fn prod_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M>,
+    {
+        <NaiveCpu as Prod<T>>::prod_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            naive_mut::<T, M>(out),
+        )
+    }
+}
+
+impl<T> Var<T> for ParallelCpu
+where
+    T: Float + Default,
+{
+    fn var_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        unbiased: bool,
+        out: &mut <Self as HasStorage<T, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M>,
+    {
+        <NaiveCpu as Var<T>>::var_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            unbiased,
+            naive_mut::<T, M>(out),
+        )
+    }
+
+    fn std_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        unbiased: bool,
+        out: &mut <Self as HasStorage<T, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M>,
+    {
+        <NaiveCpu as Var<T>>::std_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            unbiased,
+            naive_mut::<T, M>(out),
+        )
+    }
+}
+
+impl<T> LogSumExp<T> for ParallelCpu
+where
+    T: Float + Default,
+{
+    fn logsumexp_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M>,
+    {
+        <NaiveCpu as LogSumExp<T>>::logsumexp_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            naive_mut::<T, M>(out),
+        )
+    }
+}
+
+impl<T> Norm<T> for ParallelCpu
+where
+    T: Float + Default,
+{
+    fn l1_norm_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M>,
+    {
+        <NaiveCpu as Norm<T>>::l1_norm_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            naive_mut::<T, M>(out),
+        )
+    }
+
+    fn l2_norm_axes<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axes: &[usize],
+        out: &mut <Self as HasStorage<T, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M>,
+    {
+        <NaiveCpu as Norm<T>>::l2_norm_axes::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axes,
+            naive_mut::<T, M>(out),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::storage::parallel_cpu::ParallelCpu;
+    use crate::tensor::Tensor3;
+
+    #[test]
+    fn test_fallback_matches_naive_cpu() {
+        let x: Vec<f32> = (0..2 * 3 * 4).map(|i| (i % 7) as f32 - 3.0).collect();
+        let p = Tensor3::<f32, 2, 3, 4, ParallelCpu>::new_from_slice(&x);
+        let n = Tensor3::<f32, 2, 3, 4, NaiveCpu>::new_from_slice(&x);
+        assert_eq!(p.sum_axis1().as_slice(), n.sum_axis1().as_slice());
+        assert_eq!(p.argmax_axis2().as_slice(), n.argmax_axis2().as_slice());
+        assert_eq!(p.softmax::<2>().as_slice(), n.softmax::<2>().as_slice());
+    }
+}
@@ -86,6 +86,19 @@ impl Relu<f32> for MetalGpu {
         }
     }
 
+    fn relu_inplace<const N: usize>(a: &mut <Self as HasStorage<f32, N>>::Storage)
+    where
+        Self: HasStorage<f32, N>,
+    {
+        // MPS does not promise that a kernel may read and write the same
+        // buffer, and clones of a MetalGpuStorage share their buffer. Run the
+        // out-of-place kernel into a fresh buffer and swap it in, so other
+        // clones of the tensor keep their values.
+        let mut out = <Self as HasStorage<f32, N>>::storage_uninit();
+        Self::relu::<N>(a, &mut out);
+        *a = out;
+    }
+
     fn relu_backward<const N: usize>(
         _input: &<Self as HasStorage<f32, N>>::Storage,
         _grad_output: &<Self as HasStorage<f32, N>>::Storage,
@@ -3,6 +3,7 @@
 //! Backend implementers should implement [`Relu`].
 
 pub mod naive_cpu;
+pub mod parallel_cpu;
 #[cfg(target_os = "macos")]
 pub mod metal_gpu;
 
@@ -18,6 +19,10 @@ pub trait Relu<T: Copy + Default + PartialOrd>: Sized {
     ) where
         Self: HasStorage<T, N>;
 
+    fn relu_inplace<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage)
+    where
+        Self: HasStorage<T, N>;
+
     fn relu_backward<const N: usize>(
         input: &<Self as HasStorage<T, N>>::Storage,
         grad_output: &<Self as HasStorage<T, N>>::Storage,
@@ -40,6 +45,12 @@ where
             _p: core::marker::PhantomData,
         }
     }
+
+    /// In-place variant of [`Self::relu`].
+    #[inline]
+    pub fn relu_(&mut self) {
+        B::relu_inplace::<{ R * C }>(&mut self.storage);
+    }
 }
 
 impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
@@ -57,6 +68,12 @@ where
             _p: core::marker::PhantomData,
         }
     }
+
+    /// In-place variant of [`Self::relu`].
+    #[inline]
+    pub fn relu_(&mut self) {
+        B::relu_inplace::<{ D0 * (D1 * D2) }>(&mut self.storage);
+    }
 }
 
 impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
@@ -76,6 +93,12 @@ where
         }
     }
 
+    /// In-place variant of [`Self::relu`].
+    #[inline]
+    pub fn relu_(&mut self) {
+        B::relu_inplace::<{ D0 * (D1 * (D3 * D4)) }>(&mut self.storage);
+    }
+
     #[inline]
     pub fn relu_backward(
         input: &Self,
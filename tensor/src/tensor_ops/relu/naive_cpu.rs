@@ -22,6 +22,16 @@ where
         }
     }
 
+    fn relu_inplace<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage)
+    where
+        Self: HasStorage<T, N>,
+    {
+        let zero = T::default();
+        for v in <Self as HasStorage<T, N>>::as_mut_slice(a).iter_mut() {
+            *v = if *v > zero { *v } else { zero };
+        }
+    }
+
     fn relu_backward<const N: usize>(
         input: &<Self as HasStorage<T, N>>::Storage,
         grad_output: &<Self as HasStorage<T, N>>::Storage,
@@ -55,4 +65,11 @@ mod tests {
         assert_eq!(s[2], 0.0);
         assert_eq!(s[3], 3.0);
     }
+
+    #[test]
+    fn test_relu_inplace() {
+        let mut t = crate::tensor::Tensor3::<f32, 1, 2, 2, NaiveCpu>::new([-1.0, 0.5, -2.0, 3.0]);
+        t.relu_();
+        assert_eq!(t.as_slice(), &[0.0, 0.5, 0.0, 3.0]);
+    }
 }
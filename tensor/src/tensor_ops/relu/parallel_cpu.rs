@@ -0,0 +1,41 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::relu::Relu;
+use core::cmp::PartialOrd;
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> Relu<T> for ParallelCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn relu<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Relu<T>>::relu::<N>(naive::<T, N>(a), naive_mut::<T, N>(out))
+    }
+
+    fn relu_inplace<const N: usize>(a: &mut <Self as HasStorage<T, N>>::Storage)
+    where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Relu<T>>::relu_inplace::<N>(naive_mut::<T, N>(a))
+    }
+
+    fn relu_backward<const N: usize>(
+        input: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Relu<T>>::relu_backward::<N>(
+            naive::<T, N>(input),
+            naive::<T, N>(grad_output),
+            naive_mut::<T, N>(grad_input),
+        )
+    }
+}
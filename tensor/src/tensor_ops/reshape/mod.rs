@@ -3,6 +3,7 @@
 //! Backend implementers should implement [`Reshape`] for their backend.
 
 pub mod naive_cpu;
+pub mod parallel_cpu;
 
 use crate::storage::HasStorage;
 use crate::tensor::{Tensor2, Tensor3, Tensor4};
@@ -0,0 +1,120 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::reshape::Reshape;
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> Reshape<T> for ParallelCpu
+where
+    T: Copy + Default,
+{
+    fn reshape22<const R: usize, const C: usize, const NR: usize, const NC: usize>(
+        src: &<Self as HasStorage<T, { R * C }>>::Storage,
+        dst: &mut <Self as HasStorage<T, { NR * NC }>>::Storage,
+    ) where
+        Self: HasStorage<T, { R * C }> + HasStorage<T, { NR * NC }>,
+    {
+        <NaiveCpu as Reshape<T>>::reshape22::<R, C, NR, NC>(
+            naive::<T, { R * C }>(src),
+            naive_mut::<T, { NR * NC }>(dst),
+        )
+    }
+
+    fn reshape32<
+        const D0: usize,
+        const D1: usize,
+        const D2: usize,
+        const R: usize,
+        const C: usize,
+    >(
+        src: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
+        dst: &mut <Self as HasStorage<T, { R * C }>>::Storage,
+    ) where
+        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { R * C }>,
+    {
+        <NaiveCpu as Reshape<T>>::reshape32::<D0, D1, D2, R, C>(
+            naive::<T, { D0 * (D1 * D2) }>(src),
+            naive_mut::<T, { R * C }>(dst),
+        )
+    }
+
+    fn reshape33<
+        const D0: usize,
+        const D1: usize,
+        const D2: usize,
+        const ND0: usize,
+        const ND1: usize,
+        const ND2: usize,
+    >(
+        src: &<Self as HasStorage<T, { D0 * (D1 * D2) }>>::Storage,
+        dst: &mut <Self as HasStorage<T, { ND0 * (ND1 * ND2) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { D0 * (D1 * D2) }> + HasStorage<T, { ND0 * (ND1 * ND2) }>,
+    {
+        <NaiveCpu as Reshape<T>>::reshape33::<D0, D1, D2, ND0, ND1, ND2>(
+            naive::<T, { D0 * (D1 * D2) }>(src),
+            naive_mut::<T, { ND0 * (ND1 * ND2) }>(dst),
+        )
+    }
+
+    fn reshape44<
+        const D0: usize,
+        const D1: usize,
+        const D2: usize,
+        const D3: usize,
+        const ND0: usize,
+        const ND1: usize,
+        const ND2: usize,
+        const ND3: usize,
+    >(
+        src: &<Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::Storage,
+        dst: &mut <Self as HasStorage<T, { ND0 * (ND1 * (ND2 * ND3)) }>>::Storage,
+    ) where
+        Self:
+            HasStorage<T, { D0 * (D1 * (D2 * D3)) }> + HasStorage<T, { ND0 * (ND1 * (ND2 * ND3)) }>,
+    {
+        <NaiveCpu as Reshape<T>>::reshape44::<D0, D1, D2, D3, ND0, ND1, ND2, ND3>(
+            naive::<T, { D0 * (D1 * (D2 * D3)) }>(src),
+            naive_mut::<T, { ND0 * (ND1 * (ND2 * ND3)) }>(dst),
+        )
+    }
+
+    fn reshape43<
+        const D0: usize,
+        const D1: usize,
+        const D2: usize,
+        const D3: usize,
+        const ND0: usize,
+        const ND1: usize,
+        const ND2: usize,
+    >(
+        src: &<Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::Storage,
+        dst: &mut <Self as HasStorage<T, { ND0 * (ND1 * ND2) }>>::Storage,
+    ) where
+        Self: HasStorage<T, { D0 * (D1 * (D2 * D3)) }> + HasStorage<T, { ND0 * (ND1 * ND2) }>,
+    {
+        <NaiveCpu as Reshape<T>>::reshape43::<D0, D1, D2, D3, ND0, ND1, ND2>(
+            naive::<T, { D0 * (D1 * (D2 * D3)) }>(src),
+            naive_mut::<T, { ND0 * (ND1 * ND2) }>(dst),
+        )
+    }
+
+    fn reshape42<
+        const D0: usize,
+        const D1: usize,
+        const D2: usize,
+        const D3: usize,
+        const R: usize,
+        const C: usize,
+    >(
+        src: &<Self as HasStorage<T, { D0 * (D1 * (D2 * D3)) }>>::Storage,
+        dst: &mut <Self as HasStorage<T, { R * C }>>::Storage,
+    ) where
+        Self: HasStorage<T, { D0 * (D1 * (D2 * D3)) }> + HasStorage<T, { R * C }>,
+    {
+        <NaiveCpu as Reshape<T>>::reshape42::<D0, D1, D2, D3, R, C>(
+            naive::<T, { D0 * (D1 * (D2 * D3)) }>(src),
+            naive_mut::<T, { R * C }>(dst),
+        )
+    }
+}
@@ -0,0 +1,153 @@
+//! Cumulative scans (prefix sums, products and maxima) along an axis.
+//!
+//! Backend implementers should implement [`Scan`].
+
+pub mod naive_cpu;
+pub mod parallel_cpu;
+
+use crate::storage::HasStorage;
+use crate::tensor::{Tensor2, Tensor3, Tensor4};
+use crate::tensor_ops::axis_in_range;
+use num_traits::{Bounded, Num};
+
+/// The combining operation of a scan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScanOp {
+    Sum,
+    Prod,
+    Max,
+}
+
+/// Variants of a scan.
+///
+/// An exclusive scan leaves the current element out, so its first output is
+/// the identity (`0`, `1`, or `T::min_value()` for [`ScanOp::Max`]). A reverse
+/// scan runs from the end of the axis towards the start.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScanOptions {
+    pub exclusive: bool,
+    pub reverse: bool,
+}
+
+/// Backend trait for scans along one axis of a tensor of logical shape `shape`.
+pub trait Scan<T: Copy + Default + Num + PartialOrd + Bounded>: Sized {
+    fn scan<const N: usize>(
+        op: ScanOp,
+        opts: ScanOptions,
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+
+    /// Gradient of [`Scan::scan`] with respect to its input.
+    ///
+    /// For [`ScanOp::Max`] the gradient of each output flows to the element
+    /// that produced it, the earliest one on ties.
+    fn scan_backward<const N: usize>(
+        op: ScanOp,
+        opts: ScanOptions,
+        input: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+}
+
+macro_rules! impl_scan {
+    ($name:ident [$($d:ident),+], rank $rank:literal) => {
+        impl<T, $(const $d: usize,)+ B> $name<T, $($d,)+ B>
+        where
+            T: Copy + Default + Num + PartialOrd + Bounded,
+            B: Scan<T> + HasStorage<T, { impl_scan!(@prod $($d),+) }>,
+            [(); impl_scan!(@prod $($d),+)]:,
+        {
+            /// Scans along `AXIS` with `op`.
+            #[inline]
+            pub fn scan<const AXIS: usize>(self, op: ScanOp, opts: ScanOptions) -> Self
+            where
+                [(); axis_in_range(AXIS, $rank)]:,
+            {
+                let mut out =
+                    <B as HasStorage<T, { impl_scan!(@prod $($d),+) }>>::storage_uninit();
+                B::scan::<{ impl_scan!(@prod $($d),+) }>(
+                    op,
+                    opts,
+                    &self.storage,
+                    &[$($d),+],
+                    AXIS,
+                    &mut out,
+                );
+                Self {
+                    storage: out,
+                    _p: core::marker::PhantomData,
+                }
+            }
+
+            /// Gradient of [`Self::scan`] with respect to `input`.
+            #[inline]
+            pub fn scan_backward<const AXIS: usize>(
+                op: ScanOp,
+                opts: ScanOptions,
+                input: &Self,
+                grad_output: &Self,
+            ) -> Self
+            where
+                [(); axis_in_range(AXIS, $rank)]:,
+            {
+                let mut grad_input =
+                    <B as HasStorage<T, { impl_scan!(@prod $($d),+) }>>::storage_uninit();
+                B::scan_backward::<{ impl_scan!(@prod $($d),+) }>(
+                    op,
+                    opts,
+                    &input.storage,
+                    &grad_output.storage,
+                    &[$($d),+],
+                    AXIS,
+                    &mut grad_input,
+                );
+                Self {
+                    storage: grad_input,
+                    _p: core::marker::PhantomData,
+                }
+            }
+
+            /// Inclusive cumulative sum along `AXIS`.
+            #[inline]
+            pub fn cumsum<const AXIS: usize>(self) -> Self
+            where
+                [(); axis_in_range(AXIS, $rank)]:,
+            {
+                self.scan::<AXIS>(ScanOp::Sum, ScanOptions::default())
+            }
+
+            /// Inclusive cumulative product along `AXIS`.
+            #[inline]
+            pub fn cumprod<const AXIS: usize>(self) -> Self
+            where
+                [(); axis_in_range(AXIS, $rank)]:,
+            {
+                self.scan::<AXIS>(ScanOp::Prod, ScanOptions::default())
+            }
+
+            /// Inclusive running maximum along `AXIS`.
+            #[inline]
+            pub fn cummax<const AXIS: usize>(self) -> Self
+            where
+                [(); axis_in_range(AXIS, $rank)]:,
+            {
+                self.scan::<AXIS>(ScanOp::Max, ScanOptions::default())
+            }
+        }
+    };
+
+    (@prod $first:expr $(,$rest:expr)+) => { $first * impl_scan!(@prod $($rest),+) };
+    (@prod $only:expr) => { $only };
+}
+
+impl_scan!(Tensor2 [R, C], rank 2);
+impl_scan!(Tensor3 [D0, D1, D2], rank 3);
+impl_scan!(Tensor4 [D0, D1, D2, D3], rank 4);
@@ -0,0 +1,277 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::tensor_ops::axis_lines;
+use crate::tensor_ops::scan::{Scan, ScanOp, ScanOptions};
+use num_traits::{Bounded, Num};
+
+/// Position of the `k`-th element visited along a line of length `len`.
+#[inline]
+fn visit(k: usize, len: usize, reverse: bool) -> usize {
+    if reverse { len - 1 - k } else { k }
+}
+
+fn identity<T: Num + Bounded>(op: ScanOp) -> T {
+    match op {
+        ScanOp::Sum => T::zero(),
+        ScanOp::Prod => T::one(),
+        ScanOp::Max => T::min_value(),
+    }
+}
+
+fn combine<T: Copy + Num + PartialOrd>(op: ScanOp, acc: T, x: T) -> T {
+    match op {
+        ScanOp::Sum => acc + x,
+        ScanOp::Prod => acc * x,
+        ScanOp::Max => {
+            if x > acc {
+                x
+            } else {
+                acc
+            }
+        }
+    }
+}
+
+fn scan_into<T>(
+    op: ScanOp,
+    opts: ScanOptions,
+    src: &[T],
+    shape: &[usize],
+    axis: usize,
+    dst: &mut [T],
+) where
+    T: Copy + Num + PartialOrd + Bounded,
+{
+    let (outer, len, inner) = axis_lines(shape, axis);
+    for o in 0..outer {
+        for i in 0..inner {
+            let at = |p: usize| (o * len + p) * inner + i;
+            let mut acc = identity::<T>(op);
+            for k in 0..len {
+                let idx = at(visit(k, len, opts.reverse));
+                let next = combine(op, acc, src[idx]);
+                dst[idx] = if opts.exclusive { acc } else { next };
+                acc = next;
+            }
+        }
+    }
+}
+
+impl<T> Scan<T> for NaiveCpu
+where
+    T: Copy + Default + Num + PartialOrd + Bounded,
+{
+    fn scan<const N: usize>(
+        op: ScanOp,
+        opts: ScanOptions,
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        scan_into(op, opts, src, shape, axis, dst);
+    }
+
+    fn scan_backward<const N: usize>(
+        op: ScanOp,
+        opts: ScanOptions,
+        input: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let x = <Self as HasStorage<T, N>>::as_slice(input);
+        let g = <Self as HasStorage<T, N>>::as_slice(grad_output);
+        let dx = <Self as HasStorage<T, N>>::as_mut_slice(grad_input);
+
+        if op == ScanOp::Sum {
+            // Each output sums the inputs before it, so each input receives
+            // the sum of the gradients after it: the same scan run backwards.
+            let flipped = ScanOptions {
+                reverse: !opts.reverse,
+                ..opts
+            };
+            scan_into(ScanOp::Sum, flipped, g, shape, axis, dx);
+            return;
+        }
+
+        let (outer, len, inner) = axis_lines(shape, axis);
+        for o in 0..outer {
+            for i in 0..inner {
+                let at = |k: usize| (o * len + visit(k, len, opts.reverse)) * inner + i;
+                match op {
+                    ScanOp::Sum => unreachable!("handled above"),
+                    ScanOp::Prod => {
+                        // In visiting order, dx[q] = prod(x[..q]) * s[q], where
+                        // s[q] sums g[k] * prod(x[q + 1..=end]) over the outputs
+                        // k whose product runs to some `end >= q`. Backwards,
+                        // s[q] = x[q + 1] * s[q + 1] + (gradient of the output
+                        // ending at q). Nothing is divided, so zeros are exact.
+                        let ends_at = |q: usize| {
+                            let k = if opts.exclusive { q + 1 } else { q };
+                            if k < len { g[at(k)] } else { T::zero() }
+                        };
+                        let mut s = T::zero();
+                        for q in (0..len).rev() {
+                            if q + 1 < len {
+                                s = s * x[at(q + 1)];
+                            }
+                            s = s + ends_at(q);
+                            dx[at(q)] = s;
+                        }
+                        let mut prefix = T::one();
+                        for q in 0..len {
+                            dx[at(q)] = prefix * dx[at(q)];
+                            prefix = prefix * x[at(q)];
+                        }
+                    }
+                    ScanOp::Max => {
+                        for q in 0..len {
+                            dx[at(q)] = T::zero();
+                        }
+                        let mut best: Option<usize> = None;
+                        for k in 0..len {
+                            if opts.exclusive
+                                && let Some(b) = best
+                            {
+                                dx[at(b)] = dx[at(b)] + g[at(k)];
+                            }
+                            if best.is_none_or(|b| x[at(k)] > x[at(b)]) {
+                                best = Some(k);
+                            }
+                            if !opts.exclusive {
+                                let b = best.unwrap();
+                                dx[at(b)] = dx[at(b)] + g[at(k)];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Tensor2, Tensor3, Tensor4};
+
+    fn assert_close(got: &[f64], want: &[f64]) {
+        for (g, w) in got.iter().zip(want) {
+            assert!((g - w).abs() < 1e-9, "got {got:?}, want {want:?}");
+        }
+    }
+
+    const EXCLUSIVE: ScanOptions = ScanOptions {
+        exclusive: true,
+        reverse: false,
+    };
+    const REVERSE: ScanOptions = ScanOptions {
+        exclusive: false,
+        reverse: true,
+    };
+
+    #[test]
+    fn test_inclusive_scans_any_axis() {
+        let t = Tensor2::<i32, 2, 3, NaiveCpu>::new([1, 2, 3, 4, 5, 6]);
+        assert_eq!(t.cumsum::<1>().as_slice(), &[1, 3, 6, 4, 9, 15]);
+        assert_eq!(t.cumsum::<0>().as_slice(), &[1, 2, 3, 5, 7, 9]);
+        assert_eq!(t.cumprod::<1>().as_slice(), &[1, 2, 6, 4, 20, 120]);
+
+        let m = Tensor3::<f64, 1, 4, 1, NaiveCpu>::new([3.0, 1.0, 4.0, 1.0]);
+        assert_close(m.cummax::<1>().as_slice(), &[3.0, 3.0, 4.0, 4.0]);
+
+        let t4 = Tensor4::<i32, 2, 1, 1, 2, NaiveCpu>::new([1, 2, 3, 4]);
+        assert_eq!(t4.cumsum::<0>().as_slice(), &[1, 2, 4, 6]);
+        assert_eq!(t4.cumsum::<3>().as_slice(), &[1, 3, 3, 7]);
+    }
+
+    #[test]
+    fn test_exclusive_and_reverse_scans() {
+        let t = Tensor2::<i32, 1, 4, NaiveCpu>::new([1, 2, 3, 4]);
+        assert_eq!(t.scan::<1>(ScanOp::Sum, EXCLUSIVE).as_slice(), &[0, 1, 3, 6]);
+        assert_eq!(t.scan::<1>(ScanOp::Prod, EXCLUSIVE).as_slice(), &[1, 1, 2, 6]);
+        assert_eq!(t.scan::<1>(ScanOp::Sum, REVERSE).as_slice(), &[10, 9, 7, 4]);
+        let both = ScanOptions {
+            exclusive: true,
+            reverse: true,
+        };
+        assert_eq!(t.scan::<1>(ScanOp::Sum, both).as_slice(), &[9, 7, 4, 0]);
+        assert_eq!(
+            t.scan::<1>(ScanOp::Max, EXCLUSIVE).as_slice(),
+            &[i32::MIN, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_scan_backward() {
+        type T = Tensor2<f64, 1, 4, NaiveCpu>;
+        let x = T::new([2.0, 0.0, 3.0, 1.0]);
+        let g = T::new([1.0, 1.0, 1.0, 1.0]);
+
+        let dx = T::scan_backward::<1>(ScanOp::Sum, ScanOptions::default(), &x, &g);
+        assert_close(dx.as_slice(), &[4.0, 3.0, 2.0, 1.0]);
+        let dx = T::scan_backward::<1>(ScanOp::Sum, REVERSE, &x, &g);
+        assert_close(dx.as_slice(), &[1.0, 2.0, 3.0, 4.0]);
+
+        // cumprod = [2, 0, 0, 0]; the zero input still gets a gradient.
+        let dx = T::scan_backward::<1>(ScanOp::Prod, ScanOptions::default(), &x, &g);
+        assert_close(dx.as_slice(), &[1.0, 2.0 + 6.0 + 6.0, 0.0, 0.0]);
+
+        // cummax = [2, 2, 3, 3]; ties go to the earliest maximum.
+        let dx = T::scan_backward::<1>(ScanOp::Max, ScanOptions::default(), &x, &g);
+        assert_close(dx.as_slice(), &[2.0, 0.0, 2.0, 0.0]);
+        let dx = T::scan_backward::<1>(ScanOp::Max, EXCLUSIVE, &x, &g);
+        assert_close(dx.as_slice(), &[2.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_cumprod_backward_with_zeros_matches_brute_force() {
+        type T = Tensor2<i64, 1, 7, NaiveCpu>;
+        let xs = [3, 0, -2, 5, 0, 4, -1];
+        let gs = [1, -2, 3, 1, 2, -1, 4];
+        for opts in [ScanOptions::default(), EXCLUSIVE, REVERSE] {
+            let dx = T::scan_backward::<1>(ScanOp::Prod, opts, &T::new(xs), &T::new(gs));
+            // Visiting order is the storage order unless reversed.
+            let at = |k: usize| if opts.reverse { 6 - k } else { k };
+            for q in 0..7 {
+                let mut want = 0;
+                for k in 0..7usize {
+                    let end = if opts.exclusive { k.checked_sub(1) } else { Some(k) };
+                    let Some(end) = end.filter(|&e| e >= q) else { continue };
+                    let partial: i64 =
+                        (0..=end).filter(|&p| p != q).map(|p| xs[at(p)]).product();
+                    want += gs[at(k)] * partial;
+                }
+                assert_eq!(dx.as_slice()[at(q)], want, "{opts:?}, q = {q}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_cumprod_backward_matches_finite_differences() {
+        type T = Tensor3<f64, 2, 3, 1, NaiveCpu>;
+        let xs = [0.5, -1.5, 2.0, 1.2, 0.7, -0.3];
+        let g = T::new([0.3, -0.2, 1.0, 0.4, 0.9, -1.1]);
+        let loss = |v: [f64; 6]| -> f64 {
+            let y = T::new(v).cumprod::<1>();
+            y.as_slice().iter().zip(g.as_slice()).map(|(a, b)| a * b).sum()
+        };
+        let dx = T::scan_backward::<1>(ScanOp::Prod, ScanOptions::default(), &T::new(xs), &g);
+        let h = 1e-6;
+        for j in 0..6 {
+            let (mut up, mut down) = (xs, xs);
+            up[j] += h;
+            down[j] -= h;
+            let numeric = (loss(up) - loss(down)) / (2.0 * h);
+            assert!((numeric - dx.as_slice()[j]).abs() < 1e-6);
+        }
+    }
+}
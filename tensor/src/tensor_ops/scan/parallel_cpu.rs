@@ -0,0 +1,53 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::scan::{Scan, ScanOp, ScanOptions};
+use num_traits::{Bounded, Num};
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> Scan<T> for ParallelCpu
+where
+    T: Copy + Default + Num + PartialOrd + Bounded,
+{
+    fn scan<const N: usize>(
+        op: ScanOp,
+        opts: ScanOptions,
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Scan<T>>::scan::<N>(
+            op,
+            opts,
+            naive::<T, N>(a),
+            shape,
+            axis,
+            naive_mut::<T, N>(out),
+        )
+    }
+
+    fn scan_backward<const N: usize>(
+        op: ScanOp,
+        opts: ScanOptions,
+        input: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Scan<T>>::scan_backward::<N>(
+            op,
+            opts,
+            naive::<T, N>(input),
+            naive::<T, N>(grad_output),
+            shape,
+            axis,
+            naive_mut::<T, N>(grad_input),
+        )
+    }
+}
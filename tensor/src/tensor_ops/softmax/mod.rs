@@ -0,0 +1,125 @@
+//! Softmax and log-softmax along an axis.
+//!
+//! Both subtract the per-line maximum before exponentiating, so large logits
+//! do not overflow. Backend implementers should implement [`Softmax`].
+
+pub mod naive_cpu;
+pub mod parallel_cpu;
+
+use crate::storage::HasStorage;
+use crate::tensor::{Tensor1, Tensor2, Tensor3, Tensor4};
+use crate::tensor_ops::axis_in_range;
+use num_traits::Float;
+
+/// Backend trait for softmax along one axis of a tensor of logical shape
+/// `shape`.
+///
+/// The backward passes take the forward *output*, which is all the gradient
+/// needs: `dx = y * (dy - sum(dy * y))` for softmax and
+/// `dx = dy - exp(y) * sum(dy)` for log-softmax.
+pub trait Softmax<T: Float + Default>: Sized {
+    fn softmax<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+
+    fn log_softmax<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+
+    fn softmax_backward<const N: usize>(
+        output: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+
+    fn log_softmax_backward<const N: usize>(
+        output: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+}
+
+macro_rules! impl_softmax {
+    ($name:ident [$($d:ident),+], rank $rank:literal) => {
+        impl<T, $(const $d: usize,)+ B> $name<T, $($d,)+ B>
+        where
+            T: Float + Default,
+            B: Softmax<T> + HasStorage<T, { impl_softmax!(@prod $($d),+) }>,
+            [(); impl_softmax!(@prod $($d),+)]:,
+        {
+            impl_softmax!(@forward softmax, "Softmax", $($d),+; $rank);
+            impl_softmax!(@forward log_softmax, "Log-softmax", $($d),+; $rank);
+            impl_softmax!(@backward softmax_backward, "[`Self::softmax`]", $($d),+; $rank);
+            impl_softmax!(
+                @backward log_softmax_backward, "[`Self::log_softmax`]", $($d),+; $rank
+            );
+        }
+    };
+
+    (@forward $method:ident, $doc:literal, $($d:ident),+; $rank:literal) => {
+        #[doc = concat!($doc, " along `AXIS`.")]
+        #[inline]
+        pub fn $method<const AXIS: usize>(self) -> Self
+        where
+            [(); axis_in_range(AXIS, $rank)]:,
+        {
+            let mut out =
+                <B as HasStorage<T, { impl_softmax!(@prod $($d),+) }>>::storage_uninit();
+            B::$method::<{ impl_softmax!(@prod $($d),+) }>(
+                &self.storage,
+                &[$($d),+],
+                AXIS,
+                &mut out,
+            );
+            Self {
+                storage: out,
+                _p: core::marker::PhantomData,
+            }
+        }
+    };
+
+    (@backward $method:ident, $doc:literal, $($d:ident),+; $rank:literal) => {
+        #[doc = concat!("Gradient of ", $doc, " along `AXIS`, given its output.")]
+        #[inline]
+        pub fn $method<const AXIS: usize>(output: &Self, grad_output: &Self) -> Self
+        where
+            [(); axis_in_range(AXIS, $rank)]:,
+        {
+            let mut grad_input =
+                <B as HasStorage<T, { impl_softmax!(@prod $($d),+) }>>::storage_uninit();
+            B::$method::<{ impl_softmax!(@prod $($d),+) }>(
+                &output.storage,
+                &grad_output.storage,
+                &[$($d),+],
+                AXIS,
+                &mut grad_input,
+            );
+            Self {
+                storage: grad_input,
+                _p: core::marker::PhantomData,
+            }
+        }
+    };
+
+    (@prod $first:expr $(,$rest:expr)+) => { $first * impl_softmax!(@prod $($rest),+) };
+    (@prod $only:expr) => { $only };
+}
+
+impl_softmax!(Tensor1 [N], rank 1);
+impl_softmax!(Tensor2 [R, C], rank 2);
+impl_softmax!(Tensor3 [D0, D1, D2], rank 3);
+impl_softmax!(Tensor4 [D0, D1, D2, D3], rank 4);
@@ -0,0 +1,187 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::tensor_ops::axis_lines;
+use crate::tensor_ops::softmax::Softmax;
+use num_traits::Float;
+
+/// Calls `f(at)` for every line along `axis`, where `at(p)` is the flat index
+/// of position `p` in the line.
+fn for_each_line(shape: &[usize], axis: usize, mut f: impl FnMut(&dyn Fn(usize) -> usize)) {
+    let (outer, len, inner) = axis_lines(shape, axis);
+    for o in 0..outer {
+        for i in 0..inner {
+            f(&|p| (o * len + p) * inner + i);
+        }
+    }
+}
+
+/// Writes `x - max` into `dst` for one line and returns `ln(sum(exp(x - max)))`.
+fn shifted_line<T: Float>(
+    src: &[T],
+    dst: &mut [T],
+    at: &dyn Fn(usize) -> usize,
+    len: usize,
+) -> T {
+    let max = (0..len).map(|p| src[at(p)]).fold(T::neg_infinity(), T::max);
+    let mut sum = T::zero();
+    for p in 0..len {
+        let shifted = src[at(p)] - max;
+        dst[at(p)] = shifted;
+        sum = sum + shifted.exp();
+    }
+    sum.ln()
+}
+
+impl<T> Softmax<T> for NaiveCpu
+where
+    T: Float + Default,
+{
+    fn softmax<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        let len = shape[axis];
+        for_each_line(shape, axis, |at| {
+            let log_sum = shifted_line(src, dst, at, len);
+            for p in 0..len {
+                dst[at(p)] = (dst[at(p)] - log_sum).exp();
+            }
+        });
+    }
+
+    fn log_softmax<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        let len = shape[axis];
+        for_each_line(shape, axis, |at| {
+            let log_sum = shifted_line(src, dst, at, len);
+            for p in 0..len {
+                dst[at(p)] = dst[at(p)] - log_sum;
+            }
+        });
+    }
+
+    fn softmax_backward<const N: usize>(
+        output: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let y = <Self as HasStorage<T, N>>::as_slice(output);
+        let g = <Self as HasStorage<T, N>>::as_slice(grad_output);
+        let dx = <Self as HasStorage<T, N>>::as_mut_slice(grad_input);
+        let len = shape[axis];
+        for_each_line(shape, axis, |at| {
+            let dot = (0..len).fold(T::zero(), |acc, p| acc + g[at(p)] * y[at(p)]);
+            for p in 0..len {
+                dx[at(p)] = y[at(p)] * (g[at(p)] - dot);
+            }
+        });
+    }
+
+    fn log_softmax_backward<const N: usize>(
+        output: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let y = <Self as HasStorage<T, N>>::as_slice(output);
+        let g = <Self as HasStorage<T, N>>::as_slice(grad_output);
+        let dx = <Self as HasStorage<T, N>>::as_mut_slice(grad_input);
+        let len = shape[axis];
+        for_each_line(shape, axis, |at| {
+            let sum = (0..len).fold(T::zero(), |acc, p| acc + g[at(p)]);
+            for p in 0..len {
+                dx[at(p)] = g[at(p)] - y[at(p)].exp() * sum;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Tensor1, Tensor2, Tensor3, Tensor4};
+
+    fn assert_close(got: &[f64], want: &[f64]) {
+        for (g, w) in got.iter().zip(want) {
+            assert!((g - w).abs() < 1e-9, "got {got:?}, want {want:?}");
+        }
+    }
+
+    #[test]
+    fn test_softmax_any_axis() {
+        let t = Tensor2::<f64, 2, 2, NaiveCpu>::new([0.0, 2f64.ln(), 1.0, 1.0]);
+        assert_close(t.softmax::<1>().as_slice(), &[1.0 / 3.0, 2.0 / 3.0, 0.5, 0.5]);
+        let e = 1f64.exp();
+        let by_column = [1.0 / (1.0 + e), 2.0 / (2.0 + e), e / (1.0 + e), e / (2.0 + e)];
+        assert_close(t.softmax::<0>().as_slice(), &by_column);
+
+        let v = Tensor1::<f64, 3, NaiveCpu>::new([1.0, 2.0, 3.0]);
+        let log_sum = (1f64.exp() + 2f64.exp() + 3f64.exp()).ln();
+        assert_close(
+            v.log_softmax::<0>().as_slice(),
+            &[1.0 - log_sum, 2.0 - log_sum, 3.0 - log_sum],
+        );
+
+        let t4 = Tensor4::<f64, 1, 3, 1, 2, NaiveCpu>::new([1.0, 5.0, 1.0, 5.0, 1.0, 5.0]);
+        assert_close(t4.softmax::<1>().as_slice(), &[1.0 / 3.0; 6]);
+    }
+
+    #[test]
+    fn test_large_logits_do_not_overflow() {
+        let t = Tensor2::<f32, 1, 3, NaiveCpu>::new([1000.0, 1000.0, -1000.0]);
+        assert_eq!(t.softmax::<1>().as_slice(), &[0.5, 0.5, 0.0]);
+        let log = t.log_softmax::<1>();
+        assert!((log.as_slice()[0] + 2f32.ln()).abs() < 1e-6);
+        assert!(log.as_slice()[2].is_finite());
+    }
+
+    #[test]
+    fn test_backward_matches_finite_differences() {
+        type T = Tensor3<f64, 2, 3, 1, NaiveCpu>;
+        let xs = [0.3, -1.2, 2.0, 0.5, 0.5, -0.7];
+        let g = T::new([0.4, -0.1, 1.3, 0.2, -0.9, 0.6]);
+        let dot = |y: T| -> f64 {
+            y.as_slice().iter().zip(g.as_slice()).map(|(a, b)| a * b).sum()
+        };
+
+        let y = T::new(xs).softmax::<1>();
+        let dx = T::softmax_backward::<1>(&y, &g);
+        let log_y = T::new(xs).log_softmax::<1>();
+        let log_dx = T::log_softmax_backward::<1>(&log_y, &g);
+
+        let h = 1e-6;
+        for j in 0..6 {
+            let (mut up, mut down) = (xs, xs);
+            up[j] += h;
+            down[j] -= h;
+            let numeric =
+                (dot(T::new(up).softmax::<1>()) - dot(T::new(down).softmax::<1>())) / (2.0 * h);
+            assert!((numeric - dx.as_slice()[j]).abs() < 1e-6);
+            let numeric = (dot(T::new(up).log_softmax::<1>())
+                - dot(T::new(down).log_softmax::<1>()))
+                / (2.0 * h);
+            assert!((numeric - log_dx.as_slice()[j]).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::softmax::Softmax;
+use num_traits::Float;
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> Softmax<T> for ParallelCpu
+where
+    T: Float + Default,
+{
+    fn softmax<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Softmax<T>>::softmax::<N>(
+            naive::<T, N>(a),
+            shape,
+            axis,
+            naive_mut::<T, N>(out),
+        )
+    }
+
+    fn log_softmax<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Softmax<T>>::log_softmax::<N>(
+            naive::<T, N>(a),
+            shape,
+            axis,
+            naive_mut::<T, N>(out),
+        )
+    }
+
+    fn softmax_backward<const N: usize>(
+        output: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Softmax<T>>::softmax_backward::<N>(
+            naive::<T, N>(output),
+            naive::<T, N>(grad_output),
+            shape,
+            axis,
+            naive_mut::<T, N>(grad_input),
+        )
+    }
+
+    fn log_softmax_backward<const N: usize>(
+        output: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Softmax<T>>::log_softmax_backward::<N>(
+            naive::<T, N>(output),
+            naive::<T, N>(grad_output),
+            shape,
+            axis,
+            naive_mut::<T, N>(grad_input),
+        )
+    }
+}
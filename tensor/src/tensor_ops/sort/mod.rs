@@ -0,0 +1,174 @@
+//! Sorting, argsort and top-k selection along an axis.
+//!
+//! All orderings are stable: equal elements keep their original relative
+//! order, so ties resolve to the lower index. NaNs sort last in both
+//! directions, so `topk` never returns a NaN ahead of a number. Backend
+//! implementers should implement [`Sort`].
+
+pub mod naive_cpu;
+pub mod parallel_cpu;
+
+use crate::storage::HasStorage;
+use crate::tensor::{Tensor2, Tensor3, Tensor4};
+use crate::tensor_ops::axis_in_range;
+
+/// Backend trait for sorting along one axis of a tensor of logical shape
+/// `shape`.
+pub trait Sort<T: Copy + Default + PartialOrd>: Sized {
+    fn sort_axis<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        descending: bool,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+
+    /// Positions along `axis` that would sort each line.
+    fn argsort_axis<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        descending: bool,
+        out: &mut <Self as HasStorage<usize, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<usize, N>;
+
+    /// The `k` largest elements of each line along `axis`, largest first,
+    /// with their positions. `M` is `N / shape[axis] * k`.
+    fn topk_axis<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        k: usize,
+        values: &mut <Self as HasStorage<T, M>>::Storage,
+        indices: &mut <Self as HasStorage<usize, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M> + HasStorage<usize, M>;
+}
+
+/// Size of output dimension `i` (of input size `d`) when axis `axis` of a
+/// rank-`rank` shape is cut down to its top `k` elements.
+///
+/// Panics (and therefore fails compilation when used in a type) if `axis` is
+/// out of range or `k` exceeds the axis length.
+pub const fn topk_dim(axis: usize, rank: usize, i: usize, d: usize, k: usize) -> usize {
+    assert!(axis < rank, "topk axis out of range");
+    if i == axis {
+        assert!(k <= d, "topk K exceeds the axis length");
+        k
+    } else {
+        d
+    }
+}
+
+macro_rules! impl_sort {
+    ($name:ident [$($d:ident),+], rank $rank:literal, topk [$($o:expr),+]) => {
+        impl<T, $(const $d: usize,)+ B> $name<T, $($d,)+ B>
+        where
+            T: Copy + Default + PartialOrd,
+            B: Sort<T> + HasStorage<T, { impl_sort!(@prod $($d),+) }>,
+            [(); impl_sort!(@prod $($d),+)]:,
+        {
+            /// Sorts every line along `AXIS`.
+            #[inline]
+            pub fn sort<const AXIS: usize>(self, descending: bool) -> Self
+            where
+                [(); axis_in_range(AXIS, $rank)]:,
+            {
+                let mut out =
+                    <B as HasStorage<T, { impl_sort!(@prod $($d),+) }>>::storage_uninit();
+                B::sort_axis::<{ impl_sort!(@prod $($d),+) }>(
+                    &self.storage,
+                    &[$($d),+],
+                    AXIS,
+                    descending,
+                    &mut out,
+                );
+                Self {
+                    storage: out,
+                    _p: core::marker::PhantomData,
+                }
+            }
+
+            /// Positions along `AXIS` that sort every line, so that
+            /// gathering `self` with them gives [`Self::sort`].
+            #[inline]
+            pub fn argsort<const AXIS: usize>(self, descending: bool) -> $name<usize, $($d,)+ B>
+            where
+                B: HasStorage<usize, { impl_sort!(@prod $($d),+) }>,
+                [(); axis_in_range(AXIS, $rank)]:,
+            {
+                let mut out =
+                    <B as HasStorage<usize, { impl_sort!(@prod $($d),+) }>>::storage_uninit();
+                B::argsort_axis::<{ impl_sort!(@prod $($d),+) }>(
+                    &self.storage,
+                    &[$($d),+],
+                    AXIS,
+                    descending,
+                    &mut out,
+                );
+                $name {
+                    storage: out,
+                    _p: core::marker::PhantomData,
+                }
+            }
+
+            /// The `K` largest elements along `AXIS`, largest first, and
+            /// their positions along `AXIS`. Ties go to the lower position.
+            #[inline]
+            pub fn topk<const K: usize, const AXIS: usize>(
+                self,
+            ) -> ($name<T, $({ $o },)+ B>, $name<usize, $({ $o },)+ B>)
+            where
+                B: HasStorage<T, { impl_sort!(@prod $($o),+) }>
+                    + HasStorage<usize, { impl_sort!(@prod $($o),+) }>,
+            {
+                let mut values =
+                    <B as HasStorage<T, { impl_sort!(@prod $($o),+) }>>::storage_uninit();
+                let mut indices =
+                    <B as HasStorage<usize, { impl_sort!(@prod $($o),+) }>>::storage_uninit();
+                B::topk_axis::<{ impl_sort!(@prod $($d),+) }, { impl_sort!(@prod $($o),+) }>(
+                    &self.storage,
+                    &[$($d),+],
+                    AXIS,
+                    K,
+                    &mut values,
+                    &mut indices,
+                );
+                (
+                    $name {
+                        storage: values,
+                        _p: core::marker::PhantomData,
+                    },
+                    $name {
+                        storage: indices,
+                        _p: core::marker::PhantomData,
+                    },
+                )
+            }
+        }
+    };
+
+    (@prod $first:expr $(,$rest:expr)+) => { $first * impl_sort!(@prod $($rest),+) };
+    (@prod $only:expr) => { $only };
+}
+
+impl_sort!(Tensor2 [R, C], rank 2, topk [topk_dim(AXIS, 2, 0, R, K), topk_dim(AXIS, 2, 1, C, K)]);
+impl_sort!(
+    Tensor3 [D0, D1, D2], rank 3,
+    topk [
+        topk_dim(AXIS, 3, 0, D0, K),
+        topk_dim(AXIS, 3, 1, D1, K),
+        topk_dim(AXIS, 3, 2, D2, K)
+    ]
+);
+impl_sort!(
+    Tensor4 [D0, D1, D2, D3], rank 4,
+    topk [
+        topk_dim(AXIS, 4, 0, D0, K),
+        topk_dim(AXIS, 4, 1, D1, K),
+        topk_dim(AXIS, 4, 2, D2, K),
+        topk_dim(AXIS, 4, 3, D3, K)
+    ]
+);
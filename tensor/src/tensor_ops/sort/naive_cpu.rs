@@ -0,0 +1,140 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::tensor_ops::sort::Sort;
+use crate::tensor_ops::{axis_lines, directed_cmp};
+
+/// Calls `f(o, i, order)` for every line `(o, i)` along `axis` (see
+/// [`axis_lines`]), where `order` lists the line's positions in sorted order.
+fn for_each_sorted_line<T: PartialOrd>(
+    src: &[T],
+    shape: &[usize],
+    axis: usize,
+    descending: bool,
+    mut f: impl FnMut(usize, usize, &[usize]),
+) {
+    let (outer, len, inner) = axis_lines(shape, axis);
+    let mut order: Vec<usize> = Vec::with_capacity(len);
+    for o in 0..outer {
+        for i in 0..inner {
+            let at = |p: usize| (o * len + p) * inner + i;
+            order.clear();
+            order.extend(0..len);
+            // `sort_by` is stable, so equal elements stay in position order
+            // in both directions. NaN goes last either way.
+            order.sort_by(|&p, &q| directed_cmp(&src[at(p)], &src[at(q)], descending));
+            f(o, i, &order);
+        }
+    }
+}
+
+impl<T> Sort<T> for NaiveCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn sort_axis<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        descending: bool,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        let (_, len, inner) = axis_lines(shape, axis);
+        for_each_sorted_line(src, shape, axis, descending, |o, i, order| {
+            let at = |p: usize| (o * len + p) * inner + i;
+            for (p, &q) in order.iter().enumerate() {
+                dst[at(p)] = src[at(q)];
+            }
+        });
+    }
+
+    fn argsort_axis<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        descending: bool,
+        out: &mut <Self as HasStorage<usize, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<usize, N>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<usize, N>>::as_mut_slice(out);
+        let (_, len, inner) = axis_lines(shape, axis);
+        for_each_sorted_line(src, shape, axis, descending, |o, i, order| {
+            for (p, &q) in order.iter().enumerate() {
+                dst[(o * len + p) * inner + i] = q;
+            }
+        });
+    }
+
+    fn topk_axis<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        k: usize,
+        values: &mut <Self as HasStorage<T, M>>::Storage,
+        indices: &mut <Self as HasStorage<usize, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M> + HasStorage<usize, M>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let vals = <Self as HasStorage<T, M>>::as_mut_slice(values);
+        let idxs = <Self as HasStorage<usize, M>>::as_mut_slice(indices);
+        let (_, len, inner) = axis_lines(shape, axis);
+        for_each_sorted_line(src, shape, axis, true, |o, i, order| {
+            // Output lines have length `k` instead of `len`.
+            for (p, &q) in order[..k].iter().enumerate() {
+                let dst = (o * k + p) * inner + i;
+                vals[dst] = src[(o * len + q) * inner + i];
+                idxs[dst] = q;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Tensor2, Tensor3, Tensor4};
+
+    #[test]
+    fn test_sort_and_argsort_any_axis() {
+        let t = Tensor2::<i32, 2, 3, NaiveCpu>::new([3, 1, 2, 0, 5, 4]);
+        assert_eq!(t.sort::<1>(false).as_slice(), &[1, 2, 3, 0, 4, 5]);
+        assert_eq!(t.sort::<1>(true).as_slice(), &[3, 2, 1, 5, 4, 0]);
+        assert_eq!(t.sort::<0>(false).as_slice(), &[0, 1, 2, 3, 5, 4]);
+        assert_eq!(t.argsort::<1>(false).as_slice(), &[1, 2, 0, 0, 2, 1]);
+        assert_eq!(t.argsort::<0>(true).as_slice(), &[0, 1, 1, 1, 0, 0]);
+
+        let t4 = Tensor4::<f32, 1, 2, 1, 2, NaiveCpu>::new([4.0, 1.0, 2.0, 3.0]);
+        assert_eq!(t4.sort::<1>(false).as_slice(), &[2.0, 1.0, 4.0, 3.0]);
+    }
+
+    #[test]
+    fn test_ties_are_stable_and_nan_sorts_last() {
+        let t = Tensor2::<f64, 1, 5, NaiveCpu>::new([2.0, f64::NAN, 1.0, 2.0, 1.0]);
+        assert_eq!(t.argsort::<1>(false).as_slice(), &[2, 4, 0, 3, 1]);
+        assert_eq!(t.argsort::<1>(true).as_slice(), &[0, 3, 2, 4, 1]);
+
+        // NaN is never the top element.
+        let (values, indices) = t.topk::<1, 1>();
+        assert_eq!(values.as_slice(), &[2.0]);
+        assert_eq!(indices.as_slice(), &[0]);
+    }
+
+    #[test]
+    fn test_topk() {
+        let logits = Tensor2::<f32, 2, 4, NaiveCpu>::new([0.1, 0.7, 0.2, 0.7, 5.0, 3.0, 4.0, 1.0]);
+        let (values, indices): (Tensor2<f32, 2, 2, NaiveCpu>, _) = logits.topk::<2, 1>();
+        assert_eq!(values.as_slice(), &[0.7, 0.7, 5.0, 4.0]);
+        assert_eq!(indices.as_slice(), &[1, 3, 0, 2]);
+
+        let t = Tensor3::<i32, 3, 1, 2, NaiveCpu>::new([1, 6, 3, 5, 2, 4]);
+        let (values, indices) = t.topk::<1, 0>();
+        assert_eq!(values.as_slice(), &[3, 6]);
+        assert_eq!(indices.as_slice(), &[1, 0]);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::sort::Sort;
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> Sort<T> for ParallelCpu
+where
+    T: Copy + Default + PartialOrd,
+{
+    fn sort_axis<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        descending: bool,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Sort<T>>::sort_axis::<N>(
+            naive::<T, N>(a),
+            shape,
+            axis,
+            descending,
+            naive_mut::<T, N>(out),
+        )
+    }
+
+    fn argsort_axis<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        descending: bool,
+        out: &mut <Self as HasStorage<usize, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<usize, N>,
+    {
+        <NaiveCpu as Sort<T>>::argsort_axis::<N>(
+            naive::<T, N>(a),
+            shape,
+            axis,
+            descending,
+            naive_mut::<usize, N>(out),
+        )
+    }
+
+    fn topk_axis<const N: usize, const M: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        shape: &[usize],
+        axis: usize,
+        k: usize,
+        values: &mut <Self as HasStorage<T, M>>::Storage,
+        indices: &mut <Self as HasStorage<usize, M>>::Storage,
+    ) where
+        Self: HasStorage<T, N> + HasStorage<T, M> + HasStorage<usize, M>,
+    {
+        <NaiveCpu as Sort<T>>::topk_axis::<N, M>(
+            naive::<T, N>(a),
+            shape,
+            axis,
+            k,
+            naive_mut::<T, M>(values),
+            naive_mut::<usize, M>(indices),
+        )
+    }
+}
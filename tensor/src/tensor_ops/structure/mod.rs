@@ -0,0 +1,239 @@
+//! Matrix structure ops: triangular masks, diagonals, traces and Kronecker
+//! products.
+//!
+//! Every kernel takes a leading `batch` count so the same backend entry point
+//! serves [`Tensor2`] and the batched [`Tensor3`] forms. Backend implementers
+//! should implement [`MatrixStructure`].
+
+pub mod naive_cpu;
+pub mod parallel_cpu;
+
+use crate::storage::HasStorage;
+use crate::tensor::{Tensor1, Tensor2, Tensor3};
+use crate::tensor_ops::linalg::min_dim;
+use core::ops::{Add, Mul};
+
+/// Backend trait for structure ops over `batch` `rows x cols` matrices laid
+/// out back to back. `NA`, `NB` and `NO` are storage lengths.
+pub trait MatrixStructure<T: Copy + Default + Add<Output = T> + Mul<Output = T>>: Sized {
+    /// Keeps `a[b, i, j]` where `j - i <= k` and zeroes the rest.
+    fn tril<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        k: isize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+
+    /// Keeps `a[b, i, j]` where `j - i >= k` and zeroes the rest.
+    fn triu<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        k: isize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+
+    /// `out[b, i] = a[b, i, i]` for `i < min(rows, cols)`.
+    fn diagonal<const NA: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NO>;
+
+    /// `out[b] = sum_i a[b, i, i]`.
+    fn trace<const NA: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NO>;
+
+    /// `out[b, i * rb + p, j * cb + q] = a[b, i, j] * x[b, p, q]` for an
+    /// `a_shape = [ra, ca]` and `x_shape = [rb, cb]`.
+    fn kron<const NA: usize, const NB: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        x: &<Self as HasStorage<T, NB>>::Storage,
+        batch: usize,
+        a_shape: [usize; 2],
+        x_shape: [usize; 2],
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>;
+}
+
+impl<T, const R: usize, const C: usize, B> Tensor2<T, R, C, B>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    B: MatrixStructure<T> + HasStorage<T, { R * C }>,
+{
+    /// Lower triangle on and below the `K`-th diagonal (`K > 0` is above the
+    /// main one); everything else is zeroed.
+    #[inline]
+    pub fn tril<const K: isize>(&self) -> Self {
+        let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::tril::<{ R * C }>(&self.storage, 1, R, C, K, &mut out);
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Upper triangle on and above the `K`-th diagonal (`K < 0` is below the
+    /// main one); everything else is zeroed.
+    #[inline]
+    pub fn triu<const K: isize>(&self) -> Self {
+        let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::triu::<{ R * C }>(&self.storage, 1, R, C, K, &mut out);
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// The main diagonal.
+    #[inline]
+    pub fn diagonal(&self) -> Tensor1<T, { min_dim(R, C) }, B>
+    where
+        B: HasStorage<T, { min_dim(R, C) }>,
+    {
+        let mut out = <B as HasStorage<T, { min_dim(R, C) }>>::storage_uninit();
+        B::diagonal::<{ R * C }, { min_dim(R, C) }>(&self.storage, 1, R, C, &mut out);
+        Tensor1 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Sum of the main diagonal.
+    #[inline]
+    pub fn trace(&self) -> T
+    where
+        B: HasStorage<T, 1>,
+    {
+        let mut out = <B as HasStorage<T, 1>>::storage_uninit();
+        B::trace::<{ R * C }, 1>(&self.storage, 1, R, C, &mut out);
+        <B as HasStorage<T, 1>>::as_slice(&out)[0]
+    }
+
+    /// Kronecker product `self ⊗ other`: block `(i, j)` is `self[i, j] * other`.
+    #[inline]
+    pub fn kron<const R2: usize, const C2: usize>(
+        &self,
+        other: &Tensor2<T, R2, C2, B>,
+    ) -> Tensor2<T, { R * R2 }, { C * C2 }, B>
+    where
+        B: HasStorage<T, { R2 * C2 }> + HasStorage<T, { (R * R2) * (C * C2) }>,
+    {
+        let mut out = <B as HasStorage<T, { (R * R2) * (C * C2) }>>::storage_uninit();
+        B::kron::<{ R * C }, { R2 * C2 }, { (R * R2) * (C * C2) }>(
+            &self.storage,
+            &other.storage,
+            1,
+            [R, C],
+            [R2, C2],
+            &mut out,
+        );
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, const BATCH: usize, const R: usize, const C: usize, B> Tensor3<T, BATCH, R, C, B>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    B: MatrixStructure<T> + HasStorage<T, { BATCH * (R * C) }>,
+{
+    /// [`Tensor2::tril`] of every matrix in the batch.
+    #[inline]
+    pub fn tril<const K: isize>(&self) -> Self {
+        let mut out = <B as HasStorage<T, { BATCH * (R * C) }>>::storage_uninit();
+        B::tril::<{ BATCH * (R * C) }>(&self.storage, BATCH, R, C, K, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// [`Tensor2::triu`] of every matrix in the batch.
+    #[inline]
+    pub fn triu<const K: isize>(&self) -> Self {
+        let mut out = <B as HasStorage<T, { BATCH * (R * C) }>>::storage_uninit();
+        B::triu::<{ BATCH * (R * C) }>(&self.storage, BATCH, R, C, K, &mut out);
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// The main diagonal of every matrix in the batch, one per row.
+    #[inline]
+    pub fn diagonal(&self) -> Tensor2<T, BATCH, { min_dim(R, C) }, B>
+    where
+        B: HasStorage<T, { BATCH * min_dim(R, C) }>,
+    {
+        let mut out = <B as HasStorage<T, { BATCH * min_dim(R, C) }>>::storage_uninit();
+        B::diagonal::<{ BATCH * (R * C) }, { BATCH * min_dim(R, C) }>(
+            &self.storage,
+            BATCH,
+            R,
+            C,
+            &mut out,
+        );
+        Tensor2 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// The trace of every matrix in the batch.
+    #[inline]
+    pub fn trace(&self) -> Tensor1<T, BATCH, B>
+    where
+        B: HasStorage<T, BATCH>,
+    {
+        let mut out = <B as HasStorage<T, BATCH>>::storage_uninit();
+        B::trace::<{ BATCH * (R * C) }, BATCH>(&self.storage, BATCH, R, C, &mut out);
+        Tensor1 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// Batch-wise Kronecker products: `out[b] = self[b] ⊗ other[b]`.
+    #[inline]
+    pub fn kron<const R2: usize, const C2: usize>(
+        &self,
+        other: &Tensor3<T, BATCH, R2, C2, B>,
+    ) -> Tensor3<T, BATCH, { R * R2 }, { C * C2 }, B>
+    where
+        B: HasStorage<T, { BATCH * (R2 * C2) }>
+            + HasStorage<T, { BATCH * ((R * R2) * (C * C2)) }>,
+    {
+        let mut out =
+            <B as HasStorage<T, { BATCH * ((R * R2) * (C * C2)) }>>::storage_uninit();
+        B::kron::<{ BATCH * (R * C) }, { BATCH * (R2 * C2) }, { BATCH * ((R * R2) * (C * C2)) }>(
+            &self.storage,
+            &other.storage,
+            BATCH,
+            [R, C],
+            [R2, C2],
+            &mut out,
+        );
+        Tensor3 {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+}
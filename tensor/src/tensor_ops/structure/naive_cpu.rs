@@ -0,0 +1,165 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::tensor_ops::linalg::min_dim;
+use crate::tensor_ops::structure::MatrixStructure;
+use core::ops::{Add, Mul};
+
+/// Copies `a` into `out`, zeroing entries whose diagonal offset `j - i`
+/// fails `keep`.
+fn mask<T: Copy + Default>(
+    a: &[T],
+    out: &mut [T],
+    rows: usize,
+    cols: usize,
+    keep: impl Fn(isize) -> bool,
+) {
+    for (src, dst) in a.chunks_exact(rows * cols).zip(out.chunks_exact_mut(rows * cols)) {
+        for (e, (&s, d)) in src.iter().zip(dst.iter_mut()).enumerate() {
+            let offset = (e % cols) as isize - (e / cols) as isize;
+            *d = if keep(offset) { s } else { T::default() };
+        }
+    }
+}
+
+impl<T> MatrixStructure<T> for NaiveCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    fn tril<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        k: isize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let a = <Self as HasStorage<T, N>>::as_slice(a);
+        let out = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        mask(&a[..batch * rows * cols], out, rows, cols, |d| d <= k);
+    }
+
+    fn triu<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        k: isize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let a = <Self as HasStorage<T, N>>::as_slice(a);
+        let out = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        mask(&a[..batch * rows * cols], out, rows, cols, |d| d >= k);
+    }
+
+    fn diagonal<const NA: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NO>,
+    {
+        let a = <Self as HasStorage<T, NA>>::as_slice(a);
+        let out = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+        let len = min_dim(rows, cols);
+        for b in 0..batch {
+            for i in 0..len {
+                out[b * len + i] = a[(b * rows + i) * cols + i];
+            }
+        }
+    }
+
+    fn trace<const NA: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NO>,
+    {
+        let a = <Self as HasStorage<T, NA>>::as_slice(a);
+        let out = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+        for (b, o) in out[..batch].iter_mut().enumerate() {
+            *o = (0..min_dim(rows, cols))
+                .fold(T::default(), |acc, i| acc + a[(b * rows + i) * cols + i]);
+        }
+    }
+
+    fn kron<const NA: usize, const NB: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        x: &<Self as HasStorage<T, NB>>::Storage,
+        batch: usize,
+        a_shape: [usize; 2],
+        x_shape: [usize; 2],
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>,
+    {
+        let a = <Self as HasStorage<T, NA>>::as_slice(a);
+        let x = <Self as HasStorage<T, NB>>::as_slice(x);
+        let out = <Self as HasStorage<T, NO>>::as_mut_slice(out);
+        let [ra, ca] = a_shape;
+        let [rx, cx] = x_shape;
+        let out_cols = ca * cx;
+        for b in 0..batch {
+            let a = &a[b * ra * ca..(b + 1) * ra * ca];
+            let x = &x[b * rx * cx..(b + 1) * rx * cx];
+            let out = &mut out[b * ra * rx * out_cols..(b + 1) * ra * rx * out_cols];
+            for i in 0..ra {
+                for p in 0..rx {
+                    // Output row `i * rx + p` is row `p` of `x` scaled by each
+                    // entry of row `i` of `a` in turn.
+                    let row = &mut out[(i * rx + p) * out_cols..][..out_cols];
+                    for (j, chunk) in row.chunks_exact_mut(cx).enumerate() {
+                        let s = a[i * ca + j];
+                        for (o, &v) in chunk.iter_mut().zip(&x[p * cx..(p + 1) * cx]) {
+                            *o = s * v;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::naive_cpu::NaiveCpu;
+    use crate::tensor::{Tensor2, Tensor3};
+
+    #[test]
+    fn test_triangles_diagonal_and_trace() {
+        let a = Tensor2::<i32, 3, 4, NaiveCpu>::new([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+        assert_eq!(a.tril::<0>().as_slice(), &[1, 0, 0, 0, 5, 6, 0, 0, 9, 10, 11, 0]);
+        assert_eq!(a.tril::<-1>().as_slice(), &[0, 0, 0, 0, 5, 0, 0, 0, 9, 10, 0, 0]);
+        assert_eq!(a.triu::<1>().as_slice(), &[0, 2, 3, 4, 0, 0, 7, 8, 0, 0, 0, 12]);
+        assert_eq!(a.triu::<-1>().as_slice(), &[1, 2, 3, 4, 5, 6, 7, 8, 0, 10, 11, 12]);
+        assert_eq!(a.diagonal().as_slice(), &[1, 6, 11]);
+        assert_eq!(a.trace(), 18);
+
+        let batch = Tensor3::<i32, 2, 2, 2, NaiveCpu>::new([1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(batch.tril::<0>().as_slice(), &[1, 0, 3, 4, 5, 0, 7, 8]);
+        assert_eq!(batch.triu::<0>().as_slice(), &[1, 2, 0, 4, 5, 6, 0, 8]);
+        assert_eq!(batch.diagonal().as_slice(), &[1, 4, 5, 8]);
+        assert_eq!(batch.trace().as_slice(), &[5, 13]);
+    }
+
+    #[test]
+    fn test_kron() {
+        let a = Tensor2::<i32, 2, 2, NaiveCpu>::new([1, 2, 3, 4]);
+        let b = Tensor2::<i32, 1, 2, NaiveCpu>::new([0, 5]);
+        let k = a.kron(&b);
+        assert_eq!(k.as_slice(), &[0, 5, 0, 10, 0, 15, 0, 20]);
+
+        let x = Tensor3::<i32, 2, 1, 2, NaiveCpu>::new([1, -1, 2, 3]);
+        let y = Tensor3::<i32, 2, 2, 1, NaiveCpu>::new([1, 2, 10, 0]);
+        let k = x.kron(&y);
+        assert_eq!(k.as_slice(), &[1, -1, 2, -2, 20, 30, 0, 0]);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::structure::MatrixStructure;
+use core::ops::{Add, Mul};
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> MatrixStructure<T> for ParallelCpu
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    fn tril<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        k: isize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as MatrixStructure<T>>::tril::<N>(
+            naive::<T, N>(a),
+            batch,
+            rows,
+            cols,
+            k,
+            naive_mut::<T, N>(out),
+        )
+    }
+
+    fn triu<const N: usize>(
+        a: &<Self as HasStorage<T, N>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        k: isize,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as MatrixStructure<T>>::triu::<N>(
+            naive::<T, N>(a),
+            batch,
+            rows,
+            cols,
+            k,
+            naive_mut::<T, N>(out),
+        )
+    }
+
+    fn diagonal<const NA: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NO>,
+    {
+        <NaiveCpu as MatrixStructure<T>>::diagonal::<NA, NO>(
+            naive::<T, NA>(a),
+            batch,
+            rows,
+            cols,
+            naive_mut::<T, NO>(out),
+        )
+    }
+
+    fn trace<const NA: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        batch: usize,
+        rows: usize,
+        cols: usize,
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NO>,
+    {
+        <NaiveCpu as MatrixStructure<T>>::trace::<NA, NO>(
+            naive::<T, NA>(a),
+            batch,
+            rows,
+            cols,
+            naive_mut::<T, NO>(out),
+        )
+    }
+
+    fn kron<const NA: usize, const NB: usize, const NO: usize>(
+        a: &<Self as HasStorage<T, NA>>::Storage,
+        x: &<Self as HasStorage<T, NB>>::Storage,
+        batch: usize,
+        a_shape: [usize; 2],
+        x_shape: [usize; 2],
+        out: &mut <Self as HasStorage<T, NO>>::Storage,
+    ) where
+        Self: HasStorage<T, NA> + HasStorage<T, NB> + HasStorage<T, NO>,
+    {
+        <NaiveCpu as MatrixStructure<T>>::kron::<NA, NB, NO>(
+            naive::<T, NA>(a),
+            naive::<T, NB>(x),
+            batch,
+            a_shape,
+            x_shape,
+            naive_mut::<T, NO>(out),
+        )
+    }
+}
@@ -0,0 +1,551 @@
+//! Element-wise unary math functions and their derivatives.
+//!
+//! Every function is a small marker type implementing [`UnaryOp`], which
+//! describes the scalar function and its derivative. Backends only need a
+//! single kernel to support all of them.
+//!
+//! Backend implementers should implement [`Unary`].
+
+pub mod naive_cpu;
+pub mod parallel_cpu;
+
+use crate::storage::HasStorage;
+use crate::tensor::{Tensor2, Tensor3, Tensor4};
+use num_traits::Float;
+
+/// A scalar function applied element-wise, together with its derivative.
+pub trait UnaryOp<T>: Copy {
+    /// Returns `f(x)`.
+    fn apply(&self, x: T) -> T;
+
+    /// Returns `f'(x)`, evaluated at the original input `x`.
+    fn derivative(&self, x: T) -> T;
+}
+
+/// Backend trait for element-wise unary functions.
+pub trait Unary<T: Copy + Default>: Sized {
+    fn unary<Op: UnaryOp<T>, const N: usize>(
+        op: Op,
+        a: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+
+    fn unary_inplace<Op: UnaryOp<T>, const N: usize>(
+        op: Op,
+        a: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+
+    /// Computes `grad_input = grad_output * f'(input)`.
+    fn unary_backward<Op: UnaryOp<T>, const N: usize>(
+        op: Op,
+        input: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>;
+}
+
+#[inline]
+fn lit<T: Float>(v: f64) -> T {
+    T::from(v).unwrap()
+}
+
+#[inline]
+fn sigmoid<T: Float>(x: T) -> T {
+    T::one() / (T::one() + (-x).exp())
+}
+
+/// Hyperbolic tangent.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Tanh;
+
+impl<T: Float> UnaryOp<T> for Tanh {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        x.tanh()
+    }
+
+    #[inline]
+    fn derivative(&self, x: T) -> T {
+        let t = x.tanh();
+        T::one() - t * t
+    }
+}
+
+/// Logistic sigmoid, `1 / (1 + e^-x)`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sigmoid;
+
+impl<T: Float> UnaryOp<T> for Sigmoid {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        sigmoid(x)
+    }
+
+    #[inline]
+    fn derivative(&self, x: T) -> T {
+        let s = sigmoid(x);
+        s * (T::one() - s)
+    }
+}
+
+/// GELU, using the tanh approximation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Gelu;
+
+impl Gelu {
+    const SQRT_2_OVER_PI: f64 = 0.797_884_560_802_865_4;
+    const COEFF: f64 = 0.044_715;
+}
+
+impl<T: Float> UnaryOp<T> for Gelu {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        let inner = lit::<T>(Self::SQRT_2_OVER_PI) * (x + lit::<T>(Self::COEFF) * x * x * x);
+        lit::<T>(0.5) * x * (T::one() + inner.tanh())
+    }
+
+    #[inline]
+    fn derivative(&self, x: T) -> T {
+        let k = lit::<T>(Self::SQRT_2_OVER_PI);
+        let c = lit::<T>(Self::COEFF);
+        let half = lit::<T>(0.5);
+        let t = (k * (x + c * x * x * x)).tanh();
+        let d_inner = k * (T::one() + lit::<T>(3.0) * c * x * x);
+        half * (T::one() + t) + half * x * (T::one() - t * t) * d_inner
+    }
+}
+
+/// SiLU (swish), `x * sigmoid(x)`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Silu;
+
+impl<T: Float> UnaryOp<T> for Silu {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        x * sigmoid(x)
+    }
+
+    #[inline]
+    fn derivative(&self, x: T) -> T {
+        let s = sigmoid(x);
+        s * (T::one() + x * (T::one() - s))
+    }
+}
+
+/// Softplus, `ln(1 + e^x)`, computed without overflowing for large `x`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Softplus;
+
+impl<T: Float> UnaryOp<T> for Softplus {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        x.max(T::zero()) + (-x.abs()).exp().ln_1p()
+    }
+
+    #[inline]
+    fn derivative(&self, x: T) -> T {
+        sigmoid(x)
+    }
+}
+
+/// Square root.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sqrt;
+
+impl<T: Float> UnaryOp<T> for Sqrt {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        x.sqrt()
+    }
+
+    #[inline]
+    fn derivative(&self, x: T) -> T {
+        lit::<T>(0.5) / x.sqrt()
+    }
+}
+
+/// Reciprocal square root, `1 / sqrt(x)`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Rsqrt;
+
+impl<T: Float> UnaryOp<T> for Rsqrt {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        x.sqrt().recip()
+    }
+
+    #[inline]
+    fn derivative(&self, x: T) -> T {
+        lit::<T>(-0.5) * x.powf(lit::<T>(-1.5))
+    }
+}
+
+/// Absolute value. The derivative at `0` is taken as `0`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Abs;
+
+impl<T: Float> UnaryOp<T> for Abs {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        x.abs()
+    }
+
+    #[inline]
+    fn derivative(&self, x: T) -> T {
+        Sign.apply(x)
+    }
+}
+
+/// Negation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Neg;
+
+impl<T: Float> UnaryOp<T> for Neg {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        -x
+    }
+
+    #[inline]
+    fn derivative(&self, _x: T) -> T {
+        -T::one()
+    }
+}
+
+/// Sign: `-1`, `0` or `1`. NaN is propagated.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sign;
+
+impl<T: Float> UnaryOp<T> for Sign {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        if x > T::zero() {
+            T::one()
+        } else if x < T::zero() {
+            -T::one()
+        } else {
+            // Keeps NaN as NaN and maps both zeroes to zero.
+            x
+        }
+    }
+
+    #[inline]
+    fn derivative(&self, _x: T) -> T {
+        T::zero()
+    }
+}
+
+/// Sine.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sin;
+
+impl<T: Float> UnaryOp<T> for Sin {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        x.sin()
+    }
+
+    #[inline]
+    fn derivative(&self, x: T) -> T {
+        x.cos()
+    }
+}
+
+/// Cosine.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Cos;
+
+impl<T: Float> UnaryOp<T> for Cos {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        x.cos()
+    }
+
+    #[inline]
+    fn derivative(&self, x: T) -> T {
+        -x.sin()
+    }
+}
+
+/// Power with a fixed floating-point exponent, `x^p`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Powf<T>(pub T);
+
+impl<T: Float> UnaryOp<T> for Powf<T> {
+    #[inline]
+    fn apply(&self, x: T) -> T {
+        x.powf(self.0)
+    }
+
+    #[inline]
+    fn derivative(&self, x: T) -> T {
+        self.0 * x.powf(self.0 - T::one())
+    }
+}
+
+/// Named forward, in-place and backward methods for the parameterless ops.
+///
+/// Expects `Self::unary`, `Self::unary_` and `Self::unary_backward` to be in
+/// scope on the implementing tensor type.
+macro_rules! unary_methods {
+    ($($op:ident: $name:ident, $inplace:ident, $backward:ident;)+) => {
+        $(
+            #[doc = concat!("Applies [`", stringify!($op), "`] element-wise.")]
+            #[inline]
+            pub fn $name(self) -> Self {
+                self.unary($op)
+            }
+
+            #[doc = concat!("In-place variant of [`Self::", stringify!($name), "`].")]
+            #[inline]
+            pub fn $inplace(&mut self) {
+                self.unary_($op);
+            }
+
+            #[doc = concat!("Gradient of [`Self::", stringify!($name), "`] w.r.t. `input`.")]
+            #[inline]
+            pub fn $backward(input: &Self, grad_output: &Self) -> Self {
+                Self::unary_backward($op, input, grad_output)
+            }
+        )+
+
+        /// Raises every element to the power `p`.
+        #[inline]
+        pub fn powf(self, p: T) -> Self {
+            self.unary(Powf(p))
+        }
+
+        /// In-place variant of [`Self::powf`].
+        #[inline]
+        pub fn powf_(&mut self, p: T) {
+            self.unary_(Powf(p));
+        }
+
+        /// Gradient of [`Self::powf`] with respect to `input`.
+        #[inline]
+        pub fn powf_backward(input: &Self, grad_output: &Self, p: T) -> Self {
+            Self::unary_backward(Powf(p), input, grad_output)
+        }
+    };
+}
+
+macro_rules! all_unary_methods {
+    () => {
+        unary_methods! {
+            Tanh: tanh, tanh_, tanh_backward;
+            Sigmoid: sigmoid, sigmoid_, sigmoid_backward;
+            Gelu: gelu, gelu_, gelu_backward;
+            Silu: silu, silu_, silu_backward;
+            Softplus: softplus, softplus_, softplus_backward;
+            Sqrt: sqrt, sqrt_, sqrt_backward;
+            Rsqrt: rsqrt, rsqrt_, rsqrt_backward;
+            Abs: abs, abs_, abs_backward;
+            Sign: sign, sign_, sign_backward;
+            Sin: sin, sin_, sin_backward;
+            Cos: cos, cos_, cos_backward;
+        }
+
+        // The forward pass of negation is the `-` operator, see `impl_neg!`.
+
+        /// In-place negation.
+        #[inline]
+        pub fn neg_(&mut self) {
+            self.unary_(Neg);
+        }
+
+        /// Gradient of `-input`.
+        #[inline]
+        pub fn neg_backward(input: &Self, grad_output: &Self) -> Self {
+            Self::unary_backward(Neg, input, grad_output)
+        }
+    };
+}
+
+/// `core::ops::Neg` for owned and borrowed tensors, routed through [`Neg`].
+macro_rules! impl_neg {
+    (impl<[$($gen:tt)*]> $name:ident<$($arg:ident),+>, { $n:expr } where { $($bounds:tt)* }) => {
+        impl<$($gen)*> core::ops::Neg for $name<$($arg),+>
+        where
+            $($bounds)*
+        {
+            type Output = $name<$($arg),+>;
+
+            #[inline]
+            fn neg(self) -> Self::Output {
+                -&self
+            }
+        }
+
+        impl<'a, $($gen)*> core::ops::Neg for &'a $name<$($arg),+>
+        where
+            $($bounds)*
+        {
+            type Output = $name<$($arg),+>;
+
+            #[inline]
+            fn neg(self) -> Self::Output {
+                let mut out = <B as HasStorage<T, { $n }>>::storage_uninit();
+                B::unary::<Neg, { $n }>(Neg, &self.storage, &mut out);
+                $name {
+                    storage: out,
+                    _p: core::marker::PhantomData,
+                }
+            }
+        }
+    };
+}
+
+impl<T, const R: usize, const C: usize, B> Tensor2<T, R, C, B>
+where
+    T: Copy + Default + Float,
+    B: Unary<T> + HasStorage<T, { R * C }>,
+{
+    /// Applies `op` element-wise.
+    #[inline]
+    pub fn unary<Op: UnaryOp<T>>(self, op: Op) -> Self {
+        let mut out = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::unary::<Op, { R * C }>(op, &self.storage, &mut out);
+        Self {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// In-place variant of [`Self::unary`].
+    #[inline]
+    pub fn unary_<Op: UnaryOp<T>>(&mut self, op: Op) {
+        B::unary_inplace::<Op, { R * C }>(op, &mut self.storage);
+    }
+
+    /// Gradient of [`Self::unary`] with respect to `input`.
+    #[inline]
+    pub fn unary_backward<Op: UnaryOp<T>>(op: Op, input: &Self, grad_output: &Self) -> Self {
+        let mut grad_input = <B as HasStorage<T, { R * C }>>::storage_uninit();
+        B::unary_backward::<Op, { R * C }>(
+            op,
+            &input.storage,
+            &grad_output.storage,
+            &mut grad_input,
+        );
+        Self {
+            storage: grad_input,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    all_unary_methods!();
+}
+
+impl<T, const D0: usize, const D1: usize, const D2: usize, B> Tensor3<T, D0, D1, D2, B>
+where
+    T: Copy + Default + Float,
+    B: Unary<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+    [(); D0 * (D1 * D2)]:,
+{
+    /// Applies `op` element-wise.
+    #[inline]
+    pub fn unary<Op: UnaryOp<T>>(self, op: Op) -> Self {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::unary::<Op, { D0 * (D1 * D2) }>(op, &self.storage, &mut out);
+        Self {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// In-place variant of [`Self::unary`].
+    #[inline]
+    pub fn unary_<Op: UnaryOp<T>>(&mut self, op: Op) {
+        B::unary_inplace::<Op, { D0 * (D1 * D2) }>(op, &mut self.storage);
+    }
+
+    /// Gradient of [`Self::unary`] with respect to `input`.
+    #[inline]
+    pub fn unary_backward<Op: UnaryOp<T>>(op: Op, input: &Self, grad_output: &Self) -> Self {
+        let mut grad_input = <B as HasStorage<T, { D0 * (D1 * D2) }>>::storage_uninit();
+        B::unary_backward::<Op, { D0 * (D1 * D2) }>(
+            op,
+            &input.storage,
+            &grad_output.storage,
+            &mut grad_input,
+        );
+        Self {
+            storage: grad_input,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    all_unary_methods!();
+}
+
+impl<T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B>
+    Tensor4<T, D0, D1, D3, D4, B>
+where
+    T: Copy + Default + Float,
+    B: Unary<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+    [(); D0 * (D1 * (D3 * D4))]:,
+{
+    /// Applies `op` element-wise.
+    #[inline]
+    pub fn unary<Op: UnaryOp<T>>(self, op: Op) -> Self {
+        let mut out = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::unary::<Op, { D0 * (D1 * (D3 * D4)) }>(op, &self.storage, &mut out);
+        Self {
+            storage: out,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    /// In-place variant of [`Self::unary`].
+    #[inline]
+    pub fn unary_<Op: UnaryOp<T>>(&mut self, op: Op) {
+        B::unary_inplace::<Op, { D0 * (D1 * (D3 * D4)) }>(op, &mut self.storage);
+    }
+
+    /// Gradient of [`Self::unary`] with respect to `input`.
+    #[inline]
+    pub fn unary_backward<Op: UnaryOp<T>>(op: Op, input: &Self, grad_output: &Self) -> Self {
+        let mut grad_input = <B as HasStorage<T, { D0 * (D1 * (D3 * D4)) }>>::storage_uninit();
+        B::unary_backward::<Op, { D0 * (D1 * (D3 * D4)) }>(
+            op,
+            &input.storage,
+            &grad_output.storage,
+            &mut grad_input,
+        );
+        Self {
+            storage: grad_input,
+            _p: core::marker::PhantomData,
+        }
+    }
+
+    all_unary_methods!();
+}
+
+impl_neg!(
+    impl<[T, const R: usize, const C: usize, B]> Tensor2<T, R, C, B>, { R * C }
+    where {
+        T: Copy + Default + Float,
+        B: Unary<T> + HasStorage<T, { R * C }>,
+    }
+);
+impl_neg!(
+    impl<[T, const D0: usize, const D1: usize, const D2: usize, B]> Tensor3<T, D0, D1, D2, B>,
+    { D0 * (D1 * D2) }
+    where {
+        T: Copy + Default + Float,
+        B: Unary<T> + HasStorage<T, { D0 * (D1 * D2) }>,
+        [(); D0 * (D1 * D2)]:,
+    }
+);
+impl_neg!(
+    impl<[T, const D0: usize, const D1: usize, const D3: usize, const D4: usize, B]>
+    Tensor4<T, D0, D1, D3, D4, B>, { D0 * (D1 * (D3 * D4)) }
+    where {
+        T: Copy + Default + Float,
+        B: Unary<T> + HasStorage<T, { D0 * (D1 * (D3 * D4)) }>,
+        [(); D0 * (D1 * (D3 * D4))]:,
+    }
+);
@@ -0,0 +1,134 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::tensor_ops::unary::{Unary, UnaryOp};
+use core::ops::Mul;
+
+impl<T> Unary<T> for NaiveCpu
+where
+    T: Copy + Default + Mul<Output = T>,
+{
+    fn unary<Op: UnaryOp<T>, const N: usize>(
+        op: Op,
+        a: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let src = <Self as HasStorage<T, N>>::as_slice(a);
+        let dst = <Self as HasStorage<T, N>>::as_mut_slice(out);
+        for (d, &s) in dst.iter_mut().zip(src) {
+            *d = op.apply(s);
+        }
+    }
+
+    fn unary_inplace<Op: UnaryOp<T>, const N: usize>(
+        op: Op,
+        a: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        for v in <Self as HasStorage<T, N>>::as_mut_slice(a).iter_mut() {
+            *v = op.apply(*v);
+        }
+    }
+
+    fn unary_backward<Op: UnaryOp<T>, const N: usize>(
+        op: Op,
+        input: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        let input_data = <Self as HasStorage<T, N>>::as_slice(input);
+        let grad_out = <Self as HasStorage<T, N>>::as_slice(grad_output);
+        let grad_in = <Self as HasStorage<T, N>>::as_mut_slice(grad_input);
+
+        for i in 0..N {
+            grad_in[i] = grad_out[i] * op.derivative(input_data[i]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Tensor2, Tensor3, Tensor4};
+    use crate::tensor_ops::unary::{
+        Abs, Cos, Gelu, Neg, Powf, Rsqrt, Sigmoid, Sign, Silu, Sin, Softplus, Sqrt, Tanh,
+    };
+
+    fn assert_close(got: &[f64], want: &[f64]) {
+        for (g, w) in got.iter().zip(want) {
+            assert!((g - w).abs() < 1e-6, "got {got:?}, want {want:?}");
+        }
+    }
+
+    /// Compares `derivative` against a central finite difference of `apply`.
+    fn check_derivative<Op: UnaryOp<f64>>(op: Op, xs: &[f64]) {
+        let h = 1e-5;
+        for &x in xs {
+            let numeric = (op.apply(x + h) - op.apply(x - h)) / (2.0 * h);
+            let analytic = op.derivative(x);
+            assert!((numeric - analytic).abs() < 1e-6, "x = {x}: {numeric} vs {analytic}");
+        }
+    }
+
+    #[test]
+    fn test_forward_values() {
+        let t = Tensor2::<f64, 1, 3, NaiveCpu>::new([-1.0, 0.0, 2.0]);
+        let tanh = [-0.761_594_155_955_764_9, 0.0, 0.964_027_580_075_816_9];
+        assert_close(t.tanh().as_slice(), &tanh);
+        let sigmoid = [0.268_941_421_369_995_1, 0.5, 0.880_797_077_977_882_3];
+        assert_close(t.sigmoid().as_slice(), &sigmoid);
+        let softplus = [0.313_261_687_518_222_86, 2f64.ln(), 2.126_928_011_042_972_7];
+        assert_close(t.softplus().as_slice(), &softplus);
+        let gelu = [-0.158_808_009_391_723_24, 0.0, 1.954_597_694_087_775];
+        assert_close(t.gelu().as_slice(), &gelu);
+        assert_close(t.abs().as_slice(), &[1.0, 0.0, 2.0]);
+        assert_close((-t).as_slice(), &[1.0, 0.0, -2.0]);
+        assert_close((-&t).as_slice(), &[1.0, 0.0, -2.0]);
+        assert_close(t.sign().as_slice(), &[-1.0, 0.0, 1.0]);
+
+        let p = Tensor3::<f64, 1, 1, 2, NaiveCpu>::new([4.0, 9.0]);
+        assert_close(p.sqrt().as_slice(), &[2.0, 3.0]);
+        assert_close(p.rsqrt().as_slice(), &[0.5, 1.0 / 3.0]);
+        assert_close(p.powf(1.5).as_slice(), &[8.0, 27.0]);
+
+        // Softplus must not overflow for large inputs.
+        let big = Tensor2::<f32, 1, 1, NaiveCpu>::new([1000.0]);
+        assert_eq!(big.softplus().as_slice(), &[1000.0]);
+    }
+
+    #[test]
+    fn test_derivatives_match_finite_differences() {
+        let xs = [-2.5, -0.7, 0.3, 1.9];
+        check_derivative(Tanh, &xs);
+        check_derivative(Sigmoid, &xs);
+        check_derivative(Gelu, &xs);
+        check_derivative(Silu, &xs);
+        check_derivative(Softplus, &xs);
+        check_derivative(Abs, &xs);
+        check_derivative(Neg, &xs);
+        check_derivative(Sign, &xs);
+        check_derivative(Sin, &xs);
+        check_derivative(Cos, &xs);
+
+        let positive = [0.2, 1.0, 3.7];
+        check_derivative(Sqrt, &positive);
+        check_derivative(Rsqrt, &positive);
+        check_derivative(Powf(2.5), &positive);
+    }
+
+    #[test]
+    fn test_inplace_and_backward_tensor4() {
+        let x = Tensor4::<f64, 1, 1, 1, 3, NaiveCpu>::new([-1.0, 0.0, 1.0]);
+        let mut y = x;
+        y.silu_();
+        assert_close(y.as_slice(), x.silu().as_slice());
+
+        let g = Tensor4::<f64, 1, 1, 1, 3, NaiveCpu>::new([2.0, 2.0, 2.0]);
+        let dx = Tensor4::sin_backward(&x, &g);
+        assert_close(dx.as_slice(), &[2.0 * 1f64.cos(), 2.0, 2.0 * 1f64.cos()]);
+    }
+}
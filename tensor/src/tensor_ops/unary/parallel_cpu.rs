@@ -0,0 +1,46 @@
+use crate::storage::HasStorage;
+use crate::storage::naive_cpu::NaiveCpu;
+use crate::storage::parallel_cpu::{ParallelCpu, naive, naive_mut};
+use crate::tensor_ops::unary::{Unary, UnaryOp};
+use core::ops::Mul;
+
+// No parallel kernels yet: these run the NaiveCpu ones on the calling thread.
+impl<T> Unary<T> for ParallelCpu
+where
+    T: Copy + Default + Mul<Output = T>,
+{
+    fn unary<Op: UnaryOp<T>, const N: usize>(
+        op: Op,
+        a: &<Self as HasStorage<T, N>>::Storage,
+        out: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Unary<T>>::unary::<Op, N>(op, naive::<T, N>(a), naive_mut::<T, N>(out))
+    }
+
+    fn unary_inplace<Op: UnaryOp<T>, const N: usize>(
+        op: Op,
+        a: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Unary<T>>::unary_inplace::<Op, N>(op, naive_mut::<T, N>(a))
+    }
+
+    fn unary_backward<Op: UnaryOp<T>, const N: usize>(
+        op: Op,
+        input: &<Self as HasStorage<T, N>>::Storage,
+        grad_output: &<Self as HasStorage<T, N>>::Storage,
+        grad_input: &mut <Self as HasStorage<T, N>>::Storage,
+    ) where
+        Self: HasStorage<T, N>,
+    {
+        <NaiveCpu as Unary<T>>::unary_backward::<Op, N>(
+            op,
+            naive::<T, N>(input),
+            naive::<T, N>(grad_output),
+            naive_mut::<T, N>(grad_input),
+        )
+    }
+}